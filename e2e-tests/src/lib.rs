@@ -14,6 +14,7 @@ use std::process::{Child, Command, Stdio};
 use std::time::Duration;
 
 use corepc_node::Node;
+use electrsd::ElectrsD;
 use hex_conservative::DisplayHex;
 use ldk_server_client::client::LdkServerClient;
 use ldk_server_client::ldk_server_grpc::api::{GetNodeInfoRequest, GetNodeInfoResponse};
@@ -85,6 +86,25 @@ impl TestBitcoind {
 	}
 }
 
+/// Wrapper around a managed esplora (electrs) process indexing a [`TestBitcoind`].
+pub struct TestEsplora {
+	pub electrsd: ElectrsD,
+}
+
+impl TestEsplora {
+	pub fn new(bitcoind: &TestBitcoind) -> Self {
+		let exe = electrsd::exe_path().unwrap();
+		let mut conf = electrsd::Conf::default();
+		conf.http_enabled = true;
+		let electrsd = ElectrsD::with_conf(exe, &bitcoind.bitcoind, &conf).unwrap();
+		Self { electrsd }
+	}
+
+	pub fn server_url(&self) -> String {
+		format!("http://{}", self.electrsd.esplora_url.as_ref().unwrap())
+	}
+}
+
 /// Handle to a running ldk-server child process.
 pub struct LdkServerHandle {
 	child: Option<Child>,
@@ -116,8 +136,8 @@ pub struct TestServerParams {
 /// A chain source for the test config, mirroring the server's supported backends.
 pub enum ChainSource {
 	Bitcoind { rpc_address: String, rpc_user: String, rpc_password: String },
-	Electrum { server_url: String },
-	Esplora { server_url: String },
+	Electrum { server_url: String, chain_poll_interval_secs: Option<u64> },
+	Esplora { server_url: String, chain_poll_interval_secs: Option<u64> },
 }
 
 impl ChainSource {
@@ -128,16 +148,27 @@ impl ChainSource {
 				"[bitcoind]\nrpc_address = \"{}\"\nrpc_user = \"{}\"\nrpc_password = \"{}\"",
 				rpc_address, rpc_user, rpc_password
 			),
-			ChainSource::Electrum { server_url } => {
-				format!("[electrum]\nserver_url = \"{}\"", server_url)
-			},
-			ChainSource::Esplora { server_url } => {
-				format!("[esplora]\nserver_url = \"{}\"", server_url)
-			},
+			ChainSource::Electrum { server_url, chain_poll_interval_secs } => format!(
+				"[electrum]\nserver_url = \"{}\"{}",
+				server_url,
+				chain_poll_interval_toml(*chain_poll_interval_secs)
+			),
+			ChainSource::Esplora { server_url, chain_poll_interval_secs } => format!(
+				"[esplora]\nserver_url = \"{}\"{}",
+				server_url,
+				chain_poll_interval_toml(*chain_poll_interval_secs)
+			),
 		}
 	}
 }
 
+fn chain_poll_interval_toml(chain_poll_interval_secs: Option<u64>) -> String {
+	match chain_poll_interval_secs {
+		Some(secs) => format!("\nchain_poll_interval_secs = {}", secs),
+		None => String::new(),
+	}
+}
+
 /// Builder for the ldk-server config TOML used in tests.
 ///
 /// Tests tweak named, typed knobs and call [`TestConfigBuilder::build`] once to
@@ -740,7 +771,7 @@ pub async fn setup_funded_channel(
 			channel_amount_sats,
 			push_to_counterparty_msat: None,
 			channel_config: None,
-			announce_channel: true,
+			announce_channel: Some(true),
 			disable_counterparty_reserve: false,
 		})
 		.await
@@ -13,8 +13,8 @@ use std::time::Duration;
 
 use e2e_tests::{
 	find_available_port, mine_and_sync, run_cli, run_cli_raw, run_cli_with_config,
-	setup_funded_channel, wait_for_onchain_balance, wait_for_usable_channel, LdkServerConfig,
-	LdkServerHandle, TestBitcoind,
+	setup_funded_channel, wait_for_onchain_balance, wait_for_usable_channel, ChainSource,
+	LdkServerConfig, LdkServerHandle, TestBitcoind, TestConfigBuilder, TestEsplora,
 };
 use hex_conservative::{DisplayHex, FromHex};
 use ldk_node::bitcoin::hashes::{sha256, Hash};
@@ -23,9 +23,10 @@ use ldk_node::lightning::offers::offer::Offer;
 use ldk_node::lightning_invoice::Bolt11Invoice;
 use ldk_server_client::client::EventStream;
 use ldk_server_client::ldk_server_grpc::api::{
-	Bolt11ReceiveRequest, Bolt12ReceiveRequest, GetBalancesRequest, OnchainReceiveRequest,
-	OpenChannelRequest,
+	Bolt11ReceiveRequest, Bolt12ReceiveRequest, GetBalancesRequest, GetNodeInfoRequest,
+	GetNodeInfoResponse, OnchainReceiveRequest, OpenChannelRequest,
 };
+use ldk_server_client::ldk_server_grpc::endpoints::GET_NODE_INFO_PATH;
 use ldk_server_client::ldk_server_grpc::events::event_envelope::Event;
 use ldk_server_client::ldk_server_grpc::events::{
 	ChannelClosureInitiator, ChannelState, ChannelStateChangeReasonKind, EventEnvelope,
@@ -33,6 +34,7 @@ use ldk_server_client::ldk_server_grpc::events::{
 use ldk_server_client::ldk_server_grpc::types::{
 	bolt11_invoice_description, Bolt11InvoiceDescription,
 };
+use ldk_server_client::prost::Message;
 
 const EVENT_TIMEOUT: Duration = Duration::from_secs(15);
 
@@ -73,6 +75,17 @@ async fn test_cli_get_node_info_with_server_config() {
 	assert_eq!(output["node_id"], server.node_id());
 }
 
+#[tokio::test]
+async fn test_client_call_raw_returns_undecoded_response() {
+	let bitcoind = TestBitcoind::new();
+	let server = LdkServerHandle::start(&bitcoind).await;
+
+	let request = GetNodeInfoRequest {}.encode_to_vec();
+	let response = server.client().call_raw(GET_NODE_INFO_PATH, &request).await.unwrap();
+	let node_info = GetNodeInfoResponse::decode(response.as_slice()).unwrap();
+	assert_eq!(node_info.node_id, server.node_id());
+}
+
 #[tokio::test]
 async fn test_cli_onchain_receive() {
 	let bitcoind = TestBitcoind::new();
@@ -397,6 +410,41 @@ async fn test_cli_onchain_send_all() {
 	assert_eq!(balances.total_onchain_balance_sats, 0);
 }
 
+#[tokio::test]
+async fn test_esplora_picks_up_new_blocks_within_chain_poll_interval() {
+	// ldk-node's default background sync only polls every 30 seconds, so a block picked up
+	// within the configured 10 seconds (plus a few seconds for electrs to index it) shows the
+	// interval is applied.
+	let chain_poll_interval_secs = 10;
+	let bitcoind = TestBitcoind::new();
+	let esplora = TestEsplora::new(&bitcoind);
+	let server = LdkServerHandle::start_with_config(&bitcoind, |params| {
+		TestConfigBuilder::new(params)
+			.chain_source(ChainSource::Esplora {
+				server_url: esplora.server_url(),
+				chain_poll_interval_secs: Some(chain_poll_interval_secs),
+			})
+			.build()
+	})
+	.await;
+	mine_and_sync(&bitcoind, &[&server], 1).await;
+
+	bitcoind.mine_blocks(1);
+	let expected_height = bitcoind.bitcoind.client.get_block_count().unwrap().0 as u32;
+	let timeout = Duration::from_secs(chain_poll_interval_secs + 5);
+	let start = std::time::Instant::now();
+	loop {
+		let info = server.client().get_node_info(GetNodeInfoRequest {}).await.unwrap();
+		if info.current_best_block.map(|b| b.height).unwrap_or(0) >= expected_height {
+			break;
+		}
+		if start.elapsed() > timeout {
+			panic!("New block was not picked up within {:?}", timeout);
+		}
+		tokio::time::sleep(Duration::from_millis(500)).await;
+	}
+}
+
 #[tokio::test]
 async fn test_cli_connect_peer() {
 	let bitcoind = TestBitcoind::new();
@@ -483,7 +531,7 @@ async fn test_subscribe_events_channel_state_lifecycle_pending_ready_closed() {
 			channel_amount_sats: 100_000,
 			push_to_counterparty_msat: None,
 			channel_config: None,
-			announce_channel: true,
+			announce_channel: Some(true),
 			disable_counterparty_reserve: false,
 		})
 		.await
@@ -647,7 +695,7 @@ async fn test_subscribe_events_channel_state_lifecycle_pending_ready_force_close
 			channel_amount_sats: 100_000,
 			push_to_counterparty_msat: None,
 			channel_config: None,
-			announce_channel: true,
+			announce_channel: Some(true),
 			disable_counterparty_reserve: false,
 		})
 		.await
@@ -0,0 +1,297 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+//! Double-entry ledger export of payments and forwarding fees.
+//!
+//! Every succeeded payment and every forward that earned a fee is turned into a balanced
+//! transaction, i.e. the amounts of its postings sum to zero. Positive amounts are debits and
+//! negative amounts credits. Amounts are kept in millisatoshis so no precision is lost.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use clap::ValueEnum;
+use ldk_server_client::ldk_server_grpc::types::payment_kind::Kind;
+use ldk_server_client::ldk_server_grpc::types::{
+	ForwardedPayment, HtlcLocator, Payment, PaymentDirection, PaymentStatus,
+};
+
+const LIGHTNING_ACCOUNT: &str = "Assets:Lightning";
+const ONCHAIN_ACCOUNT: &str = "Assets:Onchain";
+const RECEIVED_ACCOUNT: &str = "Income:Payments";
+const ROUTING_ACCOUNT: &str = "Income:Routing";
+const SENT_ACCOUNT: &str = "Expenses:Payments";
+const FEES_ACCOUNT: &str = "Expenses:Fees";
+
+/// The commodity amounts are denominated in for Beancount.
+const BEANCOUNT_COMMODITY: &str = "MSAT";
+
+/// The output format of the `export-ledger` command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LedgerFormat {
+	/// One CSV row per posting, with separate debit and credit columns.
+	#[default]
+	Csv,
+	/// Beancount transactions, preceded by `open` directives for the accounts used.
+	Beancount,
+}
+
+/// A balanced ledger transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerEntry {
+	/// Seconds since the Unix epoch.
+	pub timestamp: u64,
+	/// The payment ID, or the channels a forward went through.
+	pub reference: String,
+	pub description: &'static str,
+	pub postings: Vec<Posting>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Posting {
+	pub account: &'static str,
+	/// The amount debited to the account if positive, or credited if negative.
+	pub amount_msat: i128,
+}
+
+/// Builds the ledger entries for the given payments and forwards, ordered by time.
+///
+/// Pending and failed payments did not move any funds and are left out, as are forwards that
+/// earned no fee or were recorded without a timestamp.
+pub fn ledger_entries(payments: &[Payment], forwards: &[ForwardedPayment]) -> Vec<LedgerEntry> {
+	let mut entries: Vec<LedgerEntry> = payments
+		.iter()
+		.filter_map(payment_entry)
+		.chain(forwards.iter().filter_map(forward_entry))
+		.collect();
+	entries.sort_by_key(|entry| entry.timestamp);
+	entries
+}
+
+fn payment_entry(payment: &Payment) -> Option<LedgerEntry> {
+	if payment.status != PaymentStatus::Succeeded as i32 {
+		return None;
+	}
+	let amount_msat = i128::from(payment.amount_msat?);
+	let is_onchain =
+		matches!(payment.kind.as_ref().and_then(|kind| kind.kind.as_ref()), Some(Kind::Onchain(_)));
+	let account = if is_onchain { ONCHAIN_ACCOUNT } else { LIGHTNING_ACCOUNT };
+
+	let (description, postings) = if payment.direction == PaymentDirection::Inbound as i32 {
+		let description =
+			if is_onchain { "Received on-chain payment" } else { "Received Lightning payment" };
+		let postings = vec![
+			Posting { account, amount_msat },
+			Posting { account: RECEIVED_ACCOUNT, amount_msat: -amount_msat },
+		];
+		(description, postings)
+	} else {
+		let description =
+			if is_onchain { "Sent on-chain payment" } else { "Sent Lightning payment" };
+		let fee_msat = i128::from(payment.fee_paid_msat.unwrap_or(0));
+		let mut postings = vec![Posting { account: SENT_ACCOUNT, amount_msat }];
+		if fee_msat > 0 {
+			postings.push(Posting { account: FEES_ACCOUNT, amount_msat: fee_msat });
+		}
+		postings.push(Posting { account, amount_msat: -(amount_msat + fee_msat) });
+		(description, postings)
+	};
+
+	Some(LedgerEntry {
+		timestamp: payment.latest_update_timestamp,
+		reference: payment.id.clone(),
+		description,
+		postings,
+	})
+}
+
+fn forward_entry(forward: &ForwardedPayment) -> Option<LedgerEntry> {
+	let fee_msat = i128::from(forward.total_fee_earned_msat.filter(|fee| *fee > 0)?);
+	Some(LedgerEntry {
+		timestamp: forward.forwarded_at?,
+		reference: format!(
+			"{}->{}",
+			channel_ids(&forward.prev_htlcs),
+			channel_ids(&forward.next_htlcs)
+		),
+		description: "Routing fee",
+		postings: vec![
+			Posting { account: LIGHTNING_ACCOUNT, amount_msat: fee_msat },
+			Posting { account: ROUTING_ACCOUNT, amount_msat: -fee_msat },
+		],
+	})
+}
+
+fn channel_ids(htlcs: &[HtlcLocator]) -> String {
+	htlcs.iter().map(|htlc| htlc.channel_id.as_str()).collect::<Vec<_>>().join("+")
+}
+
+/// Formats the entries as CSV, with one row per posting.
+pub fn format_csv(entries: &[LedgerEntry]) -> String {
+	let mut out =
+		String::from("date,timestamp,reference,description,account,debit_msat,credit_msat\n");
+	for entry in entries {
+		let date = format_date(entry.timestamp);
+		for posting in &entry.postings {
+			let (debit, credit) = if posting.amount_msat >= 0 {
+				(posting.amount_msat.to_string(), String::new())
+			} else {
+				(String::new(), (-posting.amount_msat).to_string())
+			};
+			writeln!(
+				out,
+				"{},{},{},{},{},{},{}",
+				date,
+				entry.timestamp,
+				entry.reference,
+				entry.description,
+				posting.account,
+				debit,
+				credit
+			)
+			.unwrap();
+		}
+	}
+	out
+}
+
+/// Formats the entries as a Beancount ledger, amounts denominated in millisatoshis.
+pub fn format_beancount(entries: &[LedgerEntry]) -> String {
+	let mut out = String::new();
+	let accounts: BTreeSet<&str> =
+		entries.iter().flat_map(|entry| entry.postings.iter().map(|p| p.account)).collect();
+	for account in &accounts {
+		writeln!(out, "1970-01-01 open {} {}", account, BEANCOUNT_COMMODITY).unwrap();
+	}
+
+	for entry in entries {
+		writeln!(out).unwrap();
+		writeln!(out, "{} * \"{}\"", format_date(entry.timestamp), entry.description).unwrap();
+		writeln!(out, "  reference: \"{}\"", entry.reference).unwrap();
+		for posting in &entry.postings {
+			writeln!(out, "  {}  {} {}", posting.account, posting.amount_msat, BEANCOUNT_COMMODITY)
+				.unwrap();
+		}
+	}
+	out
+}
+
+/// Formats a Unix timestamp as a `YYYY-MM-DD` date in UTC.
+fn format_date(timestamp: u64) -> String {
+	// Converts days since the epoch to a civil date, following Howard Hinnant's
+	// `civil_from_days` algorithm.
+	let days = (timestamp / 86_400) as i64 + 719_468;
+	let era = days.div_euclid(146_097);
+	let day_of_era = days.rem_euclid(146_097);
+	let year_of_era =
+		(day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+	let year = era * 400 + year_of_era + i64::from(month <= 2);
+	format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_client::ldk_server_grpc::types::{Bolt11, PaymentKind};
+
+	use super::*;
+
+	fn payment(
+		id: &str, direction: PaymentDirection, status: PaymentStatus, amount_msat: u64,
+		fee_paid_msat: Option<u64>,
+	) -> Payment {
+		Payment {
+			id: id.to_string(),
+			kind: Some(PaymentKind { kind: Some(Kind::Bolt11(Bolt11::default())) }),
+			amount_msat: Some(amount_msat),
+			fee_paid_msat,
+			direction: direction as i32,
+			status: status as i32,
+			latest_update_timestamp: 1_700_000_000,
+			fee_breakdown: None,
+		}
+	}
+
+	fn htlc(channel_id: &str) -> HtlcLocator {
+		HtlcLocator { channel_id: channel_id.to_string(), ..Default::default() }
+	}
+
+	#[test]
+	fn entries_are_balanced_with_fees_as_separate_expense() {
+		let payments = [
+			payment("in", PaymentDirection::Inbound, PaymentStatus::Succeeded, 50_000, None),
+			payment("out", PaymentDirection::Outbound, PaymentStatus::Succeeded, 20_000, Some(15)),
+			payment("pending", PaymentDirection::Outbound, PaymentStatus::Pending, 1_000, None),
+		];
+		let forwards = [
+			ForwardedPayment {
+				total_fee_earned_msat: Some(7),
+				prev_htlcs: vec![htlc("aa")],
+				next_htlcs: vec![htlc("bb")],
+				forwarded_at: Some(1_700_000_100),
+				..Default::default()
+			},
+			ForwardedPayment { total_fee_earned_msat: Some(3), ..Default::default() },
+		];
+
+		let entries = ledger_entries(&payments, &forwards);
+		assert_eq!(entries.len(), 3, "pending payments and undated forwards are left out");
+		for entry in &entries {
+			assert_eq!(entry.postings.iter().map(|p| p.amount_msat).sum::<i128>(), 0);
+		}
+
+		let sent = entries.iter().find(|entry| entry.reference == "out").unwrap();
+		assert_eq!(
+			sent.postings,
+			vec![
+				Posting { account: SENT_ACCOUNT, amount_msat: 20_000 },
+				Posting { account: FEES_ACCOUNT, amount_msat: 15 },
+				Posting { account: LIGHTNING_ACCOUNT, amount_msat: -20_015 },
+			]
+		);
+
+		let forward = entries.last().unwrap();
+		assert_eq!(forward.reference, "aa->bb");
+		assert_eq!(forward.postings[1], Posting { account: ROUTING_ACCOUNT, amount_msat: -7 });
+	}
+
+	#[test]
+	fn formats_csv_and_beancount() {
+		let payments =
+			[payment("in", PaymentDirection::Inbound, PaymentStatus::Succeeded, 50_000, None)];
+		let entries = ledger_entries(&payments, &[]);
+
+		assert_eq!(
+			format_csv(&entries),
+			"date,timestamp,reference,description,account,debit_msat,credit_msat\n\
+			2023-11-14,1700000000,in,Received Lightning payment,Assets:Lightning,50000,\n\
+			2023-11-14,1700000000,in,Received Lightning payment,Income:Payments,,50000\n"
+		);
+		assert_eq!(
+			format_beancount(&entries),
+			"1970-01-01 open Assets:Lightning MSAT\n\
+			1970-01-01 open Income:Payments MSAT\n\
+			\n\
+			2023-11-14 * \"Received Lightning payment\"\n  \
+			reference: \"in\"\n  \
+			Assets:Lightning  50000 MSAT\n  \
+			Income:Payments  -50000 MSAT\n"
+		);
+	}
+
+	#[test]
+	fn formats_dates_in_utc() {
+		assert_eq!(format_date(0), "1970-01-01");
+		assert_eq!(format_date(951_782_400), "2000-02-29");
+		assert_eq!(format_date(1_700_000_000), "2023-11-14");
+	}
+}
@@ -9,6 +9,7 @@
 
 use std::fmt::Write;
 use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Shell};
@@ -21,6 +22,7 @@ use ldk_server_client::config::{
 use ldk_server_client::error::LdkServerError;
 use ldk_server_client::error::LdkServerErrorCode::{
 	AuthError, InternalError, InternalServerError, InvalidRequestError, LightningError,
+	NotFoundError, PermissionDeniedError, RateLimited,
 };
 use ldk_server_client::ldk_server_grpc::api::{
 	Bolt11ClaimForHashRequest, Bolt11ClaimForHashResponse, Bolt11FailForHashRequest,
@@ -30,35 +32,50 @@ use ldk_server_client::ldk_server_grpc::api::{
 	Bolt11ReceiveViaJitChannelResponse, Bolt11SendRequest, Bolt11SendResponse,
 	Bolt12ReceiveRequest, Bolt12ReceiveResponse, Bolt12SendRequest, Bolt12SendResponse,
 	CloseChannelRequest, CloseChannelResponse, ConnectPeerRequest, ConnectPeerResponse,
-	DecodeInvoiceRequest, DecodeInvoiceResponse, DecodeOfferRequest, DecodeOfferResponse,
-	DisconnectPeerRequest, DisconnectPeerResponse, ExportPathfindingScoresRequest,
-	ForceCloseChannelRequest, ForceCloseChannelResponse, GetBalancesRequest, GetBalancesResponse,
-	GetNodeInfoRequest, GetNodeInfoResponse, GetPaymentDetailsRequest, GetPaymentDetailsResponse,
-	GraphGetChannelRequest, GraphGetChannelResponse, GraphGetNodeRequest, GraphGetNodeResponse,
-	GraphListChannelsRequest, GraphListChannelsResponse, GraphListNodesRequest,
-	GraphListNodesResponse, ListChannelsRequest, ListChannelsResponse,
-	ListForwardedPaymentsRequest, ListPaymentsRequest, ListPeersRequest, ListPeersResponse,
-	OnchainReceiveRequest, OnchainReceiveResponse, OnchainSendRequest, OnchainSendResponse,
-	OpenChannelRequest, OpenChannelResponse, SignMessageRequest, SignMessageResponse,
-	SpliceInRequest, SpliceInResponse, SpliceOutRequest, SpliceOutResponse, SpontaneousSendRequest,
-	SpontaneousSendResponse, UnifiedSendRequest, UnifiedSendResponse, UpdateChannelConfigRequest,
-	UpdateChannelConfigResponse, VerifySignatureRequest, VerifySignatureResponse,
+	CreateLnurlWithdrawRequest, CreateLnurlWithdrawResponse, DecodeInvoiceRequest,
+	DecodeInvoiceResponse, DecodeOfferRequest, DecodeOfferResponse, DisconnectPeerRequest,
+	DisconnectPeerResponse, ExportPathfindingScoresRequest, ForceCloseChannelRequest,
+	ForceCloseChannelResponse, GetApiVersionResponse, GetBalancesRequest, GetBalancesResponse,
+	GetChannelAnnouncementStatusRequest, GetChannelAnnouncementStatusResponse,
+	GetChannelRoutingStatsRequest, GetChannelRoutingStatsResponse, GetClaimableBalancesRequest,
+	GetClaimableBalancesResponse, GetConfigRequest, GetConfigResponse, GetForwardedPaymentRequest,
+	GetForwardedPaymentResponse, GetNodeInfoRequest, GetNodeInfoResponse, GetPaymentDetailsRequest,
+	GetPaymentDetailsResponse, GetPeerDetailsRequest, GetPeerDetailsResponse, GetSnapshotRequest,
+	GetWalletDescriptorRequest, GetWalletDescriptorResponse, GraphGetChannelRequest,
+	GraphGetChannelResponse, GraphGetNodeRequest, GraphGetNodeResponse, GraphListChannelsRequest,
+	GraphListChannelsResponse, GraphListNodesRequest, GraphListNodesResponse, IsOwnInvoiceRequest,
+	IsOwnInvoiceResponse, ListAuditLogRequest, ListChannelsRequest, ListChannelsResponse,
+	ListForwardedPaymentsRequest, ListOffersRequest, ListPaymentsRequest, ListPeersRequest,
+	ListPeersResponse, OnchainReceiveRequest, OnchainReceiveResponse, OnchainSendRequest,
+	OnchainSendResponse, OpenChannelRequest, OpenChannelResponse, PrunePaymentsRequest,
+	PrunePaymentsResponse, RunPreflightChecksRequest, RunPreflightChecksResponse,
+	SignMessageRequest, SignMessageResponse, SpliceInRequest, SpliceInResponse, SpliceOutRequest,
+	SpliceOutResponse, SpontaneousSendRequest, SpontaneousSendResponse, UnifiedSendRequest,
+	UnifiedSendResponse, UpdateChannelConfigRequest, UpdateChannelConfigResponse,
+	VerifySignatureRequest, VerifySignatureResponse, WhoAmIRequest, WhoAmIResponse,
 };
 use ldk_server_client::ldk_server_grpc::types::{
 	bolt11_invoice_description, Bolt11InvoiceDescription, ChannelConfig, CustomTlvRecord,
-	PageToken, RouteParametersConfig,
+	PageToken, PreflightCheckStatus, RouteParametersConfig,
 };
 use ldk_server_client::{
 	DEFAULT_EXPIRY_SECS, DEFAULT_MAX_CHANNEL_SATURATION_POWER_OF_HALF, DEFAULT_MAX_PATH_COUNT,
 	DEFAULT_MAX_TOTAL_CLTV_EXPIRY_DELTA,
 };
+use ledger::{format_beancount, format_csv, ledger_entries, LedgerFormat};
 use serde::Serialize;
 use serde_json::{json, Value};
+use sink::run_command;
 use types::{
-	Amount, CliListForwardedPaymentsResponse, CliListPaymentsResponse, CliPaginatedResponse,
+	Amount, AmountRounding, CliListAuditLogResponse, CliListForwardedPaymentsResponse,
+	CliListOffersResponse, CliListPaymentsResponse, CliPaginatedResponse, CliSnapshot,
 };
+use watch::{balance_report, AmountUnit, ChannelBalance};
 
+mod ledger;
+mod sink;
 mod types;
+mod watch;
 
 const FULL_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), " (", env!("GIT_HASH"), ")");
 
@@ -96,6 +113,14 @@ struct Cli {
 	#[arg(short, long, help = format!("Path to config file. Defaults to {DEFAULT_DIR}/config.toml"))]
 	config: Option<String>,
 
+	#[arg(
+		long,
+		value_enum,
+		default_value_t = AmountRounding::Error,
+		help = "How to handle amounts with sub-satoshi precision where whole satoshis are required, e.g. for on-chain sends"
+	)]
+	amount_rounding: AmountRounding,
+
 	#[command(subcommand)]
 	command: Commands,
 }
@@ -106,8 +131,25 @@ enum Commands {
 	GetNodeInfo,
 	#[command(about = "Retrieve an overview of all known balances")]
 	GetBalances,
+	#[command(
+		name = "claimable-balances",
+		about = "Retrieve the balances claimable from channel monitors, including closing channels"
+	)]
+	ClaimableBalances,
+	#[command(about = "Show the API version and enabled optional features of the server")]
+	ServerVersion,
+	#[command(about = "Show the runtime configuration of the server, with secrets redacted")]
+	GetConfig,
+	#[command(name = "whoami", about = "Show the name and scopes of the API key in use")]
+	WhoAmI,
+	#[command(about = "Run preflight checks, exiting with a non-zero status if any of them fails")]
+	Preflight,
+	#[command(about = "Retrieve a snapshot of the node's state for monitoring, including alerts")]
+	Snapshot,
 	#[command(about = "Retrieve a new on-chain funding address")]
 	OnchainReceive,
+	#[command(about = "Show the watch-only output descriptors of the on-chain wallet")]
+	WalletDescriptor,
 	#[command(about = "Send an on-chain payment to the given address")]
 	OnchainSend {
 		#[arg(help = "The address to send coins to")]
@@ -245,6 +287,11 @@ enum Commands {
 			help = "Maximum share of a channel's total capacity to send over a channel, as a power of 1/2 (default: 2)"
 		)]
 		max_channel_saturation_power_of_half: Option<u32>,
+		#[arg(
+			long,
+			help = "Confirm paying a zero-amount invoice an amount above the server's large payment threshold"
+		)]
+		confirm_large: bool,
 	},
 	#[command(about = "Return a BOLT12 offer for receiving payments")]
 	Bolt12Receive {
@@ -259,6 +306,17 @@ enum Commands {
 		#[arg(long, help = "Number of items requested. Can only be set for fixed-amount offers")]
 		quantity: Option<u64>,
 	},
+	#[command(about = "List the BOLT12 offers created by this node, newest first")]
+	ListOffers {
+		#[arg(
+			short,
+			long,
+			help = "Fetch at least this many offers by iterating through multiple pages. Returns combined results with the last page token. If not provided, returns only a single page."
+		)]
+		number_of_offers: Option<u64>,
+		#[arg(long, help = "Page token to continue from a previous page (format: token:index)")]
+		page_token: Option<String>,
+	},
 	#[command(about = "Send a payment for a BOLT12 offer")]
 	Bolt12Send {
 		#[arg(help = "A BOLT12 offer for a payment within the Lightning Network")]
@@ -349,11 +407,28 @@ enum Commands {
 		)]
 		max_channel_saturation_power_of_half: Option<u32>,
 	},
+	#[command(
+		name = "create-withdraw",
+		about = "Create a single-use LNURL-withdraw that a wallet can pull funds from"
+	)]
+	CreateWithdraw {
+		#[arg(help = "The most the wallet may withdraw, e.g. 50sat or 50000msat")]
+		max_amount: Amount,
+		#[arg(long, help = "The least the wallet may withdraw. Defaults to the maximum")]
+		min_amount: Option<Amount>,
+		#[arg(long, help = "The description the wallet suggests for the withdrawal invoice")]
+		description: Option<String>,
+	},
 	#[command(about = "Decode a BOLT11 invoice and display its fields")]
 	DecodeInvoice {
 		#[arg(help = "The BOLT11 invoice string to decode")]
 		invoice: String,
 	},
+	#[command(about = "Check whether a BOLT11 invoice was issued by this node")]
+	IsOwnInvoice {
+		#[arg(help = "The BOLT11 invoice string to check")]
+		invoice: String,
+	},
 	#[command(about = "Decode a BOLT12 offer and display its fields")]
 	DecodeOffer {
 		#[arg(help = "The BOLT12 offer string to decode")]
@@ -389,8 +464,14 @@ enum Commands {
 		channel_amount: Amount,
 		#[arg(long, help = "Amount to push to the remote side, e.g. 50sat or 50000msat")]
 		push_to_counterparty: Option<Amount>,
-		#[arg(long, help = "Whether the channel should be public")]
-		announce_channel: bool,
+		#[arg(
+			long,
+			num_args = 0..=1,
+			require_equals = true,
+			default_missing_value = "true",
+			help = "Whether the channel should be public. Pass `--announce-channel=false` to open a private channel if the server announces channels by default"
+		)]
+		announce_channel: Option<bool>,
 		#[arg(
 			long,
 			help = "Allow the counterparty to spend all its channel balance. This cannot be set together with `announce_channel`."
@@ -456,11 +537,22 @@ enum Commands {
 		#[arg(help = "Page token to continue from a previous page (format: token:index)")]
 		page_token: Option<String>,
 	},
+	#[command(
+		about = "Remove old succeeded and failed payments from the payment history. Without limits, the configured retention is applied"
+	)]
+	PrunePayments {
+		#[arg(long, help = "Remove payments last updated longer ago than this many seconds")]
+		max_age_secs: Option<u64>,
+		#[arg(long, help = "Remove all but this many of the most recent payments")]
+		max_records: Option<u64>,
+	},
 	#[command(about = "Get details of a specific payment by its payment ID")]
 	GetPaymentDetails {
 		#[arg(help = "The payment ID in hex-encoded form")]
 		payment_id: String,
 	},
+	#[command(about = "Retrieve the fees earned and amounts forwarded per channel")]
+	ChannelRoutingStats,
 	#[command(about = "Retrieves list of all forwarded payments")]
 	ListForwardedPayments {
 		#[arg(
@@ -472,6 +564,57 @@ enum Commands {
 		#[arg(long, help = "Page token to continue from a previous page (format: token:index)")]
 		page_token: Option<String>,
 	},
+	#[command(about = "Retrieve a single forwarded payment by its ID")]
+	GetForward {
+		#[arg(help = "The forward ID in hex-encoded form, as shown by list-forwarded-payments")]
+		forward_id: String,
+	},
+	#[command(
+		about = "Poll a channel's balance and print the change each interval, until interrupted with Ctrl-C"
+	)]
+	WatchChannel {
+		#[arg(help = "The channel to watch, by its hex-encoded channel_id or its user_channel_id")]
+		channel_id: String,
+		#[arg(long, default_value_t = 10, help = "Seconds between polls")]
+		interval: u64,
+		#[arg(
+			long,
+			help = "Report when the outbound balance crosses this amount, e.g. 50000sat or 50000000msat"
+		)]
+		threshold: Option<Amount>,
+		#[arg(long, value_enum, default_value_t = AmountUnit::Msat, help = "The unit to report amounts in")]
+		amounts: AmountUnit,
+	},
+	#[command(
+		about = "Run a command for each server event, with the event as JSON on its standard input, until interrupted with Ctrl-C"
+	)]
+	Sink {
+		#[arg(
+			long,
+			help = "The shell command to run for each event. Events are handled one at a time, and a failing command is logged and skipped"
+		)]
+		exec: String,
+	},
+	#[command(about = "Export payments and forwarding fees as double-entry ledger records")]
+	ExportLedger {
+		#[arg(long, value_enum, default_value_t = LedgerFormat::Csv, help = "The output format")]
+		format: LedgerFormat,
+	},
+	#[command(about = "List audit log entries for fund-moving operations, newest first")]
+	AuditLog {
+		#[arg(
+			short,
+			long,
+			help = "Fetch at least this many entries by iterating through multiple pages. Returns combined results with the last page token. If not provided, returns only a single page."
+		)]
+		number_of_entries: Option<u64>,
+		#[arg(long, help = "Page token to continue from a previous page (format: token:index)")]
+		page_token: Option<String>,
+		#[arg(long, help = "Only return entries recorded after this UNIX timestamp (in seconds)")]
+		after: Option<u64>,
+		#[arg(long, help = "Only return entries recorded before this UNIX timestamp (in seconds)")]
+		before: Option<u64>,
+	},
 	#[command(about = "Update the forwarding fees and CLTV expiry delta for an existing channel")]
 	UpdateChannelConfig {
 		#[arg(help = "The local user_channel_id of this channel")]
@@ -520,6 +663,11 @@ enum Commands {
 	},
 	#[command(about = "Return a list of peers")]
 	ListPeers,
+	#[command(about = "Show connection details and the announcement of a single peer")]
+	PeerDetails {
+		#[arg(help = "The hex-encoded node ID of the peer")]
+		node_id: String,
+	},
 	#[command(about = "Sign a message with the node's secret key")]
 	SignMessage {
 		#[arg(help = "The message to sign")]
@@ -550,6 +698,11 @@ enum Commands {
 		#[arg(help = "The hex-encoded node ID to look up")]
 		node_id: String,
 	},
+	#[command(about = "Check whether a channel and its channel updates are in the network graph")]
+	ChannelAnnouncementStatus {
+		#[arg(help = "The local user_channel_id of the channel")]
+		user_channel_id: String,
+	},
 	#[command(about = "Generate shell completions for the CLI")]
 	Completions {
 		#[arg(
@@ -609,24 +762,66 @@ async fn main() {
 		std::process::exit(1);
 	});
 
+	let amount_rounding = cli.amount_rounding;
 	match cli.command {
 		Commands::GetNodeInfo => {
 			handle_response_result::<_, GetNodeInfoResponse>(
 				client.get_node_info(GetNodeInfoRequest {}).await,
 			);
 		},
+		Commands::ServerVersion => {
+			handle_response_result::<_, GetApiVersionResponse>(client.server_version().await);
+		},
+		Commands::GetConfig => {
+			handle_response_result::<_, GetConfigResponse>(
+				client.get_config(GetConfigRequest {}).await,
+			);
+		},
+		Commands::WhoAmI => {
+			handle_response_result::<_, WhoAmIResponse>(client.who_am_i(WhoAmIRequest {}).await);
+		},
+		Commands::Preflight => {
+			let response = client
+				.run_preflight_checks(RunPreflightChecksRequest {})
+				.await
+				.unwrap_or_else(|e| handle_error(e));
+			let failed = response
+				.checks
+				.iter()
+				.any(|check| check.status == PreflightCheckStatus::Fail as i32);
+			handle_response_result::<_, RunPreflightChecksResponse>(Ok(response));
+			if failed {
+				std::process::exit(1);
+			}
+		},
+		Commands::Snapshot => {
+			handle_response_result::<_, CliSnapshot>(
+				client.get_snapshot(GetSnapshotRequest {}).await,
+			);
+		},
 		Commands::GetBalances => {
 			handle_response_result::<_, GetBalancesResponse>(
 				client.get_balances(GetBalancesRequest {}).await,
 			);
 		},
+		Commands::ClaimableBalances => {
+			handle_response_result::<_, GetClaimableBalancesResponse>(
+				client.get_claimable_balances(GetClaimableBalancesRequest {}).await,
+			);
+		},
 		Commands::OnchainReceive => {
 			handle_response_result::<_, OnchainReceiveResponse>(
 				client.onchain_receive(OnchainReceiveRequest {}).await,
 			);
 		},
+		Commands::WalletDescriptor => {
+			handle_response_result::<_, GetWalletDescriptorResponse>(
+				client.get_wallet_descriptor(GetWalletDescriptorRequest {}).await,
+			);
+		},
 		Commands::OnchainSend { address, amount, send_all, fee_rate_sat_per_vb } => {
-			let amount_sats = amount.map(|a| a.to_sat().unwrap_or_else(|e| handle_error_msg(e)));
+			let amount_sats = amount
+				.map(|a| a.to_sat_rounded(amount_rounding).unwrap_or_else(|e| handle_error_msg(e)));
 			handle_response_result::<_, OnchainSendResponse>(
 				client
 					.onchain_send(OnchainSendRequest {
@@ -744,6 +939,7 @@ async fn main() {
 			max_total_cltv_expiry_delta,
 			max_path_count,
 			max_channel_saturation_power_of_half,
+			confirm_large,
 		} => {
 			let amount_msat = amount.map(|a| a.to_msat());
 			let max_total_routing_fee_msat = max_total_routing_fee.map(|a| a.to_msat());
@@ -761,6 +957,7 @@ async fn main() {
 						invoice,
 						amount_msat,
 						route_parameters: Some(route_parameters),
+						confirm_large,
 					})
 					.await,
 			);
@@ -778,6 +975,20 @@ async fn main() {
 					.await,
 			);
 		},
+		Commands::ListOffers { number_of_offers, page_token } => {
+			let page_token = page_token
+				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+
+			handle_response_result::<_, CliListOffersResponse>(
+				fetch_paginated(
+					number_of_offers,
+					page_token,
+					|pt| client.list_offers(ListOffersRequest { page_token: pt }),
+					|r| (r.offers, r.next_page_token),
+				)
+				.await,
+			);
+		},
 		Commands::Bolt12Send {
 			offer,
 			amount,
@@ -875,11 +1086,27 @@ async fn main() {
 					.await,
 			);
 		},
+		Commands::CreateWithdraw { max_amount, min_amount, description } => {
+			handle_response_result::<_, CreateLnurlWithdrawResponse>(
+				client
+					.create_lnurl_withdraw(CreateLnurlWithdrawRequest {
+						max_amount_msat: max_amount.to_msat(),
+						min_amount_msat: min_amount.map(|a| a.to_msat()),
+						description,
+					})
+					.await,
+			);
+		},
 		Commands::DecodeInvoice { invoice } => {
 			handle_response_result::<_, DecodeInvoiceResponse>(
 				client.decode_invoice(DecodeInvoiceRequest { invoice }).await,
 			);
 		},
+		Commands::IsOwnInvoice { invoice } => {
+			handle_response_result::<_, IsOwnInvoiceResponse>(
+				client.is_own_invoice(IsOwnInvoiceRequest { invoice }).await,
+			);
+		},
 		Commands::DecodeOffer { offer } => {
 			handle_response_result::<_, DecodeOfferResponse>(
 				client.decode_offer(DecodeOfferRequest { offer }).await,
@@ -918,15 +1145,16 @@ async fn main() {
 			forwarding_fee_base_msat,
 			cltv_expiry_delta,
 		} => {
-			let channel_amount_sats =
-				channel_amount.to_sat().unwrap_or_else(|e| handle_error_msg(e));
+			let channel_amount_sats = channel_amount
+				.to_sat_rounded(amount_rounding)
+				.unwrap_or_else(|e| handle_error_msg(e));
 			let push_to_counterparty_msat = push_to_counterparty.map(|a| a.to_msat());
 			let channel_config = build_open_channel_config(
 				forwarding_fee_proportional_millionths,
 				forwarding_fee_base_msat,
 				cltv_expiry_delta,
 			);
-			if announce_channel && disable_counterparty_reserve {
+			if announce_channel == Some(true) && disable_counterparty_reserve {
 				handle_error(LdkServerError::new(
 					InvalidRequestError,
 					"Cannot set both `announce_channel` and `disable_counterparty_reserve`",
@@ -948,7 +1176,9 @@ async fn main() {
 			);
 		},
 		Commands::SpliceIn { user_channel_id, counterparty_node_id, splice_amount } => {
-			let splice_amount_sats = splice_amount.to_sat().unwrap_or_else(|e| handle_error_msg(e));
+			let splice_amount_sats = splice_amount
+				.to_sat_rounded(amount_rounding)
+				.unwrap_or_else(|e| handle_error_msg(e));
 			handle_response_result::<_, SpliceInResponse>(
 				client
 					.splice_in(SpliceInRequest {
@@ -960,7 +1190,9 @@ async fn main() {
 			);
 		},
 		Commands::SpliceOut { user_channel_id, counterparty_node_id, address, splice_amount } => {
-			let splice_amount_sats = splice_amount.to_sat().unwrap_or_else(|e| handle_error_msg(e));
+			let splice_amount_sats = splice_amount
+				.to_sat_rounded(amount_rounding)
+				.unwrap_or_else(|e| handle_error_msg(e));
 			handle_response_result::<_, SpliceOutResponse>(
 				client
 					.splice_out(SpliceOutRequest {
@@ -991,11 +1223,21 @@ async fn main() {
 				.await,
 			);
 		},
+		Commands::PrunePayments { max_age_secs, max_records } => {
+			handle_response_result::<_, PrunePaymentsResponse>(
+				client.prune_payments(PrunePaymentsRequest { max_age_secs, max_records }).await,
+			);
+		},
 		Commands::GetPaymentDetails { payment_id } => {
 			handle_response_result::<_, GetPaymentDetailsResponse>(
 				client.get_payment_details(GetPaymentDetailsRequest { payment_id }).await,
 			);
 		},
+		Commands::ChannelRoutingStats => {
+			handle_response_result::<_, GetChannelRoutingStatsResponse>(
+				client.get_channel_routing_stats(GetChannelRoutingStatsRequest {}).await,
+			);
+		},
 		Commands::ListForwardedPayments { number_of_payments, page_token } => {
 			let page_token = page_token
 				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
@@ -1014,6 +1256,113 @@ async fn main() {
 				.await,
 			);
 		},
+		Commands::GetForward { forward_id } => {
+			handle_response_result::<_, GetForwardedPaymentResponse>(
+				client.get_forwarded_payment(GetForwardedPaymentRequest { forward_id }).await,
+			);
+		},
+		Commands::WatchChannel { channel_id, interval, threshold, amounts } => {
+			if interval == 0 {
+				handle_error_msg("The interval must be at least 1 second".to_string());
+			}
+			let threshold_msat = threshold.map(|t| t.to_msat());
+			let mut previous = None;
+			loop {
+				let channels = client
+					.list_channels(ListChannelsRequest {})
+					.await
+					.unwrap_or_else(|e| handle_error(e))
+					.channels;
+				let channel = channels
+					.iter()
+					.find(|c| c.channel_id == channel_id || c.user_channel_id == channel_id)
+					.unwrap_or_else(|| {
+						handle_error_msg(format!("No channel with id {}", channel_id))
+					});
+				let current = ChannelBalance::from(channel);
+				let timestamp =
+					SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+				let report = balance_report(timestamp, previous, current, threshold_msat, amounts);
+				match serde_json::to_string(&report) {
+					Ok(json) => println!("{}", sanitize_for_terminal(json)),
+					Err(e) => handle_error_msg(format!("Error serializing report to JSON: {e}")),
+				}
+				previous = Some(current);
+				tokio::select! {
+					_ = tokio::time::sleep(Duration::from_secs(interval)) => {},
+					_ = tokio::signal::ctrl_c() => break,
+				}
+			}
+		},
+		Commands::Sink { exec } => {
+			let mut events = client.subscribe_events().await.unwrap_or_else(|e| handle_error(e));
+			while let Some(event) = events.next_message().await {
+				let event = event.unwrap_or_else(|e| handle_error(e));
+				let json = serde_json::to_string(&event).unwrap_or_else(|e| {
+					handle_error_msg(format!("Error serializing event to JSON: {e}"))
+				});
+				// The next event is only read once the command exited, so a slow command holds
+				// back the stream rather than spawning unboundedly many processes.
+				if let Err(e) = run_command(&exec, &json) {
+					eprintln!("Error: {}", sanitize_for_terminal(e));
+				}
+			}
+			handle_error_msg("The server closed the event stream".to_string());
+		},
+		Commands::ExportLedger { format } => {
+			// Asking for `u64::MAX` items keeps fetching pages until the last one.
+			let payments = fetch_paginated(
+				Some(u64::MAX),
+				None,
+				|pt| client.list_payments(ListPaymentsRequest { page_token: pt }),
+				|r| (r.payments, r.next_page_token),
+			)
+			.await
+			.unwrap_or_else(|e| handle_error(e))
+			.list;
+			let forwards = fetch_paginated(
+				Some(u64::MAX),
+				None,
+				|pt| {
+					client.list_forwarded_payments(ListForwardedPaymentsRequest { page_token: pt })
+				},
+				|r| (r.forwarded_payments, r.next_page_token),
+			)
+			.await
+			.unwrap_or_else(|e| handle_error(e))
+			.list;
+
+			let undated_forwards = forwards.iter().filter(|f| f.forwarded_at.is_none()).count();
+			if undated_forwards > 0 {
+				eprintln!(
+					"Warning: skipped {} forwards recorded without a timestamp by an older server",
+					undated_forwards
+				);
+			}
+
+			let entries = ledger_entries(&payments, &forwards);
+			match format {
+				LedgerFormat::Csv => print!("{}", format_csv(&entries)),
+				LedgerFormat::Beancount => print!("{}", format_beancount(&entries)),
+			}
+		},
+
+		Commands::AuditLog { number_of_entries, page_token, after, before } => {
+			let page_token = page_token
+				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+
+			handle_response_result::<_, CliListAuditLogResponse>(
+				fetch_paginated(
+					number_of_entries,
+					page_token,
+					|pt| {
+						client.list_audit_log(ListAuditLogRequest { page_token: pt, after, before })
+					},
+					|r| (r.entries, r.next_page_token),
+				)
+				.await,
+			);
+		},
 		Commands::UpdateChannelConfig {
 			user_channel_id,
 			counterparty_node_id,
@@ -1063,6 +1412,11 @@ async fn main() {
 				client.list_peers(ListPeersRequest {}).await,
 			);
 		},
+		Commands::PeerDetails { node_id } => {
+			handle_response_result::<_, GetPeerDetailsResponse>(
+				client.get_peer_details(GetPeerDetailsRequest { node_id }).await,
+			);
+		},
 		Commands::SignMessage { message } => {
 			handle_response_result::<_, SignMessageResponse>(
 				client
@@ -1111,6 +1465,15 @@ async fn main() {
 				client.graph_get_node(GraphGetNodeRequest { node_id }).await,
 			);
 		},
+		Commands::ChannelAnnouncementStatus { user_channel_id } => {
+			handle_response_result::<_, GetChannelAnnouncementStatusResponse>(
+				client
+					.get_channel_announcement_status(GetChannelAnnouncementStatusRequest {
+						user_channel_id,
+					})
+					.await,
+			);
+		},
 		Commands::Completions { .. } => unreachable!("Handled above"),
 	}
 }
@@ -1147,7 +1510,7 @@ where
 {
 	match target_count {
 		Some(count) => {
-			let mut items = Vec::with_capacity(count as usize);
+			let mut items = Vec::new();
 			let mut page_token = initial_page_token;
 			let mut next_page_token;
 
@@ -1288,6 +1651,9 @@ fn handle_error(e: LdkServerError) -> ! {
 		AuthError => "Authentication Error",
 		LightningError => "Lightning Error",
 		InternalServerError => "Internal Server Error",
+		PermissionDeniedError => "Permission Denied",
+		NotFoundError => "Not Found",
+		RateLimited { .. } => "Rate Limited",
 		InternalError => "Internal Error",
 	};
 	eprintln!("Error ({}): {}", error_type, e.message);
@@ -0,0 +1,74 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+//! Delivery of events to an external command for the `sink` command.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Runs `command` through the shell with `payload` on its standard input, followed by a newline,
+/// and waits for it to exit. Its standard output and error are passed through.
+///
+/// As the command is waited for, events are delivered one at a time and a slow command holds back
+/// the event stream instead of piling up processes.
+pub fn run_command(command: &str, payload: &str) -> Result<(), String> {
+	let mut child = shell(command)
+		.stdin(Stdio::piped())
+		.spawn()
+		.map_err(|e| format!("Failed to run `{}`: {}", command, e))?;
+
+	// The command may exit without reading its input, which is not an error in itself.
+	if let Some(mut stdin) = child.stdin.take() {
+		let _ = stdin.write_all(payload.as_bytes()).and_then(|()| stdin.write_all(b"\n"));
+	}
+
+	let status = child.wait().map_err(|e| format!("Failed to wait for `{}`: {}", command, e))?;
+	if status.success() {
+		Ok(())
+	} else {
+		Err(format!("`{}` failed with {}", command, status))
+	}
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> Command {
+	let mut shell = Command::new("sh");
+	shell.arg("-c").arg(command);
+	shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+	let mut shell = Command::new("cmd");
+	shell.arg("/C").arg(command);
+	shell
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn payload_is_passed_on_stdin() {
+		let path = std::env::temp_dir().join(format!("ldk-server-cli-sink-{}", std::process::id()));
+		let command = format!("cat > {}", path.display());
+
+		run_command(&command, r#"{"event":{}}"#).unwrap();
+
+		assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"event\":{}}\n");
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn failing_command_is_reported() {
+		let err = run_command("exit 3", "{}").unwrap_err();
+		assert!(err.contains("`exit 3` failed"), "{}", err);
+		assert!(run_command("true", "{}").is_ok());
+	}
+}
@@ -16,7 +16,11 @@
 use std::fmt;
 use std::str::FromStr;
 
-use ldk_server_client::ldk_server_grpc::types::{ForwardedPayment, PageToken, Payment};
+use clap::ValueEnum;
+use ldk_server_client::ldk_server_grpc::api::GetSnapshotResponse;
+use ldk_server_client::ldk_server_grpc::types::{
+	AuditLogEntry, ForwardedPayment, OfferRecord, PageToken, Payment,
+};
 use serde::Serialize;
 
 /// CLI-specific wrapper for paginated responses that formats the page token
@@ -38,11 +42,47 @@ impl<T> CliPaginatedResponse<T> {
 
 pub type CliListPaymentsResponse = CliPaginatedResponse<Payment>;
 pub type CliListForwardedPaymentsResponse = CliPaginatedResponse<ForwardedPayment>;
+pub type CliListAuditLogResponse = CliPaginatedResponse<AuditLogEntry>;
+pub type CliListOffersResponse = CliPaginatedResponse<OfferRecord>;
 
 fn format_page_token(token: PageToken) -> String {
 	format!("{}:{}", token.token, token.index)
 }
 
+/// CLI-specific wrapper for [`GetSnapshotResponse`] that leads with a summary of the snapshot, so
+/// the figures a glance at the node's health needs come before the full details.
+#[derive(Debug, Clone, Serialize)]
+pub struct CliSnapshot {
+	pub summary: SnapshotSummary,
+	#[serde(flatten)]
+	pub snapshot: GetSnapshotResponse,
+}
+
+/// Counts derived from a [`GetSnapshotResponse`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SnapshotSummary {
+	pub channels: usize,
+	pub usable_channels: usize,
+	pub peers: usize,
+	pub connected_peers: usize,
+	pub pending_payments: usize,
+	pub alerts: usize,
+}
+
+impl From<GetSnapshotResponse> for CliSnapshot {
+	fn from(snapshot: GetSnapshotResponse) -> Self {
+		let summary = SnapshotSummary {
+			channels: snapshot.channels.len(),
+			usable_channels: snapshot.channels.iter().filter(|c| c.is_usable).count(),
+			peers: snapshot.peers.len(),
+			connected_peers: snapshot.peers.iter().filter(|p| p.is_connected).count(),
+			pending_payments: snapshot.pending_payments.len(),
+			alerts: snapshot.alerts.len(),
+		};
+		Self { summary, snapshot }
+	}
+}
+
 /// A denomination-aware amount that stores its value internally in millisatoshis.
 ///
 /// Accepts the following formats when parsed from a string:
@@ -56,6 +96,11 @@ pub struct Amount {
 }
 
 impl Amount {
+	/// Creates an amount of the given number of millisatoshis.
+	pub fn from_msat(msats: u64) -> Self {
+		Self { msats }
+	}
+
 	/// Returns the value in millisatoshis.
 	pub fn to_msat(self) -> u64 {
 		self.msats
@@ -74,6 +119,28 @@ impl Amount {
 			Ok(self.msats / 1000)
 		}
 	}
+
+	/// Returns the value in satoshis, handling sub-satoshi precision according to `rounding`.
+	pub fn to_sat_rounded(self, rounding: AmountRounding) -> Result<u64, String> {
+		match rounding {
+			AmountRounding::Error => self.to_sat(),
+			AmountRounding::Up => Ok(self.msats.div_ceil(1000)),
+			AmountRounding::Down => Ok(self.msats / 1000),
+		}
+	}
+}
+
+/// How amounts with sub-satoshi precision are converted where whole satoshis are required, e.g.
+/// for on-chain sends, channel opens and splices.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum AmountRounding {
+	/// Reject the amount instead of silently changing it.
+	#[default]
+	Error,
+	/// Round up to the next whole satoshi.
+	Up,
+	/// Round down to the previous whole satoshi.
+	Down,
 }
 
 impl fmt::Display for Amount {
@@ -185,4 +252,59 @@ mod tests {
 		let big = format!("{}sat", u64::MAX);
 		assert!(Amount::from_str(&big).is_err());
 	}
+
+	#[test]
+	fn amount_rounding_modes() {
+		// e.g. the amount of an invoice for 1500msat
+		let amount = Amount::from_str("1500msat").unwrap();
+		assert!(amount.to_sat_rounded(AmountRounding::Error).is_err());
+		assert_eq!(amount.to_sat_rounded(AmountRounding::Up).unwrap(), 2);
+		assert_eq!(amount.to_sat_rounded(AmountRounding::Down).unwrap(), 1);
+
+		// whole sat amounts are never changed
+		let amount = Amount::from_str("2sat").unwrap();
+		assert_eq!(amount.to_sat_rounded(AmountRounding::Error).unwrap(), 2);
+		assert_eq!(amount.to_sat_rounded(AmountRounding::Up).unwrap(), 2);
+		assert_eq!(amount.to_sat_rounded(AmountRounding::Down).unwrap(), 2);
+
+		assert_eq!(AmountRounding::default(), AmountRounding::Error);
+	}
+
+	#[test]
+	fn unknown_enum_values_are_printed_with_their_discriminant() {
+		// Prost keeps enum values it does not know as the raw `i32`, e.g. a status added in a
+		// newer server version.
+		let payments: CliListPaymentsResponse = CliPaginatedResponse::new(
+			vec![Payment { status: 7, direction: 9, ..Default::default() }],
+			None,
+		);
+
+		let json = serde_json::to_value(&payments).unwrap();
+		assert_eq!(json["list"][0]["status"], "unknown(7)");
+		assert_eq!(json["list"][0]["direction"], "unknown(9)");
+		assert!(json["list"][0]["kind"].is_null());
+	}
+
+	#[test]
+	fn snapshot_leads_with_a_summary() {
+		use ldk_server_client::ldk_server_grpc::types::{Channel, Peer};
+
+		let snapshot = GetSnapshotResponse {
+			timestamp: 1_700_000_000,
+			channels: vec![
+				Channel { is_usable: true, ..Default::default() },
+				Channel { is_usable: false, ..Default::default() },
+			],
+			peers: vec![Peer { is_connected: true, ..Default::default() }],
+			..Default::default()
+		};
+
+		let json = serde_json::to_value(CliSnapshot::from(snapshot)).unwrap();
+		assert_eq!(json["summary"]["channels"], 2);
+		assert_eq!(json["summary"]["usable_channels"], 1);
+		assert_eq!(json["summary"]["connected_peers"], 1);
+		assert_eq!(json["summary"]["alerts"], 0);
+		assert_eq!(json["timestamp"], 1_700_000_000);
+		assert_eq!(json["channels"].as_array().unwrap().len(), 2);
+	}
 }
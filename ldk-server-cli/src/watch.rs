@@ -0,0 +1,166 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+//! Balance reports for the `watch-channel` command.
+
+use clap::ValueEnum;
+use ldk_server_client::ldk_server_grpc::types::Channel;
+use serde::Serialize;
+
+use crate::types::Amount;
+
+/// The unit amounts are reported in by the `watch-channel` command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum AmountUnit {
+	/// Millisatoshis, e.g. `1500500msats`.
+	#[default]
+	Msat,
+	/// Satoshis, with sub-satoshi amounts as decimals, e.g. `1500.5sat`.
+	Sat,
+}
+
+/// The balance of a channel as observed by a single poll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelBalance {
+	pub outbound_msat: u64,
+	pub inbound_msat: u64,
+}
+
+impl From<&Channel> for ChannelBalance {
+	fn from(channel: &Channel) -> Self {
+		Self {
+			outbound_msat: channel.outbound_capacity_msat,
+			inbound_msat: channel.inbound_capacity_msat,
+		}
+	}
+}
+
+/// The direction in which the outbound balance crossed the configured threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdCrossing {
+	Below,
+	Above,
+}
+
+/// A single line of `watch-channel` output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChannelBalanceReport {
+	/// Seconds since the Unix epoch.
+	pub timestamp: u64,
+	pub outbound: String,
+	pub inbound: String,
+	/// The change since the previous poll. Unset for the first poll.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub outbound_delta: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub inbound_delta: Option<String>,
+	/// Set if the outbound balance crossed the threshold since the previous poll.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub crossed_threshold: Option<ThresholdCrossing>,
+}
+
+/// Builds the report for the `current` balance, relative to the `previous` poll if any.
+///
+/// The threshold applies to the outbound balance: dropping below it is reported as
+/// [`ThresholdCrossing::Below`], and reaching it again as [`ThresholdCrossing::Above`].
+pub fn balance_report(
+	timestamp: u64, previous: Option<ChannelBalance>, current: ChannelBalance,
+	threshold_msat: Option<u64>, unit: AmountUnit,
+) -> ChannelBalanceReport {
+	let delta = |previous_msat: u64, current_msat: u64| {
+		let sign = if current_msat >= previous_msat { "+" } else { "-" };
+		format!("{}{}", sign, format_amount(current_msat.abs_diff(previous_msat), unit))
+	};
+	let crossed_threshold = match (previous, threshold_msat) {
+		(Some(previous), Some(threshold_msat)) => {
+			let was_below = previous.outbound_msat < threshold_msat;
+			let is_below = current.outbound_msat < threshold_msat;
+			match (was_below, is_below) {
+				(false, true) => Some(ThresholdCrossing::Below),
+				(true, false) => Some(ThresholdCrossing::Above),
+				_ => None,
+			}
+		},
+		_ => None,
+	};
+
+	ChannelBalanceReport {
+		timestamp,
+		outbound: format_amount(current.outbound_msat, unit),
+		inbound: format_amount(current.inbound_msat, unit),
+		outbound_delta: previous.map(|p| delta(p.outbound_msat, current.outbound_msat)),
+		inbound_delta: previous.map(|p| delta(p.inbound_msat, current.inbound_msat)),
+		crossed_threshold,
+	}
+}
+
+fn format_amount(amount_msat: u64, unit: AmountUnit) -> String {
+	match unit {
+		AmountUnit::Msat => Amount::from_msat(amount_msat).to_string(),
+		AmountUnit::Sat => {
+			let sats = amount_msat / 1000;
+			let msats = amount_msat % 1000;
+			if msats == 0 {
+				format!("{}sat", sats)
+			} else {
+				let fraction = format!("{:03}", msats);
+				format!("{}.{}sat", sats, fraction.trim_end_matches('0'))
+			}
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn balance(outbound_msat: u64, inbound_msat: u64) -> ChannelBalance {
+		ChannelBalance { outbound_msat, inbound_msat }
+	}
+
+	#[test]
+	fn reports_deltas_relative_to_previous_poll() {
+		let first = balance_report(1, None, balance(5_000, 1_000), None, AmountUnit::Msat);
+		assert_eq!(first.outbound, "5000msats");
+		assert_eq!(first.outbound_delta, None);
+
+		let second = balance_report(
+			2,
+			Some(balance(5_000, 1_000)),
+			balance(3_500, 2_500),
+			None,
+			AmountUnit::Sat,
+		);
+		assert_eq!(second.outbound, "3.5sat");
+		assert_eq!(second.outbound_delta.as_deref(), Some("-1.5sat"));
+		assert_eq!(second.inbound_delta.as_deref(), Some("+1.5sat"));
+		assert_eq!(second.crossed_threshold, None);
+	}
+
+	#[test]
+	fn reports_outbound_balance_crossing_threshold() {
+		let report = |previous, current| {
+			let (previous, current) = (Some(balance(previous, 0)), balance(current, 0));
+			balance_report(1, previous, current, Some(1_000), AmountUnit::Msat).crossed_threshold
+		};
+		assert_eq!(report(1_000, 999), Some(ThresholdCrossing::Below));
+		assert_eq!(report(999, 1_000), Some(ThresholdCrossing::Above));
+		assert_eq!(report(2_000, 1_000), None);
+		assert_eq!(report(500, 100), None);
+	}
+
+	#[test]
+	fn formats_amounts_in_the_requested_unit() {
+		assert_eq!(format_amount(1_500_500, AmountUnit::Msat), "1500500msats");
+		assert_eq!(format_amount(1_500_500, AmountUnit::Sat), "1500.5sat");
+		assert_eq!(format_amount(1_500_000, AmountUnit::Sat), "1500sat");
+		assert_eq!(format_amount(7, AmountUnit::Sat), "0.007sat");
+	}
+}
@@ -23,40 +23,55 @@ use ldk_server_grpc::api::{
 	Bolt11ReceiveViaJitChannelResponse, Bolt11SendRequest, Bolt11SendResponse,
 	Bolt12ReceiveRequest, Bolt12ReceiveResponse, Bolt12SendRequest, Bolt12SendResponse,
 	CloseChannelRequest, CloseChannelResponse, ConnectPeerRequest, ConnectPeerResponse,
-	DecodeInvoiceRequest, DecodeInvoiceResponse, DecodeOfferRequest, DecodeOfferResponse,
-	DisconnectPeerRequest, DisconnectPeerResponse, ExportPathfindingScoresRequest,
-	ExportPathfindingScoresResponse, ForceCloseChannelRequest, ForceCloseChannelResponse,
-	GetBalancesRequest, GetBalancesResponse, GetNodeInfoRequest, GetNodeInfoResponse,
-	GetPaymentDetailsRequest, GetPaymentDetailsResponse, GraphGetChannelRequest,
-	GraphGetChannelResponse, GraphGetNodeRequest, GraphGetNodeResponse, GraphListChannelsRequest,
-	GraphListChannelsResponse, GraphListNodesRequest, GraphListNodesResponse, ListChannelsRequest,
-	ListChannelsResponse, ListForwardedPaymentsRequest, ListForwardedPaymentsResponse,
-	ListPaymentsRequest, ListPaymentsResponse, ListPeersRequest, ListPeersResponse,
-	OnchainReceiveRequest, OnchainReceiveResponse, OnchainSendRequest, OnchainSendResponse,
-	OpenChannelRequest, OpenChannelResponse, SignMessageRequest, SignMessageResponse,
-	SpliceInRequest, SpliceInResponse, SpliceOutRequest, SpliceOutResponse, SpontaneousSendRequest,
+	CreateLnurlWithdrawRequest, CreateLnurlWithdrawResponse, DecodeInvoiceRequest,
+	DecodeInvoiceResponse, DecodeOfferRequest, DecodeOfferResponse, DisconnectPeerRequest,
+	DisconnectPeerResponse, ExportPathfindingScoresRequest, ExportPathfindingScoresResponse,
+	ForceCloseChannelRequest, ForceCloseChannelResponse, GetApiVersionRequest,
+	GetApiVersionResponse, GetBalancesRequest, GetBalancesResponse,
+	GetChannelAnnouncementStatusRequest, GetChannelAnnouncementStatusResponse,
+	GetChannelRoutingStatsRequest, GetChannelRoutingStatsResponse, GetClaimableBalancesRequest,
+	GetClaimableBalancesResponse, GetConfigRequest, GetConfigResponse, GetForwardedPaymentRequest,
+	GetForwardedPaymentResponse, GetNodeInfoRequest, GetNodeInfoResponse, GetPaymentDetailsRequest,
+	GetPaymentDetailsResponse, GetPeerDetailsRequest, GetPeerDetailsResponse, GetSnapshotRequest,
+	GetSnapshotResponse, GetWalletDescriptorRequest, GetWalletDescriptorResponse,
+	GraphGetChannelRequest, GraphGetChannelResponse, GraphGetNodeRequest, GraphGetNodeResponse,
+	GraphListChannelsRequest, GraphListChannelsResponse, GraphListNodesRequest,
+	GraphListNodesResponse, IsOwnInvoiceRequest, IsOwnInvoiceResponse, ListAuditLogRequest,
+	ListAuditLogResponse, ListChannelsRequest, ListChannelsResponse, ListForwardedPaymentsRequest,
+	ListForwardedPaymentsResponse, ListOffersRequest, ListOffersResponse, ListPaymentsRequest,
+	ListPaymentsResponse, ListPeersRequest, ListPeersResponse, OnchainReceiveRequest,
+	OnchainReceiveResponse, OnchainSendRequest, OnchainSendResponse, OpenChannelRequest,
+	OpenChannelResponse, PrunePaymentsRequest, PrunePaymentsResponse, RunPreflightChecksRequest,
+	RunPreflightChecksResponse, SignMessageRequest, SignMessageResponse, SpliceInRequest,
+	SpliceInResponse, SpliceOutRequest, SpliceOutResponse, SpontaneousSendRequest,
 	SpontaneousSendResponse, SubscribeEventsRequest, UnifiedSendRequest, UnifiedSendResponse,
 	UpdateChannelConfigRequest, UpdateChannelConfigResponse, VerifySignatureRequest,
-	VerifySignatureResponse,
+	VerifySignatureResponse, WhoAmIRequest, WhoAmIResponse,
 };
 use ldk_server_grpc::endpoints::{
 	BOLT11_CLAIM_FOR_HASH_PATH, BOLT11_FAIL_FOR_HASH_PATH, BOLT11_RECEIVE_FOR_HASH_PATH,
 	BOLT11_RECEIVE_PATH, BOLT11_RECEIVE_VARIABLE_AMOUNT_VIA_JIT_CHANNEL_PATH,
 	BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH, BOLT11_SEND_PATH, BOLT12_RECEIVE_PATH, BOLT12_SEND_PATH,
-	CLOSE_CHANNEL_PATH, CONNECT_PEER_PATH, DECODE_INVOICE_PATH, DECODE_OFFER_PATH,
-	DISCONNECT_PEER_PATH, EXPORT_PATHFINDING_SCORES_PATH, FORCE_CLOSE_CHANNEL_PATH,
-	GET_BALANCES_PATH, GET_METRICS_PATH, GET_NODE_INFO_PATH, GET_PAYMENT_DETAILS_PATH,
-	GRAPH_GET_CHANNEL_PATH, GRAPH_GET_NODE_PATH, GRAPH_LIST_CHANNELS_PATH, GRAPH_LIST_NODES_PATH,
-	GRPC_SERVICE_PREFIX, LIST_CHANNELS_PATH, LIST_FORWARDED_PAYMENTS_PATH, LIST_PAYMENTS_PATH,
-	LIST_PEERS_PATH, ONCHAIN_RECEIVE_PATH, ONCHAIN_SEND_PATH, OPEN_CHANNEL_PATH, SIGN_MESSAGE_PATH,
+	CLOSE_CHANNEL_PATH, CONNECT_PEER_PATH, CREATE_LNURL_WITHDRAW_PATH, DECODE_INVOICE_PATH,
+	DECODE_OFFER_PATH, DISCONNECT_PEER_PATH, EXPORT_PATHFINDING_SCORES_PATH,
+	FORCE_CLOSE_CHANNEL_PATH, GET_API_VERSION_PATH, GET_BALANCES_PATH,
+	GET_CHANNEL_ANNOUNCEMENT_STATUS_PATH, GET_CHANNEL_ROUTING_STATS_PATH,
+	GET_CLAIMABLE_BALANCES_PATH, GET_CONFIG_PATH, GET_FORWARDED_PAYMENT_PATH, GET_METRICS_PATH,
+	GET_NODE_INFO_PATH, GET_PAYMENT_DETAILS_PATH, GET_PEER_DETAILS_PATH, GET_SNAPSHOT_PATH,
+	GET_WALLET_DESCRIPTOR_PATH, GRAPH_GET_CHANNEL_PATH, GRAPH_GET_NODE_PATH,
+	GRAPH_LIST_CHANNELS_PATH, GRAPH_LIST_NODES_PATH, GRPC_SERVICE_PREFIX, IS_OWN_INVOICE_PATH,
+	LIST_AUDIT_LOG_PATH, LIST_CHANNELS_PATH, LIST_FORWARDED_PAYMENTS_PATH, LIST_OFFERS_PATH,
+	LIST_PAYMENTS_PATH, LIST_PEERS_PATH, ONCHAIN_RECEIVE_PATH, ONCHAIN_SEND_PATH,
+	OPEN_CHANNEL_PATH, PRUNE_PAYMENTS_PATH, RUN_PREFLIGHT_CHECKS_PATH, SIGN_MESSAGE_PATH,
 	SPLICE_IN_PATH, SPLICE_OUT_PATH, SPONTANEOUS_SEND_PATH, SUBSCRIBE_EVENTS_PATH,
-	UNIFIED_SEND_PATH, UPDATE_CHANNEL_CONFIG_PATH, VERIFY_SIGNATURE_PATH,
+	UNIFIED_SEND_PATH, UPDATE_CHANNEL_CONFIG_PATH, VERIFY_SIGNATURE_PATH, WHO_AM_I_PATH,
 };
 use ldk_server_grpc::events::EventEnvelope;
 use ldk_server_grpc::grpc::{
 	decode_grpc_body, encode_grpc_frame, percent_decode, GRPC_STATUS_FAILED_PRECONDITION,
-	GRPC_STATUS_INTERNAL, GRPC_STATUS_INVALID_ARGUMENT, GRPC_STATUS_OK,
-	GRPC_STATUS_UNAUTHENTICATED, GRPC_STATUS_UNAVAILABLE,
+	GRPC_STATUS_INTERNAL, GRPC_STATUS_INVALID_ARGUMENT, GRPC_STATUS_NOT_FOUND, GRPC_STATUS_OK,
+	GRPC_STATUS_PERMISSION_DENIED, GRPC_STATUS_RESOURCE_EXHAUSTED, GRPC_STATUS_UNAUTHENTICATED,
+	GRPC_STATUS_UNAVAILABLE,
 };
 use prost::Message;
 use reqwest::header::HeaderMap;
@@ -67,6 +82,7 @@ use rustls_pemfile::certs;
 use crate::error::LdkServerError;
 use crate::error::LdkServerErrorCode::{
 	AuthError, InternalError, InternalServerError, InvalidRequestError, LightningError,
+	NotFoundError, PermissionDeniedError, RateLimited,
 };
 
 type StreamingClient = HyperClient<HttpsConnector<hyper::client::HttpConnector>, HyperBody>;
@@ -424,6 +440,129 @@ impl LdkServerClient {
 		self.grpc_unary(&request, GRAPH_GET_NODE_PATH).await
 	}
 
+	/// Retrieves entries from the audit log of fund-moving operations.
+	pub async fn list_audit_log(
+		&self, request: ListAuditLogRequest,
+	) -> Result<ListAuditLogResponse, LdkServerError> {
+		self.grpc_unary(&request, LIST_AUDIT_LOG_PATH).await
+	}
+
+	/// Returns the name and scopes of the API key used by this client.
+	pub async fn who_am_i(&self, request: WhoAmIRequest) -> Result<WhoAmIResponse, LdkServerError> {
+		self.grpc_unary(&request, WHO_AM_I_PATH).await
+	}
+
+	/// Retrieve the balances tracked by the channel monitors of all channels.
+	pub async fn get_claimable_balances(
+		&self, request: GetClaimableBalancesRequest,
+	) -> Result<GetClaimableBalancesResponse, LdkServerError> {
+		self.grpc_unary(&request, GET_CLAIMABLE_BALANCES_PATH).await
+	}
+
+	/// Retrieve routing statistics per channel.
+	pub async fn get_channel_routing_stats(
+		&self, request: GetChannelRoutingStatsRequest,
+	) -> Result<GetChannelRoutingStatsResponse, LdkServerError> {
+		self.grpc_unary(&request, GET_CHANNEL_ROUTING_STATS_PATH).await
+	}
+
+	/// Retrieve the API version and enabled optional features of the server.
+	///
+	/// Servers predating this endpoint fail the call with an `UNIMPLEMENTED` status, which
+	/// clients may treat as an API version of `0`.
+	pub async fn server_version(&self) -> Result<GetApiVersionResponse, LdkServerError> {
+		self.grpc_unary(&GetApiVersionRequest {}, GET_API_VERSION_PATH).await
+	}
+
+	/// Retrieve the effective runtime configuration with all secrets redacted.
+	pub async fn get_config(
+		&self, request: GetConfigRequest,
+	) -> Result<GetConfigResponse, LdkServerError> {
+		self.grpc_unary(&request, GET_CONFIG_PATH).await
+	}
+
+	/// Retrieves the BOLT12 offers created by this node, newest first.
+	/// For API contract/usage, refer to docs for [`ListOffersRequest`] and [`ListOffersResponse`].
+	pub async fn list_offers(
+		&self, request: ListOffersRequest,
+	) -> Result<ListOffersResponse, LdkServerError> {
+		self.grpc_unary(&request, LIST_OFFERS_PATH).await
+	}
+
+	/// Removes old succeeded and failed payments from the payment history.
+	/// For API contract/usage, refer to docs for [`PrunePaymentsRequest`] and [`PrunePaymentsResponse`].
+	pub async fn prune_payments(
+		&self, request: PrunePaymentsRequest,
+	) -> Result<PrunePaymentsResponse, LdkServerError> {
+		self.grpc_unary(&request, PRUNE_PAYMENTS_PATH).await
+	}
+
+	/// Retrieve whether a channel is announced in the node's network graph.
+	/// For API contract/usage, refer to docs for [`GetChannelAnnouncementStatusRequest`] and
+	/// [`GetChannelAnnouncementStatusResponse`].
+	pub async fn get_channel_announcement_status(
+		&self, request: GetChannelAnnouncementStatusRequest,
+	) -> Result<GetChannelAnnouncementStatusResponse, LdkServerError> {
+		self.grpc_unary(&request, GET_CHANNEL_ANNOUNCEMENT_STATUS_PATH).await
+	}
+
+	/// Retrieve details on a single peer, including its announced addresses and features.
+	/// For API contract/usage, refer to docs for [`GetPeerDetailsRequest`] and [`GetPeerDetailsResponse`].
+	pub async fn get_peer_details(
+		&self, request: GetPeerDetailsRequest,
+	) -> Result<GetPeerDetailsResponse, LdkServerError> {
+		self.grpc_unary(&request, GET_PEER_DETAILS_PATH).await
+	}
+
+	/// Check whether a BOLT11 invoice was issued by this node.
+	/// For API contract/usage, refer to docs for [`IsOwnInvoiceRequest`] and [`IsOwnInvoiceResponse`].
+	pub async fn is_own_invoice(
+		&self, request: IsOwnInvoiceRequest,
+	) -> Result<IsOwnInvoiceResponse, LdkServerError> {
+		self.grpc_unary(&request, IS_OWN_INVOICE_PATH).await
+	}
+
+	/// Run a set of preflight checks on the node and report the outcome of each.
+	/// For API contract/usage, refer to docs for [`RunPreflightChecksRequest`] and [`RunPreflightChecksResponse`].
+	pub async fn run_preflight_checks(
+		&self, request: RunPreflightChecksRequest,
+	) -> Result<RunPreflightChecksResponse, LdkServerError> {
+		self.grpc_unary(&request, RUN_PREFLIGHT_CHECKS_PATH).await
+	}
+
+	/// Retrieve a single forwarded payment by its identifier.
+	/// For API contract/usage, refer to docs for [`GetForwardedPaymentRequest`] and [`GetForwardedPaymentResponse`].
+	pub async fn get_forwarded_payment(
+		&self, request: GetForwardedPaymentRequest,
+	) -> Result<GetForwardedPaymentResponse, LdkServerError> {
+		self.grpc_unary(&request, GET_FORWARDED_PAYMENT_PATH).await
+	}
+
+	/// Retrieve the watch-only output descriptors of the node's on-chain wallet.
+	/// For API contract/usage, refer to docs for [`GetWalletDescriptorRequest`] and [`GetWalletDescriptorResponse`].
+	pub async fn get_wallet_descriptor(
+		&self, request: GetWalletDescriptorRequest,
+	) -> Result<GetWalletDescriptorResponse, LdkServerError> {
+		self.grpc_unary(&request, GET_WALLET_DESCRIPTOR_PATH).await
+	}
+
+	/// Retrieve a snapshot of the node's balances, channels, peers, pending payments, sync status
+	/// and alerts.
+	/// For API contract/usage, refer to docs for [`GetSnapshotRequest`] and [`GetSnapshotResponse`].
+	pub async fn get_snapshot(
+		&self, request: GetSnapshotRequest,
+	) -> Result<GetSnapshotResponse, LdkServerError> {
+		self.grpc_unary(&request, GET_SNAPSHOT_PATH).await
+	}
+
+	/// Create a single-use LNURL-withdraw that a wallet can pull funds from.
+	/// For API contract/usage, refer to docs for [`CreateLnurlWithdrawRequest`] and [`CreateLnurlWithdrawResponse`].
+	pub async fn create_lnurl_withdraw(
+		&self, request: CreateLnurlWithdrawRequest,
+	) -> Result<CreateLnurlWithdrawResponse, LdkServerError> {
+		self.grpc_unary(&request, CREATE_LNURL_WITHDRAW_PATH).await
+	}
+
 	/// Subscribe to a stream of server events via server-streaming gRPC.
 	///
 	/// Returns an [`EventStream`] that yields [`EventEnvelope`] messages as they arrive.
@@ -431,11 +570,14 @@ impl LdkServerClient {
 		self.grpc_server_streaming(&SubscribeEventsRequest {}, SUBSCRIBE_EVENTS_PATH).await
 	}
 
-	/// Send a unary gRPC request and decode the response.
-	async fn grpc_unary<Rq: Message, Rs: Message + Default>(
-		&self, request: &Rq, method: &str,
-	) -> Result<Rs, LdkServerError> {
-		let grpc_body = encode_grpc_frame(&request.encode_to_vec()).to_vec();
+	/// Send a raw protobuf-encoded request to the given RPC method, e.g. [`GET_NODE_INFO_PATH`],
+	/// and return the raw protobuf-encoded response without decoding it.
+	///
+	/// This is an escape hatch for proxying or storing responses as-is. The caller is responsible
+	/// for encoding `request` as the method's request message and for decoding the response as its
+	/// response message. Errors are still reported as [`LdkServerError`]s.
+	pub async fn call_raw(&self, method: &str, request: &[u8]) -> Result<Vec<u8>, LdkServerError> {
+		let grpc_body = encode_grpc_frame(request).to_vec();
 		let content_length = grpc_body.len().to_string();
 
 		let url = format!("https://{}{}{}", self.base_url, GRPC_SERVICE_PREFIX, method);
@@ -466,8 +608,15 @@ impl LdkServerClient {
 
 		let proto_bytes = decode_grpc_body(&payload)
 			.map_err(|e| LdkServerError::new(InternalError, e.message))?;
+		Ok(proto_bytes.to_vec())
+	}
 
-		Rs::decode(proto_bytes).map_err(|e| {
+	/// Send a unary gRPC request and decode the response.
+	async fn grpc_unary<Rq: Message, Rs: Message + Default>(
+		&self, request: &Rq, method: &str,
+	) -> Result<Rs, LdkServerError> {
+		let proto_bytes = self.call_raw(method, &request.encode_to_vec()).await?;
+		Rs::decode(proto_bytes.as_slice()).map_err(|e| {
 			LdkServerError::new(InternalError, format!("Failed to decode gRPC response: {}", e))
 		})
 	}
@@ -558,7 +707,9 @@ fn check_grpc_unary_response_len(len: u64) -> Result<(), LdkServerError> {
 /// Map a gRPC status code to an LdkServerError.
 fn grpc_code_to_error(code: u32, message: String) -> LdkServerError {
 	match code {
-		GRPC_STATUS_INVALID_ARGUMENT => LdkServerError::new(InvalidRequestError, message),
+		GRPC_STATUS_INVALID_ARGUMENT | GRPC_STATUS_RESOURCE_EXHAUSTED => {
+			LdkServerError::new(InvalidRequestError, message)
+		},
 		GRPC_STATUS_FAILED_PRECONDITION => LdkServerError::new(LightningError, message),
 		GRPC_STATUS_INTERNAL => LdkServerError::new(InternalServerError, message),
 		GRPC_STATUS_UNAVAILABLE => LdkServerError::new(
@@ -570,6 +721,8 @@ fn grpc_code_to_error(code: u32, message: String) -> LdkServerError {
 			},
 		),
 		GRPC_STATUS_UNAUTHENTICATED => LdkServerError::new(AuthError, message),
+		GRPC_STATUS_PERMISSION_DENIED => LdkServerError::new(PermissionDeniedError, message),
+		GRPC_STATUS_NOT_FOUND => LdkServerError::new(NotFoundError, message),
 		_ => LdkServerError::new(
 			InternalError,
 			if message.is_empty() {
@@ -592,6 +745,18 @@ fn grpc_error_from_headers(headers: &HeaderMap) -> Option<LdkServerError> {
 		.and_then(|v| v.to_str().ok())
 		.map(percent_decode)
 		.unwrap_or_default();
+
+	// The server only tells when to retry if the request exceeded a rate limit, rather than e.g.
+	// the request body size limit.
+	if code == GRPC_STATUS_RESOURCE_EXHAUSTED {
+		let retry_after_secs = headers
+			.get("retry-after")
+			.and_then(|v| v.to_str().ok())
+			.and_then(|v| v.parse::<u64>().ok());
+		if let Some(retry_after_secs) = retry_after_secs {
+			return Some(LdkServerError::new(RateLimited { retry_after_secs }, message));
+		}
+	}
 	Some(grpc_code_to_error(code, message))
 }
 
@@ -755,6 +920,23 @@ mod tests {
 		assert_eq!(err.message, "bad request");
 	}
 
+	#[test]
+	fn test_grpc_error_from_headers_detects_rate_limits() {
+		let mut headers = HeaderMap::new();
+		headers.insert("grpc-status", HeaderValue::from_static("8"));
+		headers.insert("grpc-message", HeaderValue::from_static("slow%20down"));
+		headers.insert("retry-after", HeaderValue::from_static("20"));
+
+		let err = grpc_error_from_headers(&headers).unwrap();
+		assert_eq!(err.error_code, RateLimited { retry_after_secs: 20 });
+		assert_eq!(err.message, "slow down");
+
+		// Exceeding other limits, such as the request body size, is not retryable.
+		headers.remove("retry-after");
+		let err = grpc_error_from_headers(&headers).unwrap();
+		assert_eq!(err.error_code, InvalidRequestError);
+	}
+
 	#[test]
 	fn test_grpc_code_to_error_marks_unavailable_streams() {
 		let err = grpc_code_to_error(GRPC_STATUS_UNAVAILABLE, "server shutting down".to_string());
@@ -849,9 +1031,12 @@ mod tests {
 	fn test_grpc_code_to_error_all_known_codes() {
 		let cases = [
 			(GRPC_STATUS_INVALID_ARGUMENT, InvalidRequestError, "msg"),
+			(GRPC_STATUS_RESOURCE_EXHAUSTED, InvalidRequestError, "msg"),
 			(GRPC_STATUS_UNAUTHENTICATED, AuthError, "msg"),
 			(GRPC_STATUS_FAILED_PRECONDITION, LightningError, "msg"),
 			(GRPC_STATUS_INTERNAL, InternalServerError, "msg"),
+			(GRPC_STATUS_PERMISSION_DENIED, PermissionDeniedError, "msg"),
+			(GRPC_STATUS_NOT_FOUND, NotFoundError, "msg"),
 		];
 		for (code, expected_error_code, msg) in cases {
 			let err = grpc_code_to_error(code, msg.to_string());
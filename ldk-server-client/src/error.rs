@@ -53,6 +53,18 @@ pub enum LdkServerErrorCode {
 	/// Please refer to [`ldk_server_grpc::error::ErrorCode::InternalServerError`].
 	InternalServerError,
 
+	/// Please refer to [`ldk_server_grpc::error::ErrorCode::PermissionDeniedError`].
+	PermissionDeniedError,
+
+	/// Please refer to [`ldk_server_grpc::error::ErrorCode::NotFoundError`].
+	NotFoundError,
+
+	/// The request was rejected because the API key exceeded its rate limit.
+	RateLimited {
+		/// How long to wait before retrying the request, in seconds, as told by the server.
+		retry_after_secs: u64,
+	},
+
 	/// There is an unknown error, it could be a client-side bug, unrecognized error-code, network error
 	/// or something else.
 	InternalError,
@@ -65,6 +77,9 @@ impl fmt::Display for LdkServerErrorCode {
 			LdkServerErrorCode::AuthError => write!(f, "AuthError"),
 			LdkServerErrorCode::LightningError => write!(f, "LightningError"),
 			LdkServerErrorCode::InternalServerError => write!(f, "InternalServerError"),
+			LdkServerErrorCode::PermissionDeniedError => write!(f, "PermissionDeniedError"),
+			LdkServerErrorCode::NotFoundError => write!(f, "NotFoundError"),
+			LdkServerErrorCode::RateLimited { .. } => write!(f, "RateLimited"),
 			LdkServerErrorCode::InternalError => write!(f, "InternalError"),
 		}
 	}
@@ -24,6 +24,9 @@ pub mod error;
 
 /// Request/Response structs required for interacting with the client.
 pub use ldk_server_grpc;
+/// The protobuf library the request/response structs are encoded with, for use with
+/// [`LdkServerClient::call_raw`](client::LdkServerClient::call_raw).
+pub use prost;
 
 /// Default maximum total CLTV expiry delta for payment routing.
 pub const DEFAULT_MAX_TOTAL_CLTV_EXPIRY_DELTA: u32 = 1008;
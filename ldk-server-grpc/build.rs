@@ -41,6 +41,8 @@ fn generate_protos() {
 			"api.DecodeInvoiceResponse.features",
 			"api.DecodeOfferResponse.features",
 			"types.GraphNodeAnnouncement.features",
+			"types.AuditLogEntry.request_summary",
+			"api.GetConfigResponse.config",
 		])
 		.type_attribute(
 			".",
@@ -68,6 +70,10 @@ fn generate_protos() {
 			"types.Payment.status",
 			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_payment_status\"))]",
 		)
+		.field_attribute(
+			"types.Payment.fee_breakdown",
+			"#[cfg_attr(feature = \"serde\", serde(skip_serializing_if = \"Option::is_none\"))]",
+		)
 		.field_attribute(
 			"types.ClaimableAwaitingConfirmations.source",
 			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_balance_source\"))]",
@@ -80,6 +86,10 @@ fn generate_protos() {
 			"types.DirectedShortChannelId.direction",
 			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_channel_direction\"))]",
 		)
+		.field_attribute(
+			"types.ClaimableBalance.kind",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_claimable_balance_kind\"))]",
+		)
 		.field_attribute(
 			"api.UnifiedSendResponse.payment_result",
 			"#[cfg_attr(feature = \"serde\", serde(flatten))]",
@@ -362,6 +362,10 @@ pub struct Bolt11SendRequest {
 	/// Configuration options for payment routing and pathfinding.
 	#[prost(message, optional, tag = "3")]
 	pub route_parameters: ::core::option::Option<super::types::RouteParametersConfig>,
+	/// Set to confirm paying a zero-amount invoice an `amount_msat` above the server's configured
+	/// `large_payment_warning_msat`. Such payments are rejected otherwise.
+	#[prost(bool, tag = "4")]
+	pub confirm_large: bool,
 }
 /// The response for the `Bolt11Send` RPC. On failure, a gRPC error status is returned.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -510,9 +514,10 @@ pub struct OpenChannelRequest {
 	/// The channel configuration to be used for opening this channel. If unset, default ChannelConfig is used.
 	#[prost(message, optional, tag = "5")]
 	pub channel_config: ::core::option::Option<super::types::ChannelConfig>,
-	/// Whether the channel should be public.
-	#[prost(bool, tag = "6")]
-	pub announce_channel: bool,
+	/// Whether the channel should be public. If unset, the server's configured
+	/// `channels.announce_channels_by_default` applies, which defaults to private.
+	#[prost(bool, optional, tag = "6")]
+	pub announce_channel: ::core::option::Option<bool>,
 	/// Allow the counterparty to spend all its channel balance. This cannot be set together with `announce_channel`.
 	#[prost(bool, tag = "7")]
 	pub disable_counterparty_reserve: bool,
@@ -527,6 +532,12 @@ pub struct OpenChannelResponse {
 	/// The local channel id of the created channel that user can use to refer to channel.
 	#[prost(string, tag = "1")]
 	pub user_channel_id: ::prost::alloc::string::String,
+	/// The reserve the counterparty is expected to require this node to keep in the channel, which
+	/// cannot be spent. It is estimated as 1% of the channel amount with a floor of 1,000 sats, as
+	/// the counterparty only sets it once the channel is negotiated. `ListChannels` reports the actual
+	/// reserve as `unspendable_punishment_reserve`.
+	#[prost(uint64, tag = "2")]
+	pub estimated_reserve_sats: u64,
 }
 /// Increases the channel balance by the given amount.
 /// See more: <https://docs.rs/ldk-node/latest/ldk_node/struct.Node.html#method.splice_in>
@@ -1172,11 +1183,18 @@ pub struct DecodeInvoiceResponse {
 	#[prost(string, tag = "12")]
 	pub currency: ::prost::alloc::string::String,
 	/// The payment metadata, hex-encoded. Only present if the invoice includes payment metadata.
+	/// When paying the invoice, it is forwarded to the recipient automatically.
 	#[prost(string, optional, tag = "13")]
 	pub payment_metadata: ::core::option::Option<::prost::alloc::string::String>,
 	/// Whether the invoice has expired.
 	#[prost(bool, tag = "15")]
 	pub is_expired: bool,
+	/// The amount in satoshis and bitcoin, if specified in the invoice.
+	#[prost(message, optional, tag = "16")]
+	pub formatted_amount: ::core::option::Option<super::types::FormattedAmount>,
+	/// Whether the invoice leaves the amount to the payer.
+	#[prost(bool, tag = "17")]
+	pub is_zero_amount: bool,
 }
 /// Decode a BOLT12 offer and return its parsed fields.
 /// This does not require a running node — it only parses the offer string.
@@ -1233,6 +1251,515 @@ pub struct DecodeOfferResponse {
 	/// Whether the offer has expired.
 	#[prost(bool, tag = "12")]
 	pub is_expired: bool,
+	/// The amount in satoshis and bitcoin, if the offer has a fixed Bitcoin amount.
+	#[prost(message, optional, tag = "13")]
+	pub formatted_amount: ::core::option::Option<super::types::FormattedAmount>,
+	/// Whether the offer leaves the amount to the payer.
+	#[prost(bool, tag = "14")]
+	pub is_zero_amount: bool,
+}
+/// Retrieves entries from the audit log, newest first.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAuditLogRequest {
+	/// `page_token` is a pagination token.
+	///
+	/// To query for the first page, `page_token` must not be specified.
+	///
+	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
+	/// page's response.
+	#[prost(message, optional, tag = "1")]
+	pub page_token: ::core::option::Option<super::types::PageToken>,
+	/// If set, only entries recorded strictly after this time (in seconds since the UNIX epoch)
+	/// are returned.
+	#[prost(uint64, optional, tag = "2")]
+	pub after: ::core::option::Option<u64>,
+	/// If set, only entries recorded strictly before this time (in seconds since the UNIX epoch)
+	/// are returned.
+	#[prost(uint64, optional, tag = "3")]
+	pub before: ::core::option::Option<u64>,
+}
+/// The response for the `ListAuditLog` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAuditLogResponse {
+	/// List of audit log entries.
+	#[prost(message, repeated, tag = "1")]
+	pub entries: ::prost::alloc::vec::Vec<super::types::AuditLogEntry>,
+	/// `next_page_token` is a pagination token, used to retrieve the next page of results.
+	/// Use this value to query for next-page of paginated operation, by specifying
+	/// this value as the `page_token` in the next request.
+	///
+	/// If `next_page_token` is `None`, then the "last page" of results has been processed and
+	/// there is no more data to be retrieved.
+	///
+	/// If `next_page_token` is not `None`, it does not necessarily mean that there is more data in the
+	/// result set. The only way to know when you have reached the end of the result set is when
+	/// `next_page_token` is `None`.
+	///
+	/// **Caution**: Clients must not assume a specific number of records to be present in a page for
+	/// paginated response.
+	#[prost(message, optional, tag = "2")]
+	pub next_page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// Returns the identity and scopes of the API key used to authenticate the request.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WhoAmIRequest {}
+/// The response for the `WhoAmI` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WhoAmIResponse {
+	/// The name of the API key used to authenticate the request.
+	#[prost(string, tag = "1")]
+	pub name: ::prost::alloc::string::String,
+	/// The scopes granted to the API key, including the ones implied by its highest scope.
+	/// One or more of `read_only`, `send` and `admin`.
+	#[prost(string, repeated, tag = "2")]
+	pub scopes: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Retrieves the balances tracked by the channel monitors of all channels, including ones that are
+/// closing or have been closed but not yet fully swept to the on-chain wallet.
+/// See more: <https://docs.rs/ldk-node/latest/ldk_node/struct.BalanceDetails.html#structfield.lightning_balances>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetClaimableBalancesRequest {}
+/// The response for the `GetClaimableBalances` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetClaimableBalancesResponse {
+	/// The claimable balances, one per output or HTLC tracked by a channel monitor.
+	#[prost(message, repeated, tag = "1")]
+	pub balances: ::prost::alloc::vec::Vec<super::types::ClaimableBalance>,
+}
+/// Retrieves routing statistics per channel, computed from the persisted forwarded payments.
+/// Each forwarded payment is attributed to the channel of its first outgoing HTLC.
+/// There is no per-channel index, so every request reads and decodes all persisted forwarded
+/// payments. Its cost grows with the number of forwards ever recorded, so avoid polling it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetChannelRoutingStatsRequest {}
+/// The response for the `GetChannelRoutingStats` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetChannelRoutingStatsResponse {
+	/// The statistics of every channel that forwarded at least one payment, ordered by fees earned,
+	/// highest first. This includes channels that have since been closed.
+	#[prost(message, repeated, tag = "1")]
+	pub channel_stats: ::prost::alloc::vec::Vec<super::types::ChannelRoutingStats>,
+	/// The fees, in milli-satoshis, earned across all channels. This is the sum of
+	/// `fees_earned_msat` over `channel_stats`.
+	#[prost(uint64, tag = "2")]
+	pub total_fees_earned_msat: u64,
+	/// The number of payments forwarded across all channels.
+	#[prost(uint64, tag = "3")]
+	pub total_forwards_count: u64,
+	/// The total amount, in milli-satoshis, forwarded across all channels.
+	#[prost(uint64, tag = "4")]
+	pub total_amount_forwarded_msat: u64,
+}
+/// Retrieves the API version implemented by the server along with the optional features it has
+/// enabled, allowing clients to detect capabilities and degrade gracefully against older servers.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetApiVersionRequest {}
+/// The response for the `GetApiVersion` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetApiVersionResponse {
+	/// The API version implemented by the server. The same value is returned in the
+	/// `x-ldk-server-api-version` header of every response.
+	#[prost(uint32, tag = "1")]
+	pub api_version: u32,
+	/// The version of the LDK Server release.
+	#[prost(string, tag = "2")]
+	pub server_version: ::prost::alloc::string::String,
+	/// The version of LDK Node the server is built with, or `unknown` if it could not be determined
+	/// at build time.
+	#[prost(string, tag = "4")]
+	pub ldk_version: ::prost::alloc::string::String,
+	/// The optional features enabled on the server, any of `metrics`, `lsps2_client`,
+	/// `lsps2_service`, `tor`, `probing`, `outbound_payment_limit`, `payment_history_retention`,
+	/// `offer_expiry`, `rate_limiting`, `invoice_expiry_notifications` and `lnurl_withdraw`.
+	#[prost(string, repeated, tag = "3")]
+	pub features: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Retrieves the effective runtime configuration of the server with all secrets redacted, e.g. to
+/// attach it to a bug report.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetConfigRequest {}
+/// The response for the `GetConfig` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetConfigResponse {
+	/// The configuration values keyed by setting name. Secrets such as passwords, tokens and API keys
+	/// are replaced with `***`.
+	#[prost(btree_map = "string, string", tag = "1")]
+	pub config: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
+}
+/// Retrieves a list of the BOLT12 offers created by this node, newest first.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOffersRequest {
+	/// `page_token` is a pagination token.
+	///
+	/// To query for the first page, `page_token` must not be specified.
+	///
+	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
+	/// page's response.
+	#[prost(message, optional, tag = "1")]
+	pub page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// The response for the `ListOffers` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOffersResponse {
+	/// List of offers.
+	#[prost(message, repeated, tag = "1")]
+	pub offers: ::prost::alloc::vec::Vec<super::types::OfferRecord>,
+	/// `next_page_token` is a pagination token, used to retrieve the next page of results.
+	/// Use this value to query for next-page of paginated operation, by specifying
+	/// this value as the `page_token` in the next request.
+	///
+	/// If `next_page_token` is `None`, then the "last page" of results has been processed and
+	/// there is no more data to be retrieved.
+	///
+	/// **Caution**: Clients must not assume a specific number of records to be present in a page for
+	/// paginated response.
+	#[prost(message, optional, tag = "2")]
+	pub next_page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// Removes succeeded and failed payments from the payment history returned by `ListPayments`.
+/// Pending payments are never removed.
+///
+/// The node keeps its own record of each payment, so `GetPaymentDetails` and the protection against
+/// paying the same invoice twice are not affected.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PrunePaymentsRequest {
+	/// Removes payments last updated longer ago than this many seconds.
+	///
+	/// If neither limit is set, the configured `payments.history_retention` is applied.
+	#[prost(uint64, optional, tag = "1")]
+	pub max_age_secs: ::core::option::Option<u64>,
+	/// Removes all but this many of the most recent payments.
+	///
+	/// If neither limit is set, the configured `payments.history_retention` is applied.
+	#[prost(uint64, optional, tag = "2")]
+	pub max_records: ::core::option::Option<u64>,
+}
+/// The response for the `PrunePayments` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PrunePaymentsResponse {
+	/// The number of payments that were removed.
+	#[prost(uint64, tag = "1")]
+	pub pruned_count: u64,
+	/// The number of payments that remain in the payment history.
+	#[prost(uint64, tag = "2")]
+	pub remaining_count: u64,
+}
+/// Reports whether one of the node's channels is announced in the gossip network, as seen from the
+/// node's own network graph. Useful to diagnose public channels that are not being routed through.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetChannelAnnouncementStatusRequest {
+	/// The local `user_channel_id` of the channel.
+	#[prost(string, tag = "1")]
+	pub user_channel_id: ::prost::alloc::string::String,
+}
+/// The response for the `GetChannelAnnouncementStatus` RPC. On failure, a gRPC error status is
+/// returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetChannelAnnouncementStatusResponse {
+	/// The short channel ID of the channel. Not set until the funding transaction has confirmed.
+	#[prost(uint64, optional, tag = "1")]
+	pub short_channel_id: ::core::option::Option<u64>,
+	/// Whether the channel was opened as a public channel that is meant to be announced.
+	#[prost(bool, tag = "2")]
+	pub is_announced: bool,
+	/// Whether the `channel_announcement` of the channel is present in the network graph.
+	#[prost(bool, tag = "3")]
+	pub announcement_in_graph: bool,
+	/// The `timestamp` of the latest `channel_update` for the direction from this node to the
+	/// counterparty, if present in the network graph.
+	#[prost(uint32, optional, tag = "4")]
+	pub outbound_update_timestamp: ::core::option::Option<u32>,
+	/// The `timestamp` of the latest `channel_update` for the direction from the counterparty to this
+	/// node, if present in the network graph.
+	#[prost(uint32, optional, tag = "5")]
+	pub inbound_update_timestamp: ::core::option::Option<u32>,
+}
+/// Returns details on a single peer, combining what the node tracks about the connection with what
+/// the peer announces in the network graph. Fails with `NOT_FOUND` if the node is not a peer.
+///
+/// The time of the last connection attempt, the last connection error and the features the peer
+/// sent in its `init` message are not included, as ldk-node does not expose them.
+/// See more: <https://docs.rs/ldk-node/latest/ldk_node/struct.Node.html#method.list_peers>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPeerDetailsRequest {
+	/// The hex-encoded node ID of the peer.
+	#[prost(string, tag = "1")]
+	pub node_id: ::prost::alloc::string::String,
+}
+/// The response for the `GetPeerDetails` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPeerDetailsResponse {
+	/// The peer, including the address used to connect to it and whether it is connected.
+	#[prost(message, optional, tag = "1")]
+	pub peer: ::core::option::Option<super::types::Peer>,
+	/// The peer's node announcement from the network graph, including the addresses and features it
+	/// advertises. Unset if the peer has not announced itself or its announcement is not yet known.
+	#[prost(message, optional, tag = "2")]
+	pub node_announcement: ::core::option::Option<super::types::GraphNodeAnnouncement>,
+	/// The number of channels with the peer.
+	#[prost(uint32, tag = "3")]
+	pub num_channels: u32,
+	/// The number of channels with the peer that are ready to send and receive payments.
+	#[prost(uint32, tag = "4")]
+	pub num_usable_channels: u32,
+}
+/// Checks whether a BOLT11 invoice was issued by this node.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IsOwnInvoiceRequest {
+	/// The BOLT11 invoice string to check.
+	#[prost(string, tag = "1")]
+	pub invoice: ::prost::alloc::string::String,
+}
+/// The response for the `IsOwnInvoice` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IsOwnInvoiceResponse {
+	/// Whether the invoice is signed with this node's key and the node has a record of the
+	/// inbound payment it was created for.
+	#[prost(bool, tag = "1")]
+	pub is_own: bool,
+	/// The hex-encoded payment hash of the invoice.
+	#[prost(string, tag = "2")]
+	pub payment_hash: ::prost::alloc::string::String,
+}
+/// Runs a set of preflight checks on the node and reports the outcome of each, so deployment
+/// scripts can make sure the node is ready before relying on it.
+/// This checks that the chain source is reachable, the wallets are synced, at least one channel is
+/// usable, the on-chain balance covers the anchor channel reserve and the store is writable.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunPreflightChecksRequest {}
+/// The response for the `RunPreflightChecks` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunPreflightChecksResponse {
+	/// The outcome of each check, in the order they were run.
+	#[prost(message, repeated, tag = "1")]
+	pub checks: ::prost::alloc::vec::Vec<super::types::PreflightCheck>,
+}
+/// Retrieves a single forwarded payment by the identifier it is stored under.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetForwardedPaymentRequest {
+	/// The hex-encoded identifier of the forward, as returned in `ForwardedPayment.id` by
+	/// `ListForwardedPayments` and the `PaymentForwarded` event.
+	#[prost(string, tag = "1")]
+	pub forward_id: ::prost::alloc::string::String,
+}
+/// The response for the `GetForwardedPayment` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetForwardedPaymentResponse {
+	/// The forwarded payment.
+	/// Will be `None` if no forward was recorded under the given identifier.
+	#[prost(message, optional, tag = "1")]
+	pub forwarded_payment: ::core::option::Option<super::types::ForwardedPayment>,
+}
+/// Retrieve the output descriptors of the node's on-chain wallet, for importing it into another
+/// wallet as watch-only.
+/// See more: <https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetWalletDescriptorRequest {}
+/// The response `content` for the `GetWalletDescriptor` API, when HttpStatusCode is OK (200).
+/// When HttpStatusCode is not OK (non-200), the response `content` contains a serialized `ErrorResponse`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetWalletDescriptorResponse {
+	/// The descriptor of the addresses handed out to receive funds, with its checksum. It contains
+	/// the account's extended public key only, never private keys.
+	#[prost(string, tag = "1")]
+	pub external_descriptor: ::prost::alloc::string::String,
+	/// The descriptor of the change addresses, with its checksum.
+	#[prost(string, tag = "2")]
+	pub internal_descriptor: ::prost::alloc::string::String,
+}
+/// Retrieves a snapshot of the node's state for monitoring, combining what `GetNodeInfo`,
+/// `GetBalances`, `ListChannels`, `ListPeers` and `RunPreflightChecks` return into one response.
+/// The node's state is read in one go before the response is assembled, so the parts are as
+/// consistent with each other as the node allows.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSnapshotRequest {}
+/// The response for the `GetSnapshot` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetSnapshotResponse {
+	/// The time the snapshot was taken, in seconds since the Unix epoch.
+	#[prost(uint64, tag = "1")]
+	pub timestamp: u64,
+	/// The node's info, including when its wallets were last synced.
+	#[prost(message, optional, tag = "2")]
+	pub node_info: ::core::option::Option<GetNodeInfoResponse>,
+	/// The node's balances.
+	#[prost(message, optional, tag = "3")]
+	pub balances: ::core::option::Option<GetBalancesResponse>,
+	/// The node's channels.
+	#[prost(message, repeated, tag = "4")]
+	pub channels: ::prost::alloc::vec::Vec<super::types::Channel>,
+	/// The peers the node is connected to or has channels with.
+	#[prost(message, repeated, tag = "5")]
+	pub peers: ::prost::alloc::vec::Vec<super::types::Peer>,
+	/// The payments that are still pending.
+	#[prost(message, repeated, tag = "6")]
+	pub pending_payments: ::prost::alloc::vec::Vec<super::types::Payment>,
+	/// The preflight checks that did not pass. The `persistence_writable` check is not run, as it
+	/// writes to the store.
+	#[prost(message, repeated, tag = "7")]
+	pub alerts: ::prost::alloc::vec::Vec<super::types::PreflightCheck>,
+}
+/// Creates a single-use LNURL-withdraw, which a wallet scanning it can pull funds from by handing
+/// the node an invoice to pay.
+/// The node serves the LNURL-withdraw endpoints under `payments.lnurl_base_url`, which has to be
+/// configured and reachable by wallets.
+/// See more: <https://github.com/lnurl/luds/blob/luds/03.md>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateLnurlWithdrawRequest {
+	/// The most the withdrawal invoice may request, in millisatoshis.
+	#[prost(uint64, tag = "1")]
+	pub max_amount_msat: u64,
+	/// The least the withdrawal invoice may request, in millisatoshis. Defaults to `max_amount_msat`,
+	/// i.e. a fixed amount.
+	#[prost(uint64, optional, tag = "2")]
+	pub min_amount_msat: ::core::option::Option<u64>,
+	/// The description wallets suggest for the withdrawal invoice. Defaults to an empty description.
+	#[prost(string, optional, tag = "3")]
+	pub description: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// The response for the `CreateLnurlWithdraw` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateLnurlWithdrawResponse {
+	/// The bech32-encoded LNURL to hand to the wallet, usually as a QR code.
+	#[prost(string, tag = "1")]
+	pub lnurl: ::prost::alloc::string::String,
+	/// The URL the LNURL encodes.
+	#[prost(string, tag = "2")]
+	pub url: ::prost::alloc::string::String,
+	/// The hex-encoded identifier of the withdraw, as reported by the `LnurlWithdrawClaimed` event.
+	#[prost(string, tag = "3")]
+	pub withdraw_id: ::prost::alloc::string::String,
 }
 /// Subscribe to a stream of server events.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
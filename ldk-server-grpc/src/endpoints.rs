@@ -10,6 +10,13 @@
 /// gRPC path prefix for the LightningNode service.
 pub const GRPC_SERVICE_PREFIX: &str = "/api.LightningNode/";
 
+/// The version of the API implemented by this crate. It is bumped whenever the API changes in a way
+/// clients may need to detect, e.g. when endpoints or fields are added.
+pub const API_VERSION: u32 = 1;
+
+/// The response header in which the server reports the [`API_VERSION`] it implements.
+pub const API_VERSION_HEADER: &str = "x-ldk-server-api-version";
+
 pub const GET_NODE_INFO_PATH: &str = "GetNodeInfo";
 pub const GET_BALANCES_PATH: &str = "GetBalances";
 pub const ONCHAIN_RECEIVE_PATH: &str = "OnchainReceive";
@@ -48,5 +55,21 @@ pub const GRAPH_LIST_NODES_PATH: &str = "GraphListNodes";
 pub const GRAPH_GET_NODE_PATH: &str = "GraphGetNode";
 pub const DECODE_INVOICE_PATH: &str = "DecodeInvoice";
 pub const DECODE_OFFER_PATH: &str = "DecodeOffer";
+pub const LIST_AUDIT_LOG_PATH: &str = "ListAuditLog";
+pub const WHO_AM_I_PATH: &str = "WhoAmI";
+pub const GET_CLAIMABLE_BALANCES_PATH: &str = "GetClaimableBalances";
+pub const GET_CHANNEL_ROUTING_STATS_PATH: &str = "GetChannelRoutingStats";
+pub const GET_API_VERSION_PATH: &str = "GetApiVersion";
+pub const GET_CONFIG_PATH: &str = "GetConfig";
+pub const LIST_OFFERS_PATH: &str = "ListOffers";
+pub const PRUNE_PAYMENTS_PATH: &str = "PrunePayments";
+pub const GET_CHANNEL_ANNOUNCEMENT_STATUS_PATH: &str = "GetChannelAnnouncementStatus";
+pub const GET_PEER_DETAILS_PATH: &str = "GetPeerDetails";
+pub const IS_OWN_INVOICE_PATH: &str = "IsOwnInvoice";
+pub const RUN_PREFLIGHT_CHECKS_PATH: &str = "RunPreflightChecks";
+pub const GET_FORWARDED_PAYMENT_PATH: &str = "GetForwardedPayment";
+pub const GET_WALLET_DESCRIPTOR_PATH: &str = "GetWalletDescriptor";
+pub const GET_SNAPSHOT_PATH: &str = "GetSnapshot";
+pub const CREATE_LNURL_WITHDRAW_PATH: &str = "CreateLnurlWithdraw";
 pub const GET_METRICS_PATH: &str = "metrics";
 pub const SUBSCRIBE_EVENTS_PATH: &str = "SubscribeEvents";
@@ -51,6 +51,11 @@ pub enum ErrorCode {
 	LightningError = 3,
 	/// Used when an internal server error occurred. The client is probably at no fault.
 	InternalServerError = 4,
+	/// Used when the request was authenticated, but the API key lacks the scope required by the
+	/// requested endpoint.
+	PermissionDeniedError = 5,
+	/// Used when the object the request refers to, e.g. a peer, does not exist.
+	NotFoundError = 6,
 }
 impl ErrorCode {
 	/// String value of the enum field names used in the ProtoBuf definition.
@@ -64,6 +69,8 @@ impl ErrorCode {
 			ErrorCode::AuthError => "AUTH_ERROR",
 			ErrorCode::LightningError => "LIGHTNING_ERROR",
 			ErrorCode::InternalServerError => "INTERNAL_SERVER_ERROR",
+			ErrorCode::PermissionDeniedError => "PERMISSION_DENIED_ERROR",
+			ErrorCode::NotFoundError => "NOT_FOUND_ERROR",
 		}
 	}
 	/// Creates an enum from field names used in the ProtoBuf definition.
@@ -74,6 +81,8 @@ impl ErrorCode {
 			"AUTH_ERROR" => Some(Self::AuthError),
 			"LIGHTNING_ERROR" => Some(Self::LightningError),
 			"INTERNAL_SERVER_ERROR" => Some(Self::InternalServerError),
+			"PERMISSION_DENIED_ERROR" => Some(Self::PermissionDeniedError),
+			"NOT_FOUND_ERROR" => Some(Self::NotFoundError),
 			_ => None,
 		}
 	}
@@ -14,7 +14,7 @@
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct EventEnvelope {
-	#[prost(oneof = "event_envelope::Event", tags = "2, 3, 4, 6, 7, 8")]
+	#[prost(oneof = "event_envelope::Event", tags = "2, 3, 4, 6, 7, 8, 9, 10, 11, 12")]
 	pub event: ::core::option::Option<event_envelope::Event>,
 }
 /// Nested message and enum types in `EventEnvelope`.
@@ -36,6 +36,14 @@ pub mod event_envelope {
 		PaymentClaimable(super::PaymentClaimable),
 		#[prost(message, tag = "8")]
 		ChannelStateChanged(super::ChannelStateChanged),
+		#[prost(message, tag = "9")]
+		PaymentsPruned(super::PaymentsPruned),
+		#[prost(message, tag = "10")]
+		OfferExpired(super::OfferExpired),
+		#[prost(message, tag = "11")]
+		InvoiceExpired(super::InvoiceExpired),
+		#[prost(message, tag = "12")]
+		LnurlWithdrawClaimed(super::LnurlWithdrawClaimed),
 	}
 }
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -204,6 +212,70 @@ pub struct PaymentForwarded {
 	#[prost(message, optional, tag = "1")]
 	pub forwarded_payment: ::core::option::Option<super::types::ForwardedPayment>,
 }
+/// PaymentsPruned indicates that payments were removed from the payment history, either by the
+/// configured `payments.history_retention` or via `PrunePayments`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaymentsPruned {
+	/// The number of payments that were removed.
+	#[prost(uint64, tag = "1")]
+	pub pruned_count: u64,
+	/// The number of payments that remain in the payment history.
+	#[prost(uint64, tag = "2")]
+	pub remaining_count: u64,
+}
+/// OfferExpired indicates that an offer created by this node passed its absolute expiry and was
+/// deactivated, as configured by `payments.deactivate_expired_offers`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OfferExpired {
+	/// The hex-encoded identifier of the offer.
+	#[prost(string, tag = "1")]
+	pub offer_id: ::prost::alloc::string::String,
+	/// The absolute expiry of the offer, in seconds since the Unix epoch.
+	#[prost(uint64, tag = "2")]
+	pub expired_at: u64,
+}
+/// InvoiceExpired indicates that a BOLT11 invoice created by this node passed its expiry without
+/// being paid, as configured by `payments.notify_expired_invoices`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InvoiceExpired {
+	/// The hex-encoded payment hash of the invoice.
+	#[prost(string, tag = "1")]
+	pub payment_hash: ::prost::alloc::string::String,
+	/// The amount requested by the invoice, in millisatoshis. Unset for variable-amount invoices.
+	#[prost(uint64, optional, tag = "2")]
+	pub amount_msat: ::core::option::Option<u64>,
+}
+/// LnurlWithdrawClaimed indicates that a wallet claimed an LNURL-withdraw created via
+/// `CreateLnurlWithdraw`, and the node started paying its withdrawal invoice.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LnurlWithdrawClaimed {
+	/// The hex-encoded identifier of the withdraw.
+	#[prost(string, tag = "1")]
+	pub withdraw_id: ::prost::alloc::string::String,
+	/// The identifier of the payment of the withdrawal invoice, to follow up on via the
+	/// `PaymentSuccessful` and `PaymentFailed` events.
+	#[prost(string, tag = "2")]
+	pub payment_id: ::prost::alloc::string::String,
+	/// The amount requested by the withdrawal invoice, in millisatoshis.
+	#[prost(uint64, tag = "3")]
+	pub amount_msat: u64,
+}
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -18,6 +18,9 @@ use bytes::{BufMut, Bytes, BytesMut};
 pub const GRPC_STATUS_OK: u32 = 0;
 pub const GRPC_STATUS_INVALID_ARGUMENT: u32 = 3;
 pub const GRPC_STATUS_DEADLINE_EXCEEDED: u32 = 4;
+pub const GRPC_STATUS_NOT_FOUND: u32 = 5;
+pub const GRPC_STATUS_PERMISSION_DENIED: u32 = 7;
+pub const GRPC_STATUS_RESOURCE_EXHAUSTED: u32 = 8;
 pub const GRPC_STATUS_FAILED_PRECONDITION: u32 = 9;
 pub const GRPC_STATUS_UNIMPLEMENTED: u32 = 12;
 pub const GRPC_STATUS_INTERNAL: u32 = 13;
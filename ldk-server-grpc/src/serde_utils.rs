@@ -19,17 +19,19 @@ use serde::Serializer;
 
 /// Generates a serde serializer that converts an `i32` proto enum field to its
 /// string name via `from_i32()` and `as_str_name()`.
+///
+/// Values this version does not know about, e.g. variants added by a newer server,
+/// are serialized as `unknown(<value>)` so the raw discriminant is not lost.
 macro_rules! stringify_enum_serializer {
 	($fn_name:ident, $enum_type:ty) => {
 		pub fn $fn_name<S>(value: &i32, serializer: S) -> Result<S::Ok, S::Error>
 		where
 			S: serde::Serializer,
 		{
-			let name = match <$enum_type>::from_i32(*value) {
-				Some(v) => v.as_str_name(),
-				None => "UNKNOWN",
-			};
-			serializer.serialize_str(name)
+			match <$enum_type>::from_i32(*value) {
+				Some(v) => serializer.serialize_str(v.as_str_name()),
+				None => serializer.serialize_str(&format!("unknown({})", value)),
+			}
 		}
 	};
 }
@@ -39,6 +41,8 @@ stringify_enum_serializer!(serialize_payment_status, crate::types::PaymentStatus
 stringify_enum_serializer!(serialize_balance_source, crate::types::BalanceSource);
 stringify_enum_serializer!(serialize_network, crate::types::Network);
 stringify_enum_serializer!(serialize_channel_direction, crate::types::ChannelDirection);
+stringify_enum_serializer!(serialize_claimable_balance_kind, crate::types::ClaimableBalanceKind);
+stringify_enum_serializer!(serialize_preflight_check_status, crate::types::PreflightCheckStatus);
 
 /// Serializes `Option<prost::bytes::Bytes>` as a hex string (or null).
 pub fn serialize_opt_bytes_hex<S>(
@@ -47,6 +47,28 @@ pub struct Payment {
 	/// The timestamp, in seconds since start of the UNIX epoch, when this entry was last updated.
 	#[prost(uint64, tag = "6")]
 	pub latest_update_timestamp: u64,
+	/// Where the fees of this payment went. Unset if no fee is known for the payment.
+	#[prost(message, optional, tag = "8")]
+	#[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+	pub fee_breakdown: ::core::option::Option<PaymentFeeBreakdown>,
+}
+/// The fees of a payment, split by who received them. Fields that do not apply to the payment
+/// are unset.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaymentFeeBreakdown {
+	/// The fee paid to the nodes that routed an outbound Lightning payment.
+	#[prost(uint64, optional, tag = "1")]
+	pub routing_fee_msat: ::core::option::Option<u64>,
+	/// The fee an LSP skimmed off an inbound payment that arrived via a JIT channel.
+	#[prost(uint64, optional, tag = "2")]
+	pub lsp_skimmed_fee_msat: ::core::option::Option<u64>,
+	/// The fee paid to miners for an outbound on-chain payment.
+	#[prost(uint64, optional, tag = "3")]
+	pub onchain_fee_msat: ::core::option::Option<u64>,
 }
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
@@ -331,6 +353,14 @@ pub struct ForwardedPayment {
 	/// This is the canonical outgoing HTLC representation.
 	#[prost(message, repeated, tag = "6")]
 	pub next_htlcs: ::prost::alloc::vec::Vec<HtlcLocator>,
+	/// The time the forward was recorded, as seconds since the Unix epoch.
+	/// Not set for forwards recorded by earlier versions of LDK Server.
+	#[prost(uint64, optional, tag = "7")]
+	pub forwarded_at: ::core::option::Option<u64>,
+	/// The hex-encoded identifier the forward is stored under, to look it up via
+	/// `GetForwardedPayment`.
+	#[prost(string, tag = "8")]
+	pub id: ::prost::alloc::string::String,
 }
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
@@ -464,6 +494,13 @@ pub struct Channel {
 	/// such that the outgoing HTLC is forwardable to this counterparty.
 	#[prost(uint32, optional, tag = "25")]
 	pub counterparty_forwarding_info_cltv_expiry_delta: ::core::option::Option<u32>,
+	/// The smallest value HTLC (in msat) we will accept, for this channel. Inbound HTLCs below it
+	/// are failed, and it is advertised as `htlc_minimum_msat` in our `channel_update`s.
+	#[prost(uint64, tag = "26")]
+	pub inbound_htlc_minimum_msat: u64,
+	/// The largest value HTLC (in msat) we currently will accept, for this channel.
+	#[prost(uint64, optional, tag = "27")]
+	pub inbound_htlc_maximum_msat: ::core::option::Option<u64>,
 }
 /// ChannelConfig represents the configuration settings for a channel in a Lightning Network node.
 /// See more: <https://docs.rs/lightning/latest/lightning/util/config/struct.ChannelConfig.html>
@@ -1243,6 +1280,191 @@ pub struct CustomTlvRecord {
 	#[prost(bytes = "bytes", tag = "2")]
 	pub value: ::prost::bytes::Bytes,
 }
+/// A single entry in the audit log, recorded for every fund-moving operation.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuditLogEntry {
+	/// Unique identifier of this entry.
+	#[prost(string, tag = "1")]
+	pub id: ::prost::alloc::string::String,
+	/// The time at which the operation was requested, in seconds since the UNIX epoch.
+	#[prost(uint64, tag = "2")]
+	pub timestamp: u64,
+	/// The name of the RPC that was invoked, e.g. `Bolt11Send`, or `LnurlWithdrawClaim` for claims of
+	/// LNURL-withdraws by wallets.
+	#[prost(string, tag = "3")]
+	pub operation: ::prost::alloc::string::String,
+	/// The authenticated actor that issued the request.
+	#[prost(string, tag = "4")]
+	pub actor: ::prost::alloc::string::String,
+	/// The correlation id of the request, as provided in the `x-correlation-id` header or
+	/// generated by the server if none was provided.
+	#[prost(string, tag = "5")]
+	pub correlation_id: ::prost::alloc::string::String,
+	/// A redacted summary of the request, containing amounts and destinations.
+	///
+	/// Secrets such as preimages and payer notes are never recorded.
+	#[prost(btree_map = "string, string", tag = "6")]
+	pub request_summary: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
+	/// Whether the operation completed successfully.
+	#[prost(bool, tag = "7")]
+	pub success: bool,
+	/// The error message if the operation failed.
+	#[prost(string, optional, tag = "8")]
+	pub error: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// A flattened view of a balance tracked by the channel monitor of a (possibly closing) channel.
+/// Unlike `LightningBalance`, every kind of balance shares the same fields, which makes it easier
+/// to reconcile funds across channels. It is derived from the same `LightningBalance`s returned by
+/// `GetBalances`, so both always agree.
+/// See more: <https://docs.rs/ldk-node/latest/ldk_node/enum.LightningBalance.html>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClaimableBalance {
+	/// The identifier of the channel this balance belongs to.
+	#[prost(string, tag = "1")]
+	pub channel_id: ::prost::alloc::string::String,
+	/// The identifier of our channel counterparty.
+	#[prost(string, tag = "2")]
+	pub counterparty_node_id: ::prost::alloc::string::String,
+	/// The amount of the balance, in satoshis.
+	#[prost(uint64, tag = "3")]
+	pub amount_sats: u64,
+	/// The state the balance is in.
+	#[prost(enumeration = "ClaimableBalanceKind", tag = "4")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_claimable_balance_kind")
+	)]
+	pub kind: i32,
+	/// The block height relevant to the balance, if any. Depending on `kind`, this is the height at
+	/// which the balance is considered confirmed (`CLAIMABLE_AWAITING_CONFIRMATIONS`), at which our
+	/// counterparty may claim it instead of us (`CONTENTIOUS_CLAIMABLE` and
+	/// `MAYBE_PREIMAGE_CLAIMABLE_HTLC`), or at which we may claim it (`MAYBE_TIMEOUT_CLAIMABLE_HTLC`).
+	#[prost(uint32, optional, tag = "5")]
+	pub maturity_height: ::core::option::Option<u32>,
+}
+/// Routing statistics of a single channel, aggregated over the forwarded payments that left our
+/// node through it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChannelRoutingStats {
+	/// The channel ID of the outgoing channel.
+	#[prost(string, tag = "1")]
+	pub channel_id: ::prost::alloc::string::String,
+	/// The fees, in milli-satoshis, earned by forwarding payments over this channel.
+	///
+	/// Forwards whose fee is not yet known because it will be claimed on-chain are counted as zero.
+	#[prost(uint64, tag = "2")]
+	pub fees_earned_msat: u64,
+	/// The number of payments forwarded over this channel.
+	#[prost(uint64, tag = "3")]
+	pub forwards_count: u64,
+	/// The total amount, in milli-satoshis, forwarded over this channel after fees were deducted.
+	#[prost(uint64, tag = "4")]
+	pub amount_forwarded_msat: u64,
+}
+/// A BOLT12 offer created by this node via `Bolt12Receive`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OfferRecord {
+	/// The offer, encoded as a string.
+	#[prost(string, tag = "1")]
+	pub offer: ::prost::alloc::string::String,
+	/// The hex-encoded identifier of the offer.
+	#[prost(string, tag = "2")]
+	pub offer_id: ::prost::alloc::string::String,
+	/// The time the offer was created, in seconds since the Unix epoch.
+	#[prost(uint64, tag = "3")]
+	pub created_at: u64,
+	/// The amount requested by the offer, in millisatoshis. Unset for variable-amount offers.
+	#[prost(uint64, optional, tag = "4")]
+	pub amount_msat: ::core::option::Option<u64>,
+	/// Whether the offer is still considered active. Offers are marked inactive once they expired,
+	/// if `payments.deactivate_expired_offers` is set.
+	#[prost(bool, tag = "5")]
+	pub active: bool,
+}
+/// An LNURL-withdraw created by this node via `CreateLnurlWithdraw`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LnurlWithdrawRecord {
+	/// The hex-encoded identifier of the withdraw, which is also the `k1` secret wallets present when
+	/// claiming it.
+	#[prost(string, tag = "1")]
+	pub id: ::prost::alloc::string::String,
+	/// The time the withdraw was created, in seconds since the Unix epoch.
+	#[prost(uint64, tag = "2")]
+	pub created_at: u64,
+	/// The least a withdrawal invoice may request, in millisatoshis.
+	#[prost(uint64, tag = "3")]
+	pub min_amount_msat: u64,
+	/// The most a withdrawal invoice may request, in millisatoshis.
+	#[prost(uint64, tag = "4")]
+	pub max_amount_msat: u64,
+	/// The description wallets suggest for the withdrawal invoice.
+	#[prost(string, tag = "5")]
+	pub description: ::prost::alloc::string::String,
+	/// The time the withdraw was claimed, in seconds since the Unix epoch. Unset while unclaimed.
+	#[prost(uint64, optional, tag = "6")]
+	pub claimed_at: ::core::option::Option<u64>,
+	/// The identifier of the payment of the withdrawal invoice, once claimed.
+	#[prost(string, optional, tag = "7")]
+	pub payment_id: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// An amount converted to larger units, as a decimal string without trailing zeros.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FormattedAmount {
+	/// The amount in satoshis, e.g. "1500.5" for 1,500,500 millisatoshis.
+	#[prost(string, tag = "1")]
+	pub sats: ::prost::alloc::string::String,
+	/// The amount in bitcoin, e.g. "0.000015005" for 1,500,500 millisatoshis.
+	#[prost(string, tag = "2")]
+	pub btc: ::prost::alloc::string::String,
+}
+/// The outcome of a single check run by `RunPreflightChecks`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PreflightCheck {
+	/// The name of the check, e.g. `onchain_wallet_synced`.
+	#[prost(string, tag = "1")]
+	pub name: ::prost::alloc::string::String,
+	/// Whether the check passed.
+	#[prost(enumeration = "PreflightCheckStatus", tag = "2")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_preflight_check_status")
+	)]
+	pub status: i32,
+	/// A human-readable explanation of the outcome.
+	#[prost(string, tag = "3")]
+	pub detail: ::prost::alloc::string::String,
+}
 /// Represents the direction of a payment.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
@@ -1424,3 +1646,93 @@ impl ChannelDirection {
 		}
 	}
 }
+/// The kind of a `ClaimableBalance`, mirroring the variants of `LightningBalance`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ClaimableBalanceKind {
+	/// The channel is not yet closed, and the balance is claimable if it is force-closed now.
+	ClaimableOnChannelClose = 0,
+	/// The channel has been closed, and the balance is ours but awaiting confirmations.
+	ClaimableAwaitingConfirmations = 1,
+	/// The channel has been closed, and the balance is ours but could still be claimed by our
+	/// counterparty if we fail to claim it in time.
+	ContentiousClaimable = 2,
+	/// An outbound HTLC we may claim back once it times out, unless our counterparty claims it
+	/// with the preimage first.
+	MaybeTimeoutClaimableHtlc = 3,
+	/// An inbound HTLC we may claim if we learn the preimage before it times out.
+	MaybePreimageClaimableHtlc = 4,
+	/// An output of a revoked commitment transaction broadcast by our counterparty, claimable by us.
+	CounterpartyRevokedOutputClaimable = 5,
+}
+impl ClaimableBalanceKind {
+	/// String value of the enum field names used in the ProtoBuf definition.
+	///
+	/// The values are not transformed in any way and thus are considered stable
+	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
+	pub fn as_str_name(&self) -> &'static str {
+		match self {
+			ClaimableBalanceKind::ClaimableOnChannelClose => "CLAIMABLE_ON_CHANNEL_CLOSE",
+			ClaimableBalanceKind::ClaimableAwaitingConfirmations => {
+				"CLAIMABLE_AWAITING_CONFIRMATIONS"
+			},
+			ClaimableBalanceKind::ContentiousClaimable => "CONTENTIOUS_CLAIMABLE",
+			ClaimableBalanceKind::MaybeTimeoutClaimableHtlc => "MAYBE_TIMEOUT_CLAIMABLE_HTLC",
+			ClaimableBalanceKind::MaybePreimageClaimableHtlc => "MAYBE_PREIMAGE_CLAIMABLE_HTLC",
+			ClaimableBalanceKind::CounterpartyRevokedOutputClaimable => {
+				"COUNTERPARTY_REVOKED_OUTPUT_CLAIMABLE"
+			},
+		}
+	}
+	/// Creates an enum from field names used in the ProtoBuf definition.
+	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+		match value {
+			"CLAIMABLE_ON_CHANNEL_CLOSE" => Some(Self::ClaimableOnChannelClose),
+			"CLAIMABLE_AWAITING_CONFIRMATIONS" => Some(Self::ClaimableAwaitingConfirmations),
+			"CONTENTIOUS_CLAIMABLE" => Some(Self::ContentiousClaimable),
+			"MAYBE_TIMEOUT_CLAIMABLE_HTLC" => Some(Self::MaybeTimeoutClaimableHtlc),
+			"MAYBE_PREIMAGE_CLAIMABLE_HTLC" => Some(Self::MaybePreimageClaimableHtlc),
+			"COUNTERPARTY_REVOKED_OUTPUT_CLAIMABLE" => {
+				Some(Self::CounterpartyRevokedOutputClaimable)
+			},
+			_ => None,
+		}
+	}
+}
+/// The outcome of a `PreflightCheck`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PreflightCheckStatus {
+	/// The check passed.
+	Pass = 0,
+	/// The check found something worth looking into, which does not stop the node from operating.
+	Warn = 1,
+	/// The check failed, and the node should not be relied on until it is addressed.
+	Fail = 2,
+}
+impl PreflightCheckStatus {
+	/// String value of the enum field names used in the ProtoBuf definition.
+	///
+	/// The values are not transformed in any way and thus are considered stable
+	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
+	pub fn as_str_name(&self) -> &'static str {
+		match self {
+			PreflightCheckStatus::Pass => "PASS",
+			PreflightCheckStatus::Warn => "WARN",
+			PreflightCheckStatus::Fail => "FAIL",
+		}
+	}
+	/// Creates an enum from field names used in the ProtoBuf definition.
+	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+		match value {
+			"PASS" => Some(Self::Pass),
+			"WARN" => Some(Self::Warn),
+			"FAIL" => Some(Self::Fail),
+			_ => None,
+		}
+	}
+}
@@ -15,6 +15,12 @@ pub const PARSE_ERROR: i64 = -32700;
 pub const METHOD_NOT_FOUND: i64 = -32601;
 pub const INVALID_PARAMS: i64 = -32602;
 pub const INTERNAL_ERROR: i64 = -32603;
+/// Server-defined error code for requests the API key's scope does not allow.
+pub const PERMISSION_DENIED: i64 = -32003;
+/// Server-defined error code for requests rejected by the API key's rate limit.
+pub const RATE_LIMITED: i64 = -32004;
+/// Server-defined error code for requests referring to an object that does not exist.
+pub const NOT_FOUND: i64 = -32005;
 
 /// Classified error produced by MCP tool handlers. The `code` is reused for JSON-RPC error
 /// responses at the envelope level, and for categorising the error text that gets surfaced
@@ -38,6 +44,9 @@ impl McpError {
 		match self.code {
 			INVALID_PARAMS => "Invalid params",
 			INTERNAL_ERROR => "Internal error",
+			PERMISSION_DENIED => "Permission denied",
+			RATE_LIMITED => "Rate limited",
+			NOT_FOUND => "Not found",
 			_ => "Error",
 		}
 	}
@@ -47,6 +56,12 @@ impl From<LdkServerError> for McpError {
 	fn from(e: LdkServerError) -> Self {
 		let code = match e.error_code {
 			LdkServerErrorCode::InvalidRequestError => INVALID_PARAMS,
+			LdkServerErrorCode::PermissionDeniedError => PERMISSION_DENIED,
+			LdkServerErrorCode::NotFoundError => NOT_FOUND,
+			LdkServerErrorCode::RateLimited { retry_after_secs } => {
+				let message = format!("{} (retry after {} seconds)", e.message, retry_after_secs);
+				return Self { code: RATE_LIMITED, message };
+			},
 			LdkServerErrorCode::AuthError
 			| LdkServerErrorCode::LightningError
 			| LdkServerErrorCode::InternalServerError
@@ -98,3 +113,19 @@ impl JsonRpcErrorResponse {
 		Self { jsonrpc: "2.0".to_string(), id, error: JsonRpcError { code, message, data: None } }
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rate_limited_errors_carry_the_retry_after() {
+		let error = LdkServerError::new(
+			LdkServerErrorCode::RateLimited { retry_after_secs: 42 },
+			"Rate limit exceeded",
+		);
+		let mcp_error = McpError::from(error);
+		assert_eq!(mcp_error.code, RATE_LIMITED);
+		assert_eq!(mcp_error.message, "Rate limit exceeded (retry after 42 seconds)");
+	}
+}
@@ -13,13 +13,18 @@ use ldk_server_client::ldk_server_grpc::api::{
 	Bolt11ClaimForHashRequest, Bolt11FailForHashRequest, Bolt11ReceiveForHashRequest,
 	Bolt11ReceiveRequest, Bolt11ReceiveVariableAmountViaJitChannelRequest,
 	Bolt11ReceiveViaJitChannelRequest, Bolt11SendRequest, Bolt12ReceiveRequest, Bolt12SendRequest,
-	CloseChannelRequest, ConnectPeerRequest, DecodeInvoiceRequest, DecodeOfferRequest,
-	DisconnectPeerRequest, ExportPathfindingScoresRequest, ForceCloseChannelRequest,
-	GetBalancesRequest, GetNodeInfoRequest, GetPaymentDetailsRequest, GraphGetChannelRequest,
-	GraphGetNodeRequest, GraphListChannelsRequest, GraphListNodesRequest, ListChannelsRequest,
-	ListForwardedPaymentsRequest, ListPaymentsRequest, ListPeersRequest, OnchainReceiveRequest,
-	OnchainSendRequest, OpenChannelRequest, SignMessageRequest, SpliceInRequest, SpliceOutRequest,
-	SpontaneousSendRequest, UnifiedSendRequest, UpdateChannelConfigRequest, VerifySignatureRequest,
+	CloseChannelRequest, ConnectPeerRequest, CreateLnurlWithdrawRequest, DecodeInvoiceRequest,
+	DecodeOfferRequest, DisconnectPeerRequest, ExportPathfindingScoresRequest,
+	ForceCloseChannelRequest, GetBalancesRequest, GetChannelAnnouncementStatusRequest,
+	GetChannelRoutingStatsRequest, GetClaimableBalancesRequest, GetConfigRequest,
+	GetForwardedPaymentRequest, GetNodeInfoRequest, GetPaymentDetailsRequest,
+	GetPeerDetailsRequest, GetSnapshotRequest, GetWalletDescriptorRequest, GraphGetChannelRequest,
+	GraphGetNodeRequest, GraphListChannelsRequest, GraphListNodesRequest, IsOwnInvoiceRequest,
+	ListAuditLogRequest, ListChannelsRequest, ListForwardedPaymentsRequest, ListOffersRequest,
+	ListPaymentsRequest, ListPeersRequest, OnchainReceiveRequest, OnchainSendRequest,
+	OpenChannelRequest, PrunePaymentsRequest, RunPreflightChecksRequest, SignMessageRequest,
+	SpliceInRequest, SpliceOutRequest, SpontaneousSendRequest, UnifiedSendRequest,
+	UpdateChannelConfigRequest, VerifySignatureRequest, WhoAmIRequest,
 };
 use ldk_server_client::ldk_server_grpc::types::RouteParametersConfig;
 use ldk_server_client::{
@@ -105,6 +110,48 @@ pub async fn handle_get_balances(
 	serialize_response(response)
 }
 
+pub async fn handle_get_claimable_balances(
+	client: &LdkServerClient, _args: Value,
+) -> Result<Value, McpError> {
+	let response = client
+		.get_claimable_balances(GetClaimableBalancesRequest {})
+		.await
+		.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_who_am_i(client: &LdkServerClient, _args: Value) -> Result<Value, McpError> {
+	let response = client.who_am_i(WhoAmIRequest {}).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_get_api_version(
+	client: &LdkServerClient, _args: Value,
+) -> Result<Value, McpError> {
+	let response = client.server_version().await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_get_config(client: &LdkServerClient, _args: Value) -> Result<Value, McpError> {
+	let response = client.get_config(GetConfigRequest {}).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_run_preflight_checks(
+	client: &LdkServerClient, _args: Value,
+) -> Result<Value, McpError> {
+	let response =
+		client.run_preflight_checks(RunPreflightChecksRequest {}).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_get_snapshot(
+	client: &LdkServerClient, _args: Value,
+) -> Result<Value, McpError> {
+	let response = client.get_snapshot(GetSnapshotRequest {}).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_onchain_receive(
 	client: &LdkServerClient, _args: Value,
 ) -> Result<Value, McpError> {
@@ -113,6 +160,16 @@ pub async fn handle_onchain_receive(
 	serialize_response(response)
 }
 
+pub async fn handle_get_wallet_descriptor(
+	client: &LdkServerClient, _args: Value,
+) -> Result<Value, McpError> {
+	let response = client
+		.get_wallet_descriptor(GetWalletDescriptorRequest {})
+		.await
+		.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_onchain_send(client: &LdkServerClient, args: Value) -> Result<Value, McpError> {
 	let request: OnchainSendRequest = parse_request(args)?;
 	let response = client.onchain_send(request).await.map_err(McpError::from)?;
@@ -199,6 +256,12 @@ pub async fn handle_bolt12_receive(
 	serialize_response(response)
 }
 
+pub async fn handle_list_offers(client: &LdkServerClient, args: Value) -> Result<Value, McpError> {
+	let request: ListOffersRequest = parse_request(args)?;
+	let response = client.list_offers(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_bolt12_send(client: &LdkServerClient, args: Value) -> Result<Value, McpError> {
 	let request: Bolt12SendRequest =
 		parse_request_with_route_parameters(args, |request: &mut Bolt12SendRequest| {
@@ -228,6 +291,14 @@ pub async fn handle_unified_send(client: &LdkServerClient, args: Value) -> Resul
 	serialize_response(response)
 }
 
+pub async fn handle_create_lnurl_withdraw(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: CreateLnurlWithdrawRequest = parse_request(args)?;
+	let response = client.create_lnurl_withdraw(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_open_channel(client: &LdkServerClient, args: Value) -> Result<Value, McpError> {
 	let request: OpenChannelRequest = parse_request(args)?;
 	let response = client.open_channel(request).await.map_err(McpError::from)?;
@@ -285,6 +356,14 @@ pub async fn handle_list_payments(
 	serialize_response(response)
 }
 
+pub async fn handle_prune_payments(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: PrunePaymentsRequest = parse_request(args)?;
+	let response = client.prune_payments(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_get_payment_details(
 	client: &LdkServerClient, args: Value,
 ) -> Result<Value, McpError> {
@@ -301,6 +380,32 @@ pub async fn handle_list_forwarded_payments(
 	serialize_response(response)
 }
 
+pub async fn handle_get_forwarded_payment(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: GetForwardedPaymentRequest = parse_request(args)?;
+	let response = client.get_forwarded_payment(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_get_channel_routing_stats(
+	client: &LdkServerClient, _args: Value,
+) -> Result<Value, McpError> {
+	let response = client
+		.get_channel_routing_stats(GetChannelRoutingStatsRequest {})
+		.await
+		.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_list_audit_log(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: ListAuditLogRequest = parse_request(args)?;
+	let response = client.list_audit_log(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_connect_peer(client: &LdkServerClient, args: Value) -> Result<Value, McpError> {
 	let request: ConnectPeerRequest = parse_request(args)?;
 	let response = client.connect_peer(request).await.map_err(McpError::from)?;
@@ -320,6 +425,14 @@ pub async fn handle_list_peers(client: &LdkServerClient, _args: Value) -> Result
 	serialize_response(response)
 }
 
+pub async fn handle_get_peer_details(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: GetPeerDetailsRequest = parse_request(args)?;
+	let response = client.get_peer_details(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_decode_invoice(
 	client: &LdkServerClient, args: Value,
 ) -> Result<Value, McpError> {
@@ -328,6 +441,14 @@ pub async fn handle_decode_invoice(
 	serialize_response(response)
 }
 
+pub async fn handle_is_own_invoice(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: IsOwnInvoiceRequest = parse_request(args)?;
+	let response = client.is_own_invoice(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_decode_offer(client: &LdkServerClient, args: Value) -> Result<Value, McpError> {
 	let request: DecodeOfferRequest = parse_request(args)?;
 	let response = client.decode_offer(request).await.map_err(McpError::from)?;
@@ -408,6 +529,14 @@ pub async fn handle_graph_get_node(
 	serialize_response(response)
 }
 
+pub async fn handle_get_channel_announcement_status(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: GetChannelAnnouncementStatusRequest = parse_request(args)?;
+	let response = client.get_channel_announcement_status(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
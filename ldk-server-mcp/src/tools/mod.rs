@@ -81,12 +81,54 @@ pub fn build_tool_registry() -> ToolRegistry {
 			schema::get_balances_schema,
 			|client, args| Box::pin(handlers::handle_get_balances(client, args)),
 		),
+		tool_spec(
+			"get_claimable_balances",
+			"Retrieve the balances claimable from the channel monitors of all channels, including closing ones",
+			schema::get_claimable_balances_schema,
+			|client, args| Box::pin(handlers::handle_get_claimable_balances(client, args)),
+		),
+		tool_spec(
+			"who_am_i",
+			"Retrieve the name and scopes of the API key used to connect to the server",
+			schema::who_am_i_schema,
+			|client, args| Box::pin(handlers::handle_who_am_i(client, args)),
+		),
+		tool_spec(
+			"get_api_version",
+			"Retrieve the API version and enabled optional features of the server",
+			schema::get_api_version_schema,
+			|client, args| Box::pin(handlers::handle_get_api_version(client, args)),
+		),
+		tool_spec(
+			"get_config",
+			"Retrieve the runtime configuration of the server, with secrets redacted",
+			schema::get_config_schema,
+			|client, args| Box::pin(handlers::handle_get_config(client, args)),
+		),
+		tool_spec(
+			"run_preflight_checks",
+			"Run preflight checks on the node, reporting pass, warn or fail for the chain source, wallet sync, usable channels, anchor reserve and persistence",
+			schema::run_preflight_checks_schema,
+			|client, args| Box::pin(handlers::handle_run_preflight_checks(client, args)),
+		),
+		tool_spec(
+			"get_snapshot",
+			"Retrieve a timestamped snapshot of the node's state for monitoring: node info with sync status, balances, channels, peers, pending payments and failing or warning preflight checks as alerts",
+			schema::get_snapshot_schema,
+			|client, args| Box::pin(handlers::handle_get_snapshot(client, args)),
+		),
 		tool_spec(
 			"onchain_receive",
 			"Generate a new on-chain Bitcoin funding address",
 			schema::onchain_receive_schema,
 			|client, args| Box::pin(handlers::handle_onchain_receive(client, args)),
 		),
+		tool_spec(
+			"get_wallet_descriptor",
+			"Retrieve the watch-only output descriptors of the node's on-chain wallet",
+			schema::get_wallet_descriptor_schema,
+			|client, args| Box::pin(handlers::handle_get_wallet_descriptor(client, args)),
+		),
 		tool_spec(
 			"onchain_send",
 			"Send an on-chain Bitcoin payment to an address",
@@ -145,6 +187,12 @@ pub fn build_tool_registry() -> ToolRegistry {
 			schema::bolt12_receive_schema,
 			|client, args| Box::pin(handlers::handle_bolt12_receive(client, args)),
 		),
+		tool_spec(
+			"list_offers",
+			"List the BOLT12 offers created by this node, newest first (supports pagination via page_token)",
+			schema::list_offers_schema,
+			|client, args| Box::pin(handlers::handle_list_offers(client, args)),
+		),
 		tool_spec(
 			"bolt12_send",
 			"Pay a BOLT12 Lightning offer",
@@ -163,6 +211,12 @@ pub fn build_tool_registry() -> ToolRegistry {
 			schema::unified_send_schema,
 			|client, args| Box::pin(handlers::handle_unified_send(client, args)),
 		),
+		tool_spec(
+			"create_lnurl_withdraw",
+			"Create a single-use LNURL-withdraw that a wallet can pull funds from by handing the node an invoice to pay. Requires payments.lnurl_base_url to be configured",
+			schema::create_lnurl_withdraw_schema,
+			|client, args| Box::pin(handlers::handle_create_lnurl_withdraw(client, args)),
+		),
 		tool_spec(
 			"open_channel",
 			"Open a new Lightning channel with a remote node",
@@ -211,6 +265,12 @@ pub fn build_tool_registry() -> ToolRegistry {
 			schema::list_payments_schema,
 			|client, args| Box::pin(handlers::handle_list_payments(client, args)),
 		),
+		tool_spec(
+			"prune_payments",
+			"Remove old succeeded and failed payments from the payment history; pending payments are kept",
+			schema::prune_payments_schema,
+			|client, args| Box::pin(handlers::handle_prune_payments(client, args)),
+		),
 		tool_spec(
 			"get_payment_details",
 			"Get details of a specific payment by its ID",
@@ -223,6 +283,24 @@ pub fn build_tool_registry() -> ToolRegistry {
 			schema::list_forwarded_payments_schema,
 			|client, args| Box::pin(handlers::handle_list_forwarded_payments(client, args)),
 		),
+		tool_spec(
+			"get_forwarded_payment",
+			"Retrieve a single forwarded payment by the ID returned by list_forwarded_payments",
+			schema::get_forwarded_payment_schema,
+			|client, args| Box::pin(handlers::handle_get_forwarded_payment(client, args)),
+		),
+		tool_spec(
+			"get_channel_routing_stats",
+			"Retrieve the routing fees earned, number of forwards and amount forwarded per channel",
+			schema::get_channel_routing_stats_schema,
+			|client, args| Box::pin(handlers::handle_get_channel_routing_stats(client, args)),
+		),
+		tool_spec(
+			"list_audit_log",
+			"List audit log entries for fund-moving operations, newest first (supports pagination via page_token)",
+			schema::list_audit_log_schema,
+			|client, args| Box::pin(handlers::handle_list_audit_log(client, args)),
+		),
 		tool_spec(
 			"connect_peer",
 			"Connect to a Lightning peer without opening a channel",
@@ -241,12 +319,24 @@ pub fn build_tool_registry() -> ToolRegistry {
 			schema::list_peers_schema,
 			|client, args| Box::pin(handlers::handle_list_peers(client, args)),
 		),
+		tool_spec(
+			"get_peer_details",
+			"Get connection details and the network graph announcement of a single peer",
+			schema::get_peer_details_schema,
+			|client, args| Box::pin(handlers::handle_get_peer_details(client, args)),
+		),
 		tool_spec(
 			"decode_invoice",
 			"Decode a BOLT11 invoice and return its parsed fields",
 			schema::decode_invoice_schema,
 			|client, args| Box::pin(handlers::handle_decode_invoice(client, args)),
 		),
+		tool_spec(
+			"is_own_invoice",
+			"Check whether a BOLT11 invoice was issued by this node",
+			schema::is_own_invoice_schema,
+			|client, args| Box::pin(handlers::handle_is_own_invoice(client, args)),
+		),
 		tool_spec(
 			"decode_offer",
 			"Decode a BOLT12 offer and return its parsed fields",
@@ -295,6 +385,12 @@ pub fn build_tool_registry() -> ToolRegistry {
 			schema::graph_get_node_schema,
 			|client, args| Box::pin(handlers::handle_graph_get_node(client, args)),
 		),
+		tool_spec(
+			"get_channel_announcement_status",
+			"Check whether a channel and its channel updates are in the network graph",
+			schema::get_channel_announcement_status_schema,
+			|client, args| Box::pin(handlers::handle_get_channel_announcement_status(client, args)),
+		),
 	];
 
 	let mut definitions = Vec::with_capacity(tools.len());
@@ -150,10 +150,38 @@ pub fn get_balances_schema() -> Value {
 	json!({ "type": "object", "properties": {}, "required": [] })
 }
 
+pub fn get_claimable_balances_schema() -> Value {
+	json!({ "type": "object", "properties": {}, "required": [] })
+}
+
+pub fn who_am_i_schema() -> Value {
+	json!({ "type": "object", "properties": {}, "required": [] })
+}
+
+pub fn get_api_version_schema() -> Value {
+	json!({ "type": "object", "properties": {}, "required": [] })
+}
+
+pub fn get_config_schema() -> Value {
+	json!({ "type": "object", "properties": {}, "required": [] })
+}
+
+pub fn run_preflight_checks_schema() -> Value {
+	json!({ "type": "object", "properties": {}, "required": [] })
+}
+
+pub fn get_snapshot_schema() -> Value {
+	json!({ "type": "object", "properties": {}, "required": [] })
+}
+
 pub fn onchain_receive_schema() -> Value {
 	json!({ "type": "object", "properties": {}, "required": [] })
 }
 
+pub fn get_wallet_descriptor_schema() -> Value {
+	json!({ "type": "object", "properties": {}, "required": [] })
+}
+
 pub fn onchain_send_schema() -> Value {
 	json!({
 		"type": "object",
@@ -305,7 +333,11 @@ pub fn bolt11_send_schema() -> Value {
 				"type": "integer",
 				"description": "Amount in millisatoshis. Required when paying a zero-amount invoice"
 			},
-			"route_parameters": route_parameters_config_schema()
+			"route_parameters": route_parameters_config_schema(),
+			"confirm_large": {
+				"type": "boolean",
+				"description": "Confirm paying a zero-amount invoice an amount above the server's large payment threshold"
+			}
 		},
 		"required": ["invoice"]
 	})
@@ -336,6 +368,16 @@ pub fn bolt12_receive_schema() -> Value {
 	})
 }
 
+pub fn list_offers_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"page_token": page_token_schema()
+		},
+		"required": []
+	})
+}
+
 pub fn bolt12_send_schema() -> Value {
 	json!({
 		"type": "object",
@@ -398,6 +440,27 @@ pub fn unified_send_schema() -> Value {
 	})
 }
 
+pub fn create_lnurl_withdraw_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"max_amount_msat": {
+				"type": "integer",
+				"description": "The most the withdrawal invoice may request, in millisatoshis"
+			},
+			"min_amount_msat": {
+				"type": "integer",
+				"description": "The least the withdrawal invoice may request, in millisatoshis. Defaults to max_amount_msat"
+			},
+			"description": {
+				"type": "string",
+				"description": "The description wallets suggest for the withdrawal invoice"
+			}
+		},
+		"required": ["max_amount_msat"]
+	})
+}
+
 pub fn open_channel_schema() -> Value {
 	json!({
 		"type": "object",
@@ -420,7 +483,7 @@ pub fn open_channel_schema() -> Value {
 			},
 			"announce_channel": {
 				"type": "boolean",
-				"description": "Whether the channel should be public (default: false)"
+				"description": "Whether the channel should be public (default: the server's channels.announce_channels_by_default, false unless configured)"
 			},
 			"disable_counterparty_reserve": {
 				"type": "boolean",
@@ -548,6 +611,23 @@ pub fn list_payments_schema() -> Value {
 	})
 }
 
+pub fn prune_payments_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"max_age_secs": {
+				"type": "integer",
+				"description": "Remove payments last updated longer ago than this many seconds"
+			},
+			"max_records": {
+				"type": "integer",
+				"description": "Remove all but this many of the most recent payments"
+			}
+		},
+		"required": []
+	})
+}
+
 pub fn get_payment_details_schema() -> Value {
 	json!({
 		"type": "object",
@@ -571,6 +651,41 @@ pub fn list_forwarded_payments_schema() -> Value {
 	})
 }
 
+pub fn get_forwarded_payment_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"forward_id": {
+				"type": "string",
+				"description": "The hex-encoded forward ID"
+			}
+		},
+		"required": ["forward_id"]
+	})
+}
+
+pub fn get_channel_routing_stats_schema() -> Value {
+	json!({ "type": "object", "properties": {}, "required": [] })
+}
+
+pub fn list_audit_log_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"page_token": page_token_schema(),
+			"after": {
+				"type": "integer",
+				"description": "Only return entries recorded after this UNIX timestamp (in seconds)"
+			},
+			"before": {
+				"type": "integer",
+				"description": "Only return entries recorded before this UNIX timestamp (in seconds)"
+			}
+		},
+		"required": []
+	})
+}
+
 pub fn connect_peer_schema() -> Value {
 	json!({
 		"type": "object",
@@ -609,6 +724,19 @@ pub fn list_peers_schema() -> Value {
 	json!({ "type": "object", "properties": {}, "required": [] })
 }
 
+pub fn get_peer_details_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"node_id": {
+				"type": "string",
+				"description": "The hex-encoded node ID of the peer"
+			}
+		},
+		"required": ["node_id"]
+	})
+}
+
 pub fn decode_invoice_schema() -> Value {
 	json!({
 		"type": "object",
@@ -622,6 +750,19 @@ pub fn decode_invoice_schema() -> Value {
 	})
 }
 
+pub fn is_own_invoice_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"invoice": {
+				"type": "string",
+				"description": "The BOLT11 invoice string to check"
+			}
+		},
+		"required": ["invoice"]
+	})
+}
+
 pub fn decode_offer_schema() -> Value {
 	json!({
 		"type": "object",
@@ -706,3 +847,16 @@ pub fn graph_get_node_schema() -> Value {
 		"required": ["node_id"]
 	})
 }
+
+pub fn get_channel_announcement_status_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"user_channel_id": {
+				"type": "string",
+				"description": "The local user_channel_id of the channel"
+			}
+		},
+		"required": ["user_channel_id"]
+	})
+}
@@ -11,7 +11,7 @@ use std::io::{BufRead, BufReader, Write};
 
 use serde_json::{json, Value};
 
-const NUM_TOOLS: usize = 37;
+const NUM_TOOLS: usize = 54;
 const EXPECTED_TOOLS: [&str; NUM_TOOLS] = [
 	"bolt11_claim_for_hash",
 	"bolt11_fail_for_hash",
@@ -24,25 +24,40 @@ const EXPECTED_TOOLS: [&str; NUM_TOOLS] = [
 	"bolt12_send",
 	"close_channel",
 	"connect_peer",
+	"create_lnurl_withdraw",
 	"decode_invoice",
 	"decode_offer",
 	"disconnect_peer",
 	"export_pathfinding_scores",
 	"force_close_channel",
+	"get_api_version",
 	"get_balances",
+	"get_channel_announcement_status",
+	"get_channel_routing_stats",
+	"get_claimable_balances",
+	"get_config",
+	"get_forwarded_payment",
 	"get_node_info",
 	"get_payment_details",
+	"get_peer_details",
+	"get_snapshot",
+	"get_wallet_descriptor",
 	"graph_get_channel",
 	"graph_get_node",
 	"graph_list_channels",
 	"graph_list_nodes",
+	"is_own_invoice",
+	"list_audit_log",
 	"list_channels",
 	"list_forwarded_payments",
+	"list_offers",
 	"list_payments",
 	"list_peers",
 	"onchain_receive",
 	"onchain_send",
 	"open_channel",
+	"prune_payments",
+	"run_preflight_checks",
 	"sign_message",
 	"splice_in",
 	"splice_out",
@@ -50,6 +65,7 @@ const EXPECTED_TOOLS: [&str; NUM_TOOLS] = [
 	"unified_send",
 	"update_channel_config",
 	"verify_signature",
+	"who_am_i",
 ];
 
 fn test_cert_path() -> String {
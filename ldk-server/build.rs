@@ -7,9 +7,9 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
-use std::env;
 use std::path::Path;
 use std::process::Command;
+use std::{env, fs};
 
 fn main() {
 	println!("cargo:rerun-if-changed=build.rs");
@@ -36,6 +36,26 @@ fn main() {
 		.or_else(|| env::var("GIT_HASH").ok())
 		.unwrap_or_else(|| "unknown".to_string());
 	println!("cargo:rustc-env=GIT_HASH={git_hash}");
+
+	// Embed the version of LDK Node the server is built with, as recorded in the workspace
+	// lockfile, which is missing when building outside of the workspace.
+	let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+	let lockfile = Path::new(&manifest_dir).join("..").join("Cargo.lock");
+	watch_path(&lockfile);
+	let ldk_node_version =
+		locked_version(&lockfile, "ldk-node").unwrap_or_else(|| "unknown".to_string());
+	println!("cargo:rustc-env=LDK_NODE_VERSION={ldk_node_version}");
+}
+
+/// Returns the version of the given package recorded in the lockfile at `path`, or `None` if the
+/// lockfile cannot be read or does not list the package.
+fn locked_version(path: &Path, package: &str) -> Option<String> {
+	let lockfile = fs::read_to_string(path).ok()?;
+	let name_line = format!("name = \"{package}\"");
+	let mut lines = lockfile.lines();
+	lines.find(|line| *line == name_line)?;
+	let version = lines.next()?.strip_prefix("version = \"")?.strip_suffix('"')?;
+	Some(version.to_string())
 }
 
 /// Runs `git` with the given args, returning the trimmed stdout on success or
@@ -16,12 +16,14 @@ use ldk_server_grpc::api::{Bolt11ReceiveForHashRequest, Bolt11ReceiveForHashResp
 use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::InvalidRequestError;
 use crate::service::Context;
+use crate::util::invoices::track_invoice_expiry;
 use crate::util::proto_adapter::proto_to_bolt11_description;
 
 pub(crate) async fn handle_bolt11_receive_for_hash_request(
 	context: Arc<Context>, request: Bolt11ReceiveForHashRequest,
 ) -> Result<Bolt11ReceiveForHashResponse, LdkServerError> {
-	let description = proto_to_bolt11_description(request.description)?;
+	let description =
+		proto_to_bolt11_description(request.description, context.config.max_description_length)?;
 	let hash_bytes = <[u8; 32]>::from_hex(&request.payment_hash).map_err(|_| {
 		LdkServerError::new(
 			InvalidRequestError,
@@ -43,6 +45,9 @@ pub(crate) async fn handle_bolt11_receive_for_hash_request(
 			payment_hash,
 		)?,
 	};
+	if context.config.notify_expired_invoices {
+		track_invoice_expiry(context.paginated_kv_store.as_ref(), &invoice);
+	}
 
 	Ok(Bolt11ReceiveForHashResponse { invoice: invoice.to_string() })
 }
@@ -16,19 +16,29 @@ use ldk_server_grpc::api::{
 };
 
 use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InvalidRequestError;
 use crate::service::Context;
+use crate::util::invoices::track_invoice_expiry;
 use crate::util::proto_adapter::proto_to_bolt11_description;
 
 pub(crate) async fn handle_bolt11_receive_via_jit_channel_request(
 	context: Arc<Context>, request: Bolt11ReceiveViaJitChannelRequest,
 ) -> Result<Bolt11ReceiveViaJitChannelResponse, LdkServerError> {
-	let description = proto_to_bolt11_description(request.description)?;
+	let description =
+		proto_to_bolt11_description(request.description, context.config.max_description_length)?;
+	let max_total_lsp_fee_limit_msat = lsp_fee_limit_msat(
+		context.config.max_skimmed_fee_msat,
+		request.max_total_lsp_fee_limit_msat,
+	)?;
 	let invoice = context.node.bolt11_payment().receive_via_jit_channel(
 		request.amount_msat,
 		&description,
 		request.expiry_secs,
-		request.max_total_lsp_fee_limit_msat,
+		max_total_lsp_fee_limit_msat,
 	)?;
+	if context.config.notify_expired_invoices {
+		track_invoice_expiry(context.paginated_kv_store.as_ref(), &invoice);
+	}
 
 	Ok(Bolt11ReceiveViaJitChannelResponse { invoice: invoice.to_string() })
 }
@@ -36,12 +46,84 @@ pub(crate) async fn handle_bolt11_receive_via_jit_channel_request(
 pub(crate) async fn handle_bolt11_receive_variable_amount_via_jit_channel_request(
 	context: Arc<Context>, request: Bolt11ReceiveVariableAmountViaJitChannelRequest,
 ) -> Result<Bolt11ReceiveVariableAmountViaJitChannelResponse, LdkServerError> {
-	let description = proto_to_bolt11_description(request.description)?;
+	check_variable_amount_jit_allowed(context.config.max_skimmed_fee_msat)?;
+	let description =
+		proto_to_bolt11_description(request.description, context.config.max_description_length)?;
 	let invoice = context.node.bolt11_payment().receive_variable_amount_via_jit_channel(
 		&description,
 		request.expiry_secs,
 		request.max_proportional_lsp_fee_limit_ppm_msat,
 	)?;
+	if context.config.notify_expired_invoices {
+		track_invoice_expiry(context.paginated_kv_store.as_ref(), &invoice);
+	}
 
 	Ok(Bolt11ReceiveVariableAmountViaJitChannelResponse { invoice: invoice.to_string() })
 }
+
+/// Returns the limit on the fee the LSP may skim off the payment, which ldk-node enforces by
+/// failing back payments that arrive with a larger skimmed fee.
+///
+/// The configured `max_skimmed_fee_msat` applies if the request sets no limit, and requests may
+/// only lower it.
+fn lsp_fee_limit_msat(
+	max_skimmed_fee_msat: Option<u64>, requested_limit_msat: Option<u64>,
+) -> Result<Option<u64>, LdkServerError> {
+	match (max_skimmed_fee_msat, requested_limit_msat) {
+		(Some(max_msat), Some(requested_msat)) if requested_msat > max_msat => {
+			Err(LdkServerError::new(
+				InvalidRequestError,
+				format!(
+					"LSP fee limit of {} msat exceeds configured maximum skimmed fee of {} msat",
+					requested_msat, max_msat
+				),
+			))
+		},
+		(max_msat, None) => Ok(max_msat),
+		(_, requested_msat) => Ok(requested_msat),
+	}
+}
+
+/// Rejects creating a variable-amount JIT invoice while `max_skimmed_fee_msat` is configured.
+///
+/// The LSP fee of such invoices can only be limited in proportion to the amount, which is not
+/// known up front, so the configured cap could not be enforced.
+fn check_variable_amount_jit_allowed(
+	max_skimmed_fee_msat: Option<u64>,
+) -> Result<(), LdkServerError> {
+	match max_skimmed_fee_msat {
+		Some(max_msat) => Err(LdkServerError::new(
+			InvalidRequestError,
+			format!(
+				"Variable-amount JIT invoices cannot honor the configured maximum skimmed fee of {} msat",
+				max_msat
+			),
+		)),
+		None => Ok(()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn configured_skimmed_fee_limit_is_default_and_upper_bound() {
+		assert_eq!(lsp_fee_limit_msat(None, None).unwrap(), None);
+		assert_eq!(lsp_fee_limit_msat(None, Some(9_000)).unwrap(), Some(9_000));
+		assert_eq!(lsp_fee_limit_msat(Some(5_000), None).unwrap(), Some(5_000));
+		assert_eq!(lsp_fee_limit_msat(Some(5_000), Some(4_000)).unwrap(), Some(4_000));
+
+		let err = lsp_fee_limit_msat(Some(5_000), Some(5_001)).unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+		assert!(err.message.contains("exceeds configured maximum skimmed fee of 5000 msat"));
+	}
+
+	#[test]
+	fn variable_amount_jit_invoices_are_rejected_with_a_skimmed_fee_limit() {
+		assert!(check_variable_amount_jit_allowed(None).is_ok());
+		let err = check_variable_amount_jit_allowed(Some(5_000)).unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+		assert!(err.message.contains("maximum skimmed fee of 5000 msat"));
+	}
+}
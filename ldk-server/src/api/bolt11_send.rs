@@ -13,15 +13,31 @@ use std::sync::Arc;
 use ldk_node::lightning_invoice::Bolt11Invoice;
 use ldk_server_grpc::api::{Bolt11SendRequest, Bolt11SendResponse};
 
-use crate::api::build_route_parameters_config_from_proto;
 use crate::api::error::LdkServerError;
+use crate::api::{build_route_parameters_config_from_proto, check_bolt11_payment_metadata};
 use crate::service::Context;
+use crate::util::payment_limit::{
+	check_large_payment_amount, check_optional_outbound_payment_amount,
+};
 
 pub(crate) async fn handle_bolt11_send_request(
 	context: Arc<Context>, request: Bolt11SendRequest,
 ) -> Result<Bolt11SendResponse, LdkServerError> {
 	let invoice = Bolt11Invoice::from_str(request.invoice.as_str())
 		.map_err(|_| ldk_node::NodeError::InvalidInvoice)?;
+	check_bolt11_payment_metadata(&invoice)?;
+
+	let amount_msat = request.amount_msat.or(invoice.amount_milli_satoshis());
+	check_optional_outbound_payment_amount(context.max_outbound_payment_msat, amount_msat)?;
+	// Fixed-amount invoices already state what is paid, so only amounts chosen by the caller are
+	// checked for typos.
+	if let (None, Some(amount_msat)) = (invoice.amount_milli_satoshis(), request.amount_msat) {
+		check_large_payment_amount(
+			context.config.large_payment_warning_msat,
+			amount_msat,
+			request.confirm_large,
+		)?;
+	}
 
 	let route_parameters = build_route_parameters_config_from_proto(request.route_parameters)?;
 
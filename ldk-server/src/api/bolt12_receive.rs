@@ -11,13 +11,23 @@ use std::sync::Arc;
 
 use hex::DisplayHex;
 use ldk_server_grpc::api::{Bolt12ReceiveRequest, Bolt12ReceiveResponse};
+use ldk_server_grpc::types::OfferRecord;
+use log::error;
 
 use crate::api::error::LdkServerError;
 use crate::service::Context;
+use crate::util::audit::current_timestamp;
+use crate::util::offers::{check_offer_quantity_limit, write_offer_record};
+use crate::util::proto_adapter::check_description_length;
 
 pub(crate) async fn handle_bolt12_receive_request(
 	context: Arc<Context>, request: Bolt12ReceiveRequest,
 ) -> Result<Bolt12ReceiveResponse, LdkServerError> {
+	check_offer_quantity_limit(context.config.max_offer_quantity, request.quantity)?;
+	if let Some(max_description_length) = context.config.max_description_length {
+		check_description_length(&request.description, max_description_length)?;
+	}
+
 	let offer = match request.amount_msat {
 		Some(amount_msat) => context.node.bolt12_payment().receive(
 			amount_msat,
@@ -32,6 +42,19 @@ pub(crate) async fn handle_bolt12_receive_request(
 	};
 
 	let offer_id = offer.id().0.to_lower_hex_string();
+
+	// The offer is usable regardless, so failing to record it only affects `ListOffers`.
+	let record = OfferRecord {
+		offer: offer.to_string(),
+		offer_id: offer_id.clone(),
+		created_at: current_timestamp(),
+		amount_msat: request.amount_msat,
+		active: true,
+	};
+	if let Err(e) = write_offer_record(context.paginated_kv_store.as_ref(), &record) {
+		error!("Failed to persist offer {}: {}", offer_id, e);
+	}
+
 	let response = Bolt12ReceiveResponse { offer: offer.to_string(), offer_id };
 	Ok(response)
 }
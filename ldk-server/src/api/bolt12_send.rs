@@ -16,12 +16,20 @@ use ldk_server_grpc::api::{Bolt12SendRequest, Bolt12SendResponse};
 use crate::api::build_route_parameters_config_from_proto;
 use crate::api::error::LdkServerError;
 use crate::service::Context;
+use crate::util::audit::current_timestamp;
+use crate::util::offers::{check_offer_not_expired, check_offer_quantity};
+use crate::util::payment_limit::{check_optional_outbound_payment_amount, offer_amount_msat};
 
 pub(crate) async fn handle_bolt12_send_request(
 	context: Arc<Context>, request: Bolt12SendRequest,
 ) -> Result<Bolt12SendResponse, LdkServerError> {
 	let offer =
 		Offer::from_str(request.offer.as_str()).map_err(|_| ldk_node::NodeError::InvalidOffer)?;
+	check_offer_not_expired(&offer, current_timestamp())?;
+	check_offer_quantity(&offer, request.quantity)?;
+
+	let amount_msat = request.amount_msat.or_else(|| offer_amount_msat(&offer, request.quantity));
+	check_optional_outbound_payment_amount(context.max_outbound_payment_msat, amount_msat)?;
 
 	let route_parameters = build_route_parameters_config_from_proto(request.route_parameters)?;
 
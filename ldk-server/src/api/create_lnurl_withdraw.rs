@@ -0,0 +1,72 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use hex::DisplayHex;
+use ldk_server_grpc::api::{CreateLnurlWithdrawRequest, CreateLnurlWithdrawResponse};
+use ldk_server_grpc::types::LnurlWithdrawRecord;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{InternalServerError, InvalidRequestError};
+use crate::service::Context;
+use crate::util::audit::current_timestamp;
+use crate::util::lnurl::{encode_lnurl, withdraw_url, write_withdraw_record};
+use crate::util::payment_limit::check_outbound_payment_amount;
+use crate::util::proto_adapter::check_description_length;
+
+pub(crate) async fn handle_create_lnurl_withdraw_request(
+	context: Arc<Context>, request: CreateLnurlWithdrawRequest,
+) -> Result<CreateLnurlWithdrawResponse, LdkServerError> {
+	let base_url = context.config.lnurl_base_url.as_deref().ok_or_else(|| {
+		LdkServerError::new(
+			InvalidRequestError,
+			"LNURL-withdraws require `payments.lnurl_base_url` to be configured",
+		)
+	})?;
+
+	let max_amount_msat = request.max_amount_msat;
+	let min_amount_msat = request.min_amount_msat.unwrap_or(max_amount_msat);
+	if max_amount_msat == 0 || min_amount_msat == 0 || min_amount_msat > max_amount_msat {
+		return Err(LdkServerError::new(
+			InvalidRequestError,
+			"`max_amount_msat` must be positive and at least `min_amount_msat`, which must be positive",
+		));
+	}
+	// Claims are checked against the limit as well, rejecting them here saves the wallet a trip.
+	check_outbound_payment_amount(context.max_outbound_payment_msat, min_amount_msat)?;
+	let description = request.description.unwrap_or_default();
+	if let Some(max_length) = context.config.max_description_length {
+		check_description_length(&description, max_length)?;
+	}
+
+	let mut id_bytes = [0u8; 32];
+	getrandom::getrandom(&mut id_bytes).map_err(|e| {
+		LdkServerError::new(InternalServerError, format!("Failed to generate withdraw id: {}", e))
+	})?;
+	let record = LnurlWithdrawRecord {
+		id: id_bytes.to_lower_hex_string(),
+		created_at: current_timestamp(),
+		min_amount_msat,
+		max_amount_msat,
+		description,
+		claimed_at: None,
+		payment_id: None,
+	};
+
+	let url = withdraw_url(base_url, &record.id);
+	let lnurl = encode_lnurl(&url).map_err(|e| {
+		LdkServerError::new(InternalServerError, format!("Failed to encode LNURL: {}", e))
+	})?;
+	write_withdraw_record(context.paginated_kv_store.as_ref(), &record).map_err(|e| {
+		LdkServerError::new(InternalServerError, format!("Failed to persist withdraw: {}", e))
+	})?;
+
+	Ok(CreateLnurlWithdrawResponse { lnurl, url, withdraw_id: record.id })
+}
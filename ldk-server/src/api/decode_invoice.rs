@@ -18,17 +18,23 @@ use ldk_server_grpc::types::{Bolt11HopHint, Bolt11RouteHint};
 
 use crate::api::error::LdkServerError;
 use crate::service::Context;
-use crate::util::proto_adapter::features_to_proto;
+use crate::util::proto_adapter::{features_to_proto, formatted_amount_to_proto};
 
 pub(crate) async fn handle_decode_invoice_request(
 	_context: Arc<Context>, request: DecodeInvoiceRequest,
 ) -> Result<DecodeInvoiceResponse, LdkServerError> {
-	let invoice = Bolt11Invoice::from_str(request.invoice.as_str())
-		.map_err(|_| ldk_node::NodeError::InvalidInvoice)?;
+	decode_invoice(&request.invoice)
+}
+
+fn decode_invoice(invoice: &str) -> Result<DecodeInvoiceResponse, LdkServerError> {
+	let invoice =
+		Bolt11Invoice::from_str(invoice).map_err(|_| ldk_node::NodeError::InvalidInvoice)?;
 
 	let destination = invoice.get_payee_pub_key().to_string();
 	let payment_hash = invoice.payment_hash().0.to_lower_hex_string();
 	let amount_msat = invoice.amount_milli_satoshis();
+	let formatted_amount = amount_msat.map(formatted_amount_to_proto);
+	let is_zero_amount = amount_msat.is_none();
 	let timestamp = invoice.duration_since_epoch().as_secs();
 	let expiry = invoice.expiry_time().as_secs();
 	let min_final_cltv_expiry_delta = invoice.min_final_cltv_expiry_delta();
@@ -101,5 +107,53 @@ pub(crate) async fn handle_decode_invoice_request(
 		currency,
 		payment_metadata,
 		is_expired,
+		formatted_amount,
+		is_zero_amount,
 	})
 }
+
+#[cfg(test)]
+mod tests {
+	use ldk_node::bitcoin::hashes::{sha256, Hash};
+	use ldk_node::bitcoin::secp256k1::{Secp256k1, SecretKey};
+	use ldk_node::lightning_invoice::{Currency, InvoiceBuilder};
+	use ldk_node::lightning_types::payment::PaymentSecret;
+
+	use super::*;
+
+	fn invoice(amount_msat: Option<u64>) -> String {
+		let secret_key = SecretKey::from_slice(&[42; 32]).unwrap();
+		let builder = InvoiceBuilder::new(Currency::Regtest)
+			.description("coffee".to_string())
+			.payment_hash(sha256::Hash::hash(b"preimage"))
+			.payment_secret(PaymentSecret([7; 32]))
+			.current_timestamp()
+			.min_final_cltv_expiry_delta(144);
+		let builder = match amount_msat {
+			Some(amount_msat) => builder.amount_milli_satoshis(amount_msat),
+			None => builder,
+		};
+		builder
+			.build_signed(|hash| Secp256k1::new().sign_ecdsa_recoverable(hash, &secret_key))
+			.unwrap()
+			.to_string()
+	}
+
+	#[test]
+	fn fixed_amount_invoice_is_formatted_in_sats_and_btc() {
+		let response = decode_invoice(&invoice(Some(1_500_500))).unwrap();
+		assert_eq!(response.amount_msat, Some(1_500_500));
+		assert!(!response.is_zero_amount);
+		let formatted_amount = response.formatted_amount.unwrap();
+		assert_eq!(formatted_amount.sats, "1500.5");
+		assert_eq!(formatted_amount.btc, "0.000015005");
+	}
+
+	#[test]
+	fn zero_amount_invoice_is_flagged() {
+		let response = decode_invoice(&invoice(None)).unwrap();
+		assert_eq!(response.amount_msat, None);
+		assert!(response.is_zero_amount);
+		assert_eq!(response.formatted_amount, None);
+	}
+}
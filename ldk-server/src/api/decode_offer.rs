@@ -26,13 +26,16 @@ use ldk_server_grpc::types::{
 
 use crate::api::error::LdkServerError;
 use crate::service::Context;
-use crate::util::proto_adapter::features_to_proto;
+use crate::util::proto_adapter::{features_to_proto, formatted_amount_to_proto};
 
 pub(crate) async fn handle_decode_offer_request(
 	_context: Arc<Context>, request: DecodeOfferRequest,
 ) -> Result<DecodeOfferResponse, LdkServerError> {
-	let offer =
-		Offer::from_str(request.offer.as_str()).map_err(|_| ldk_node::NodeError::InvalidOffer)?;
+	decode_offer(&request.offer)
+}
+
+fn decode_offer(offer: &str) -> Result<DecodeOfferResponse, LdkServerError> {
+	let offer = Offer::from_str(offer).map_err(|_| ldk_node::NodeError::InvalidOffer)?;
 
 	let offer_id = offer.id().0.to_lower_hex_string();
 
@@ -54,6 +57,14 @@ pub(crate) async fn handle_decode_offer_request(
 		},
 	});
 
+	let (formatted_amount, is_zero_amount) = match offer.amount() {
+		Some(ldk_node::lightning::offers::offer::Amount::Bitcoin { amount_msats }) => {
+			(Some(formatted_amount_to_proto(amount_msats)), false)
+		},
+		Some(ldk_node::lightning::offers::offer::Amount::Currency { .. }) => (None, false),
+		None => (None, true),
+	};
+
 	let issuer_signing_pubkey = offer.issuer_signing_pubkey().map(|pk| pk.to_string());
 
 	let absolute_expiry = offer.absolute_expiry().map(|d| d.as_secs());
@@ -127,6 +138,8 @@ pub(crate) async fn handle_decode_offer_request(
 		chains,
 		metadata,
 		is_expired,
+		formatted_amount,
+		is_zero_amount,
 	})
 }
 
@@ -143,3 +156,35 @@ fn chain_hash_to_name(chain: ChainHash) -> String {
 		chain.to_string()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use ldk_node::bitcoin::secp256k1::{PublicKey, Secp256k1, SecretKey};
+	use ldk_node::lightning::offers::offer::OfferBuilder;
+
+	use super::*;
+
+	fn signing_pubkey() -> PublicKey {
+		let secret_key = SecretKey::from_slice(&[42; 32]).unwrap();
+		PublicKey::from_secret_key(&Secp256k1::new(), &secret_key)
+	}
+
+	#[test]
+	fn any_amount_offer_is_flagged() {
+		let offer = OfferBuilder::new(signing_pubkey()).build().unwrap();
+		let response = decode_offer(&offer.to_string()).unwrap();
+		assert_eq!(response.amount, None);
+		assert!(response.is_zero_amount);
+		assert_eq!(response.formatted_amount, None);
+	}
+
+	#[test]
+	fn fixed_amount_offer_is_formatted_in_sats_and_btc() {
+		let offer = OfferBuilder::new(signing_pubkey()).amount_msats(2_000).build().unwrap();
+		let response = decode_offer(&offer.to_string()).unwrap();
+		assert!(!response.is_zero_amount);
+		let formatted_amount = response.formatted_amount.unwrap();
+		assert_eq!(formatted_amount.sats, "2");
+		assert_eq!(formatted_amount.btc, "0.00000002");
+	}
+}
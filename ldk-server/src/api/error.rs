@@ -52,6 +52,12 @@ pub(crate) enum LdkServerErrorCode {
 
 	/// Please refer to [`protos::error::ErrorCode::InternalServerError`].
 	InternalServerError,
+
+	/// Please refer to [`protos::error::ErrorCode::PermissionDeniedError`].
+	PermissionDeniedError,
+
+	/// Please refer to [`protos::error::ErrorCode::NotFoundError`].
+	NotFoundError,
 }
 
 impl fmt::Display for LdkServerErrorCode {
@@ -61,6 +67,8 @@ impl fmt::Display for LdkServerErrorCode {
 			LdkServerErrorCode::AuthError => write!(f, "AuthError"),
 			LdkServerErrorCode::LightningError => write!(f, "LightningError"),
 			LdkServerErrorCode::InternalServerError => write!(f, "InternalServerError"),
+			LdkServerErrorCode::PermissionDeniedError => write!(f, "PermissionDeniedError"),
+			LdkServerErrorCode::NotFoundError => write!(f, "NotFoundError"),
 		}
 	}
 }
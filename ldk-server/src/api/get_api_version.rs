@@ -0,0 +1,54 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_server_grpc::api::{GetApiVersionRequest, GetApiVersionResponse};
+use ldk_server_grpc::endpoints::API_VERSION;
+
+use crate::api::error::LdkServerError;
+use crate::service::Context;
+use crate::util::config::Config;
+
+/// Returns the names of the optional features enabled by the given configuration.
+pub(crate) fn enabled_features(config: &Config) -> Vec<String> {
+	let features = [
+		("metrics", config.metrics_enabled),
+		("lsps2_client", config.lsps2_client_config.is_some()),
+		("lsps2_service", cfg!(feature = "experimental-lsps2-support")),
+		("tor", config.tor_config.is_some()),
+		("probing", config.probing_config.is_some()),
+		("outbound_payment_limit", config.max_outbound_payment_msat.is_some()),
+		("payment_history_retention", config.payment_history_retention.is_some()),
+		("offer_expiry", config.deactivate_expired_offers),
+		(
+			"rate_limiting",
+			config.read_requests_per_minute.is_some() || config.send_requests_per_minute.is_some(),
+		),
+		("invoice_expiry_notifications", config.notify_expired_invoices),
+		("lnurl_withdraw", config.lnurl_base_url.is_some()),
+	];
+	features.into_iter().filter(|(_, enabled)| *enabled).map(|(name, _)| name.to_string()).collect()
+}
+
+/// Describes this server, with the given optional features enabled.
+pub(crate) fn api_version_response(features: Vec<String>) -> GetApiVersionResponse {
+	GetApiVersionResponse {
+		api_version: API_VERSION,
+		server_version: env!("CARGO_PKG_VERSION").to_string(),
+		ldk_version: env!("LDK_NODE_VERSION").to_string(),
+		features,
+	}
+}
+
+pub(crate) async fn handle_get_api_version_request(
+	context: Arc<Context>, _request: GetApiVersionRequest,
+) -> Result<GetApiVersionResponse, LdkServerError> {
+	Ok(api_version_response(context.config.server_features.clone()))
+}
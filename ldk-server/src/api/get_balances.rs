@@ -9,6 +9,7 @@
 
 use std::sync::Arc;
 
+use ldk_node::BalanceDetails;
 use ldk_server_grpc::api::{GetBalancesRequest, GetBalancesResponse};
 
 use crate::api::error::LdkServerError;
@@ -18,9 +19,11 @@ use crate::util::proto_adapter::{lightning_balance_to_proto, pending_sweep_balan
 pub(crate) async fn handle_get_balances_request(
 	context: Arc<Context>, _request: GetBalancesRequest,
 ) -> Result<GetBalancesResponse, LdkServerError> {
-	let balance_details = context.node.list_balances();
+	Ok(balances_to_proto(context.node.list_balances()))
+}
 
-	let response = GetBalancesResponse {
+pub(crate) fn balances_to_proto(balance_details: BalanceDetails) -> GetBalancesResponse {
+	GetBalancesResponse {
 		total_onchain_balance_sats: balance_details.total_onchain_balance_sats,
 		spendable_onchain_balance_sats: balance_details.spendable_onchain_balance_sats,
 		total_anchor_channels_reserve_sats: balance_details.total_anchor_channels_reserve_sats,
@@ -35,6 +38,5 @@ pub(crate) async fn handle_get_balances_request(
 			.into_iter()
 			.map(pending_sweep_balance_to_proto)
 			.collect(),
-	};
-	Ok(response)
+	}
 }
@@ -0,0 +1,62 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_node::lightning::routing::gossip::NodeId;
+use ldk_server_grpc::api::{
+	GetChannelAnnouncementStatusRequest, GetChannelAnnouncementStatusResponse,
+};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InvalidRequestError;
+use crate::service::Context;
+
+pub(crate) async fn handle_get_channel_announcement_status_request(
+	context: Arc<Context>, request: GetChannelAnnouncementStatusRequest,
+) -> Result<GetChannelAnnouncementStatusResponse, LdkServerError> {
+	let user_channel_id: u128 = request
+		.user_channel_id
+		.parse::<u128>()
+		.map_err(|_| LdkServerError::new(InvalidRequestError, "Invalid UserChannelId."))?;
+
+	let channel = context
+		.node
+		.list_channels()
+		.into_iter()
+		.find(|c| c.user_channel_id.0 == user_channel_id)
+		.ok_or_else(|| {
+			LdkServerError::new(InvalidRequestError, "Channel not found for given user_channel_id.")
+		})?;
+
+	let mut response = GetChannelAnnouncementStatusResponse {
+		short_channel_id: channel.short_channel_id,
+		is_announced: channel.is_announced,
+		..Default::default()
+	};
+
+	let channel_info = channel
+		.short_channel_id
+		.and_then(|short_channel_id| context.node.network_graph().channel(short_channel_id));
+	if let Some(channel_info) = channel_info {
+		// Each direction's `channel_update` is issued by the node the direction starts at.
+		let (outbound_update, inbound_update) =
+			if channel_info.node_one == NodeId::from_pubkey(&context.node.node_id()) {
+				(channel_info.one_to_two, channel_info.two_to_one)
+			} else {
+				(channel_info.two_to_one, channel_info.one_to_two)
+			};
+
+		response.announcement_in_graph = true;
+		response.outbound_update_timestamp = outbound_update.map(|update| update.last_update);
+		response.inbound_update_timestamp = inbound_update.map(|update| update.last_update);
+	}
+
+	Ok(response)
+}
@@ -0,0 +1,79 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use ldk_server_grpc::api::{GetChannelRoutingStatsRequest, GetChannelRoutingStatsResponse};
+use ldk_server_grpc::types::ForwardedPayment;
+use prost::Message;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::io::persist::{
+	FORWARDED_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+	FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::service::Context;
+use crate::util::routing_stats::RoutingStatsAggregator;
+
+// Forwarded payments are not indexed by channel, so this reads every one of them. That is fine for
+// an occasional analytics query, but makes each request O(n) in the number of recorded forwards.
+pub(crate) async fn handle_get_channel_routing_stats_request(
+	context: Arc<Context>, _request: GetChannelRoutingStatsRequest,
+) -> Result<GetChannelRoutingStatsResponse, LdkServerError> {
+	let mut aggregator = RoutingStatsAggregator::default();
+	let mut page_token = None;
+	loop {
+		let list_response = context
+			.paginated_kv_store
+			.list(
+				FORWARDED_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+				FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+				page_token,
+			)
+			.map_err(|e| {
+				LdkServerError::new(
+					InternalServerError,
+					format!("Failed to list forwarded payments: {}", e),
+				)
+			})?;
+
+		for key in list_response.keys {
+			let forwarded_payment_bytes = context
+				.paginated_kv_store
+				.read(
+					FORWARDED_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+					FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+					&key,
+				)
+				.map_err(|e| {
+					LdkServerError::new(
+						InternalServerError,
+						format!("Failed to read forwarded payment data: {}", e),
+					)
+				})?;
+			let forwarded_payment = ForwardedPayment::decode(Bytes::from(forwarded_payment_bytes))
+				.map_err(|e| {
+					LdkServerError::new(
+						InternalServerError,
+						format!("Failed to decode forwarded payment: {}", e),
+					)
+				})?;
+			aggregator.record(&forwarded_payment);
+		}
+
+		page_token = list_response.next_page_token;
+		if page_token.is_none() {
+			break;
+		}
+	}
+
+	Ok(aggregator.into_response())
+}
@@ -0,0 +1,33 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_server_grpc::api::{GetClaimableBalancesRequest, GetClaimableBalancesResponse};
+
+use crate::api::error::LdkServerError;
+use crate::service::Context;
+use crate::util::proto_adapter::{
+	lightning_balance_to_claimable_balance, lightning_balance_to_proto,
+};
+
+pub(crate) async fn handle_get_claimable_balances_request(
+	context: Arc<Context>, _request: GetClaimableBalancesRequest,
+) -> Result<GetClaimableBalancesResponse, LdkServerError> {
+	let balances = context
+		.node
+		.list_balances()
+		.lightning_balances
+		.into_iter()
+		.map(lightning_balance_to_proto)
+		.filter_map(lightning_balance_to_claimable_balance)
+		.collect();
+
+	Ok(GetClaimableBalancesResponse { balances })
+}
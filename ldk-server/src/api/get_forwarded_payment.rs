@@ -0,0 +1,68 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::io;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use hex::FromHex;
+use ldk_server_grpc::api::{GetForwardedPaymentRequest, GetForwardedPaymentResponse};
+use ldk_server_grpc::types::ForwardedPayment;
+use prost::Message;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{InternalServerError, InvalidRequestError};
+use crate::io::persist::{
+	FORWARDED_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+	FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::service::Context;
+
+/// The length of the random identifier a forward is stored under.
+const FORWARD_ID_LENGTH: usize = 32;
+
+pub(crate) async fn handle_get_forwarded_payment_request(
+	context: Arc<Context>, request: GetForwardedPaymentRequest,
+) -> Result<GetForwardedPaymentResponse, LdkServerError> {
+	// Checked up front, as the store rejects keys it could not have written with an I/O error.
+	<[u8; FORWARD_ID_LENGTH]>::from_hex(&request.forward_id).map_err(|_| {
+		LdkServerError::new(
+			InvalidRequestError,
+			format!("Invalid forward_id, must be a {}-byte hex-string.", FORWARD_ID_LENGTH),
+		)
+	})?;
+	let forward_id = request.forward_id.to_ascii_lowercase();
+
+	let forwarded_payment_bytes = match context.paginated_kv_store.read(
+		FORWARDED_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+		FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+		&forward_id,
+	) {
+		Ok(bytes) => bytes,
+		Err(e) if e.kind() == io::ErrorKind::NotFound => {
+			return Ok(GetForwardedPaymentResponse { forwarded_payment: None });
+		},
+		Err(e) => {
+			return Err(LdkServerError::new(
+				InternalServerError,
+				format!("Failed to read forwarded payment data: {}", e),
+			));
+		},
+	};
+	let mut forwarded_payment = ForwardedPayment::decode(Bytes::from(forwarded_payment_bytes))
+		.map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to decode forwarded payment: {}", e),
+			)
+		})?;
+	forwarded_payment.id = forward_id;
+
+	Ok(GetForwardedPaymentResponse { forwarded_payment: Some(forwarded_payment) })
+}
@@ -10,6 +10,7 @@
 use std::sync::Arc;
 
 use ldk_node::lightning_types::features::NodeFeatures;
+use ldk_node::NodeStatus;
 use ldk_server_grpc::api::{GetNodeInfoRequest, GetNodeInfoResponse};
 use ldk_server_grpc::types::BestBlock;
 
@@ -20,8 +21,12 @@ use crate::util::proto_adapter::{features_to_proto, network_to_proto};
 pub(crate) async fn handle_get_node_info_request(
 	context: Arc<Context>, _request: GetNodeInfoRequest,
 ) -> Result<GetNodeInfoResponse, LdkServerError> {
-	let node_status = context.node.status();
+	Ok(node_info_to_proto(&context, context.node.status()))
+}
 
+pub(crate) fn node_info_to_proto(
+	context: &Context, node_status: NodeStatus,
+) -> GetNodeInfoResponse {
 	let best_block = BestBlock {
 		block_hash: node_status.current_best_block.block_hash.to_string(),
 		height: node_status.current_best_block.height,
@@ -57,7 +62,7 @@ pub(crate) async fn handle_get_node_info_request(
 	};
 	let network = network_to_proto(node_status.network) as i32;
 
-	let response = GetNodeInfoResponse {
+	GetNodeInfoResponse {
 		node_id,
 		current_best_block: Some(best_block),
 		latest_lightning_wallet_sync_timestamp: node_status.latest_lightning_wallet_sync_timestamp,
@@ -72,6 +77,5 @@ pub(crate) async fn handle_get_node_info_request(
 		node_uris,
 		network,
 		features,
-	};
-	Ok(response)
+	}
 }
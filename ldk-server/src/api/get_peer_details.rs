@@ -0,0 +1,59 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ldk_node::bitcoin::secp256k1::PublicKey;
+use ldk_node::lightning::routing::gossip::NodeId;
+use ldk_server_grpc::api::{GetPeerDetailsRequest, GetPeerDetailsResponse};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{InvalidRequestError, NotFoundError};
+use crate::service::Context;
+use crate::util::proto_adapter::{graph_node_announcement_to_proto, peer_to_proto};
+
+pub(crate) async fn handle_get_peer_details_request(
+	context: Arc<Context>, request: GetPeerDetailsRequest,
+) -> Result<GetPeerDetailsResponse, LdkServerError> {
+	let node_id = PublicKey::from_str(&request.node_id).map_err(|_| {
+		LdkServerError::new(
+			InvalidRequestError,
+			format!("Invalid node_id: {}. Expected a hex-encoded public key.", request.node_id),
+		)
+	})?;
+
+	let peer =
+		context.node.list_peers().into_iter().find(|peer| peer.node_id == node_id).ok_or_else(
+			|| LdkServerError::new(NotFoundError, format!("Peer {} not found.", node_id)),
+		)?;
+
+	let node_announcement = context
+		.node
+		.network_graph()
+		.node(&NodeId::from_pubkey(&node_id))
+		.and_then(|node_info| node_info.announcement_info)
+		.map(graph_node_announcement_to_proto);
+
+	let channels: Vec<_> = context
+		.node
+		.list_channels()
+		.into_iter()
+		.filter(|channel| channel.counterparty.node_id == node_id)
+		.collect();
+	let num_usable_channels = channels.iter().filter(|channel| channel.is_usable).count();
+
+	let response = GetPeerDetailsResponse {
+		peer: Some(peer_to_proto(peer)),
+		node_announcement,
+		num_channels: channels.len() as u32,
+		num_usable_channels: num_usable_channels as u32,
+	};
+	Ok(response)
+}
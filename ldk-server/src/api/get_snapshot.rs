@@ -0,0 +1,73 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_node::payment::PaymentStatus;
+use ldk_server_grpc::api::{GetSnapshotRequest, GetSnapshotResponse};
+use ldk_server_grpc::types::{PreflightCheck, PreflightCheckStatus};
+
+use crate::api::error::LdkServerError;
+use crate::api::get_balances::balances_to_proto;
+use crate::api::get_node_info::node_info_to_proto;
+use crate::api::run_preflight_checks::preflight_checks;
+use crate::service::Context;
+use crate::util::audit::current_timestamp;
+use crate::util::proto_adapter::{channel_to_proto, payment_to_proto, peer_to_proto};
+
+pub(crate) async fn handle_get_snapshot_request(
+	context: Arc<Context>, _request: GetSnapshotRequest,
+) -> Result<GetSnapshotResponse, LdkServerError> {
+	// The node offers no way to read its state atomically, so everything is read back-to-back
+	// before any conversion happens, keeping the window in which it may change small.
+	let timestamp = current_timestamp();
+	let status = context.node.status();
+	let balances = context.node.list_balances();
+	let channels = context.node.list_channels();
+	let peers = context.node.list_peers();
+	let pending_payments =
+		context.node.list_payments_with_filter(|p| p.status == PaymentStatus::Pending);
+
+	let checks = preflight_checks(&status, &channels, &balances, timestamp);
+
+	Ok(GetSnapshotResponse {
+		timestamp,
+		node_info: Some(node_info_to_proto(&context, status)),
+		balances: Some(balances_to_proto(balances)),
+		channels: channels.into_iter().map(channel_to_proto).collect(),
+		peers: peers.into_iter().map(peer_to_proto).collect(),
+		pending_payments: pending_payments.into_iter().map(payment_to_proto).collect(),
+		alerts: alerts(checks),
+	})
+}
+
+/// Keeps the checks that warrant attention, i.e. those that did not pass.
+fn alerts(checks: Vec<PreflightCheck>) -> Vec<PreflightCheck> {
+	checks.into_iter().filter(|check| check.status != PreflightCheckStatus::Pass as i32).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn check(name: &str, status: PreflightCheckStatus) -> PreflightCheck {
+		PreflightCheck { name: name.to_string(), status: status as i32, detail: String::new() }
+	}
+
+	#[test]
+	fn alerts_are_checks_that_did_not_pass() {
+		let checks = vec![
+			check("chain_source", PreflightCheckStatus::Pass),
+			check("onchain_wallet_synced", PreflightCheckStatus::Warn),
+			check("usable_channel", PreflightCheckStatus::Fail),
+		];
+		let names: Vec<_> = alerts(checks).into_iter().map(|check| check.name).collect();
+		assert_eq!(names, vec!["onchain_wallet_synced", "usable_channel"]);
+	}
+}
@@ -0,0 +1,139 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ldk_node::bitcoin::bip32::{self, DerivationPath, Xpriv, Xpub};
+use ldk_node::bitcoin::secp256k1::Secp256k1;
+use ldk_node::bitcoin::Network;
+use ldk_server_grpc::api::{GetWalletDescriptorRequest, GetWalletDescriptorResponse};
+
+use crate::api::error::LdkServerError;
+use crate::service::Context;
+
+/// The characters a descriptor may consist of, in the order used to compute its checksum, as
+/// defined by BIP 380.
+const DESCRIPTOR_INPUT_CHARSET: &str =
+	"0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const DESCRIPTOR_CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+pub(crate) async fn handle_get_wallet_descriptor_request(
+	context: Arc<Context>, _request: GetWalletDescriptorRequest,
+) -> Result<GetWalletDescriptorResponse, LdkServerError> {
+	Ok(context.config.wallet_descriptor.clone())
+}
+
+/// Derives the watch-only descriptors of the on-chain wallet from the node's seed.
+///
+/// ldk-node keeps its on-chain funds in a BIP 84 wallet whose master key is derived from the seed,
+/// so the descriptors match those of the BDK wallet it uses, with the account key replaced by its
+/// extended public key.
+pub(crate) fn wallet_descriptors(
+	seed: &[u8], network: Network,
+) -> Result<GetWalletDescriptorResponse, bip32::Error> {
+	let secp = Secp256k1::new();
+	let master_key = Xpriv::new_master(network, seed)?;
+	let coin_type = if network == Network::Bitcoin { 0 } else { 1 };
+	let account_path = DerivationPath::from_str(&format!("m/84'/{}'/0'", coin_type))?;
+	let account_key = Xpub::from_priv(&secp, &master_key.derive_priv(&secp, &account_path)?);
+
+	let descriptor = |keychain: u32| {
+		let descriptor = format!(
+			"wpkh([{}/84'/{}'/0']{}/{}/*)",
+			master_key.fingerprint(&secp),
+			coin_type,
+			account_key,
+			keychain
+		);
+		let checksum = descriptor_checksum(&descriptor);
+		format!("{}#{}", descriptor, checksum)
+	};
+	Ok(GetWalletDescriptorResponse {
+		external_descriptor: descriptor(0),
+		internal_descriptor: descriptor(1),
+	})
+}
+
+/// Computes the BIP 380 checksum of the given descriptor, which must only consist of characters
+/// in [`DESCRIPTOR_INPUT_CHARSET`].
+fn descriptor_checksum(descriptor: &str) -> String {
+	fn poly_mod(mut c: u64, value: u64) -> u64 {
+		const GENERATORS: [u64; 5] =
+			[0xf5dee51989, 0xa9fdca3312, 0x1bab10e32d, 0x3706b1677a, 0x644d626ffd];
+		let c0 = c >> 35;
+		c = ((c & 0x7ffffffff) << 5) ^ value;
+		for (i, generator) in GENERATORS.iter().enumerate() {
+			if (c0 >> i) & 1 == 1 {
+				c ^= generator;
+			}
+		}
+		c
+	}
+
+	let mut c = 1;
+	let mut class = 0;
+	let mut class_count = 0;
+	for ch in descriptor.chars() {
+		let position = DESCRIPTOR_INPUT_CHARSET.find(ch).expect("Invalid descriptor character");
+		let position = position as u64;
+		c = poly_mod(c, position & 31);
+		class = class * 3 + (position >> 5);
+		class_count += 1;
+		if class_count == 3 {
+			c = poly_mod(c, class);
+			class = 0;
+			class_count = 0;
+		}
+	}
+	if class_count > 0 {
+		c = poly_mod(c, class);
+	}
+	for _ in 0..8 {
+		c = poly_mod(c, 0);
+	}
+	c ^= 1;
+
+	(0..8)
+		.map(|j| DESCRIPTOR_CHECKSUM_CHARSET[((c >> (5 * (7 - j))) & 31) as usize] as char)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_node::bip39::Mnemonic;
+
+	use super::*;
+
+	const KNOWN_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+	#[test]
+	fn computes_descriptor_checksums() {
+		assert_eq!(descriptor_checksum("raw(deadbeef)"), "89f8spxm");
+	}
+
+	#[test]
+	fn descriptors_contain_only_public_keys() {
+		let seed = Mnemonic::from_str(KNOWN_MNEMONIC).unwrap().to_seed("");
+		let descriptors = wallet_descriptors(&seed, Network::Regtest).unwrap();
+
+		for (descriptor, keychain) in
+			[(&descriptors.external_descriptor, 0), (&descriptors.internal_descriptor, 1)]
+		{
+			assert!(descriptor.starts_with("wpkh([73c5da0a/84'/1'/0']tpub"), "{}", descriptor);
+			assert!(!descriptor.contains("prv"), "{}", descriptor);
+			let (body, checksum) = descriptor.split_once('#').unwrap();
+			assert!(body.ends_with(&format!("/{}/*)", keychain)));
+			assert_eq!(checksum, descriptor_checksum(body));
+		}
+
+		let mainnet = wallet_descriptors(&seed, Network::Bitcoin).unwrap();
+		assert!(mainnet.external_descriptor.starts_with("wpkh([73c5da0a/84'/0'/0']xpub"));
+	}
+}
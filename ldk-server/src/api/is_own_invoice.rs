@@ -0,0 +1,41 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use hex::prelude::*;
+use ldk_node::lightning::ln::channelmanager::PaymentId;
+use ldk_node::lightning_invoice::Bolt11Invoice;
+use ldk_node::payment::PaymentDirection;
+use ldk_server_grpc::api::{IsOwnInvoiceRequest, IsOwnInvoiceResponse};
+
+use crate::api::error::LdkServerError;
+use crate::service::Context;
+
+pub(crate) async fn handle_is_own_invoice_request(
+	context: Arc<Context>, request: IsOwnInvoiceRequest,
+) -> Result<IsOwnInvoiceResponse, LdkServerError> {
+	let invoice = Bolt11Invoice::from_str(request.invoice.as_str())
+		.map_err(|_| ldk_node::NodeError::InvalidInvoice)?;
+
+	// ldk-node records every invoice it creates as an inbound payment, keyed by the payment hash.
+	let payment_hash = invoice.payment_hash().0;
+	let is_signed_by_node = invoice.get_payee_pub_key() == context.node.node_id();
+	let is_recorded = context
+		.node
+		.payment(&PaymentId(payment_hash))
+		.is_some_and(|payment| payment.direction == PaymentDirection::Inbound);
+
+	let response = IsOwnInvoiceResponse {
+		is_own: is_signed_by_node && is_recorded,
+		payment_hash: payment_hash.to_lower_hex_string(),
+	};
+	Ok(response)
+}
@@ -56,13 +56,15 @@ pub(crate) async fn handle_list_forwarded_payments_request(
 					format!("Failed to read forwarded payment data: {}", e),
 				)
 			})?;
-		let forwarded_payment = ForwardedPayment::decode(Bytes::from(forwarded_payment_bytes))
+		let mut forwarded_payment = ForwardedPayment::decode(Bytes::from(forwarded_payment_bytes))
 			.map_err(|e| {
 				LdkServerError::new(
 					InternalServerError,
 					format!("Failed to decode forwarded payment: {}", e),
 				)
 			})?;
+		// Forwards recorded by earlier versions of LDK Server don't carry their identifier.
+		forwarded_payment.id = key;
 		forwarded_payments.push(forwarded_payment);
 	}
 	let response = ListForwardedPaymentsResponse {
@@ -0,0 +1,52 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_server_grpc::api::{ListOffersRequest, ListOffersResponse};
+use ldk_server_grpc::types::{OfferRecord, PageToken};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::io::persist::{
+	OFFERS_PERSISTENCE_PRIMARY_NAMESPACE, OFFERS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::service::Context;
+use crate::util::offers::read_offer_record;
+
+pub(crate) async fn handle_list_offers_request(
+	context: Arc<Context>, request: ListOffersRequest,
+) -> Result<ListOffersResponse, LdkServerError> {
+	let page_token = request.page_token.map(|p| (p.token, p.index));
+	let list_response = context
+		.paginated_kv_store
+		.list(
+			OFFERS_PERSISTENCE_PRIMARY_NAMESPACE,
+			OFFERS_PERSISTENCE_SECONDARY_NAMESPACE,
+			page_token,
+		)
+		.map_err(|e| {
+			LdkServerError::new(InternalServerError, format!("Failed to list offers: {}", e))
+		})?;
+
+	let mut offers: Vec<OfferRecord> = Vec::with_capacity(list_response.keys.len());
+	for key in list_response.keys {
+		let offer = read_offer_record(context.paginated_kv_store.as_ref(), &key).map_err(|e| {
+			LdkServerError::new(InternalServerError, format!("Failed to read offer: {}", e))
+		})?;
+		offers.extend(offer);
+	}
+	let response = ListOffersResponse {
+		offers,
+		next_page_token: list_response
+			.next_page_token
+			.map(|(token, index)| PageToken { token, index }),
+	};
+	Ok(response)
+}
@@ -9,6 +9,7 @@
 
 use ldk_node::config::{ChannelConfig, MaxDustHTLCExposure};
 use ldk_node::lightning::routing::router::RouteParametersConfig;
+use ldk_node::lightning_invoice::Bolt11Invoice;
 use ldk_node::CustomTlvRecord as NodeCustomTlvRecord;
 use ldk_server_grpc::types::channel_config::MaxDustHtlcExposure;
 use ldk_server_grpc::types::CustomTlvRecord as ProtoCustomTlvRecord;
@@ -26,31 +27,47 @@ pub(crate) mod bolt12_receive;
 pub(crate) mod bolt12_send;
 pub(crate) mod close_channel;
 pub(crate) mod connect_peer;
+pub(crate) mod create_lnurl_withdraw;
 pub(crate) mod decode_invoice;
 pub(crate) mod decode_offer;
 pub(crate) mod disconnect_peer;
 pub(crate) mod error;
 pub(crate) mod export_pathfinding_scores;
+pub(crate) mod get_api_version;
 pub(crate) mod get_balances;
+pub(crate) mod get_channel_announcement_status;
+pub(crate) mod get_channel_routing_stats;
+pub(crate) mod get_claimable_balances;
+pub(crate) mod get_config;
+pub(crate) mod get_forwarded_payment;
 pub(crate) mod get_node_info;
 pub(crate) mod get_payment_details;
+pub(crate) mod get_peer_details;
+pub(crate) mod get_snapshot;
+pub(crate) mod get_wallet_descriptor;
 pub(crate) mod graph_get_channel;
 pub(crate) mod graph_get_node;
 pub(crate) mod graph_list_channels;
 pub(crate) mod graph_list_nodes;
+pub(crate) mod is_own_invoice;
+pub(crate) mod list_audit_log;
 pub(crate) mod list_channels;
 pub(crate) mod list_forwarded_payments;
+pub(crate) mod list_offers;
 pub(crate) mod list_payments;
 pub(crate) mod list_peers;
 pub(crate) mod onchain_receive;
 pub(crate) mod onchain_send;
 pub(crate) mod open_channel;
+pub(crate) mod prune_payments;
+pub(crate) mod run_preflight_checks;
 pub(crate) mod sign_message;
 pub(crate) mod splice_channel;
 pub(crate) mod spontaneous_send;
 pub(crate) mod unified_send;
 pub(crate) mod update_channel_config;
 pub(crate) mod verify_signature;
+pub(crate) mod who_am_i;
 
 pub(crate) fn build_channel_config_from_proto(
 	default_config: ChannelConfig, proto_channel_config: ldk_server_grpc::types::ChannelConfig,
@@ -128,6 +145,22 @@ pub(crate) fn build_route_parameters_config_from_proto(
 	}
 }
 
+/// Rejects paying an invoice that requires payment metadata without including any.
+///
+/// LDK forwards an invoice's payment metadata to the recipient in the onion, but a recipient
+/// requiring the feature would fail a payment that arrives without it.
+pub(crate) fn check_bolt11_payment_metadata(invoice: &Bolt11Invoice) -> Result<(), LdkServerError> {
+	let requires_payment_metadata =
+		invoice.features().is_some_and(|features| features.requires_payment_metadata());
+	if requires_payment_metadata && invoice.payment_metadata().is_none() {
+		return Err(LdkServerError::new(
+			InvalidRequestError,
+			"Invoice requires payment metadata, but does not include any",
+		));
+	}
+	Ok(())
+}
+
 pub(crate) fn proto_to_node_custom_tlv(proto: &ProtoCustomTlvRecord) -> NodeCustomTlvRecord {
 	NodeCustomTlvRecord { type_num: proto.type_num, value: proto.value.to_vec() }
 }
@@ -16,6 +16,7 @@ use ldk_server_grpc::api::{OnchainSendRequest, OnchainSendResponse};
 use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::InvalidRequestError;
 use crate::service::Context;
+use crate::util::payment_limit::check_outbound_payment_amount;
 
 pub(crate) async fn handle_onchain_send_request(
 	context: Arc<Context>, request: OnchainSendRequest,
@@ -33,9 +34,20 @@ pub(crate) async fn handle_onchain_send_request(
 	let fee_rate = request.fee_rate_sat_per_vb.and_then(FeeRate::from_sat_per_vb);
 	let txid = match (request.amount_sats, request.send_all) {
 		(Some(amount_sats), None) => {
+			check_outbound_payment_amount(
+				context.max_outbound_payment_msat,
+				amount_sats.saturating_mul(1000),
+			)?;
 			context.node.onchain_payment().send_to_address(&address, amount_sats, fee_rate)?
 		},
 		(None, Some(true)) => {
+			if context.max_outbound_payment_msat.is_some() {
+				let spendable_sats = context.node.list_balances().spendable_onchain_balance_sats;
+				check_outbound_payment_amount(
+					context.max_outbound_payment_msat,
+					spendable_sats.saturating_mul(1000),
+				)?;
+			}
 			context.node.onchain_payment().send_all_to_address(&address, true, fee_rate)?
 		},
 		_ => {
@@ -14,10 +14,18 @@ use ldk_node::bitcoin::secp256k1::PublicKey;
 use ldk_node::config::ChannelConfig;
 use ldk_node::lightning::ln::msgs::SocketAddress;
 use ldk_server_grpc::api::{OpenChannelRequest, OpenChannelResponse};
+use log::warn;
 
 use crate::api::build_channel_config_from_proto;
 use crate::api::error::{LdkServerError, LdkServerErrorCode};
 use crate::service::Context;
+use crate::util::channel_policy::apply_min_cltv_expiry_delta;
+
+/// The smallest reserve counterparties are expected to require, which is what LDK requires.
+const MIN_RESERVE_SATS: u64 = 1_000;
+
+/// Reserves above this share of the channel amount, in percent, are considered a shortfall.
+const RESERVE_SHORTFALL_PERCENT: u64 = 5;
 
 pub(crate) async fn handle_open_channel(
 	context: Arc<Context>, request: OpenChannelRequest,
@@ -26,19 +34,32 @@ pub(crate) async fn handle_open_channel(
 		.map_err(|_| ldk_node::NodeError::InvalidPublicKey)?;
 	let address = SocketAddress::from_str(&request.address)
 		.map_err(|_| ldk_node::NodeError::InvalidSocketAddress)?;
-	if request.announce_channel && request.disable_counterparty_reserve {
-		return Err(LdkServerError::new(
-			LdkServerErrorCode::InvalidRequestError,
-			"Cannot set both `announce_channel` and `disable_counterparty_reserve`",
-		));
-	}
+	check_min_channel_size(context.config.min_channel_size_sat, request.channel_amount_sats)?;
+	let estimated_reserve_sats = estimated_reserve_sats(request.channel_amount_sats);
+	check_reserve_shortfall(
+		request.channel_amount_sats,
+		estimated_reserve_sats,
+		context.config.reject_reserve_shortfall,
+	)?;
+	let announce_channel = resolve_announce_channel(
+		request.announce_channel,
+		request.disable_counterparty_reserve,
+		context.config.announce_channels_by_default,
+	)?;
 
 	let channel_config = request
 		.channel_config
 		.map(|proto_config| build_channel_config_from_proto(ChannelConfig::default(), proto_config))
 		.transpose()?;
+	let channel_config = match context.config.min_cltv_expiry_delta {
+		Some(min_cltv_expiry_delta) => Some(apply_min_cltv_expiry_delta(
+			channel_config.unwrap_or_default(),
+			min_cltv_expiry_delta,
+		)),
+		None => channel_config,
+	};
 
-	let user_channel_id = if request.announce_channel {
+	let user_channel_id = if announce_channel {
 		context.node.open_announced_channel(
 			node_id,
 			address,
@@ -64,6 +85,135 @@ pub(crate) async fn handle_open_channel(
 		)?
 	};
 
-	let response = OpenChannelResponse { user_channel_id: user_channel_id.0.to_string() };
+	let response = OpenChannelResponse {
+		user_channel_id: user_channel_id.0.to_string(),
+		estimated_reserve_sats,
+	};
 	Ok(response)
 }
+
+fn check_min_channel_size(
+	min_channel_size_sat: Option<u64>, channel_amount_sats: u64,
+) -> Result<(), LdkServerError> {
+	match min_channel_size_sat {
+		Some(min_channel_size_sat) if channel_amount_sats < min_channel_size_sat => {
+			Err(LdkServerError::new(
+				LdkServerErrorCode::InvalidRequestError,
+				format!(
+					"Channel amount of {} sats is below the minimum channel size of {} sats",
+					channel_amount_sats, min_channel_size_sat
+				),
+			))
+		},
+		_ => Ok(()),
+	}
+}
+
+/// Estimates the reserve the counterparty requires this node to keep in a channel of the given
+/// amount.
+///
+/// The counterparty only chooses its reserve once the channel is negotiated. LDK and most other
+/// implementations follow the BOLT 2 recommendation of 1% of the channel amount, with a floor that
+/// makes the reserve a large share of small channels.
+fn estimated_reserve_sats(channel_amount_sats: u64) -> u64 {
+	(channel_amount_sats / 100).max(MIN_RESERVE_SATS).min(channel_amount_sats)
+}
+
+/// Logs a warning about, or if configured rejects, opening a channel whose reserve leaves the
+/// usable outbound balance significantly below the channel amount.
+fn check_reserve_shortfall(
+	channel_amount_sats: u64, reserve_sats: u64, reject_reserve_shortfall: bool,
+) -> Result<(), LdkServerError> {
+	// Computed in u128, as the channel amount is only bounded by what the request asks for.
+	if u128::from(reserve_sats) * 100
+		<= u128::from(channel_amount_sats) * u128::from(RESERVE_SHORTFALL_PERCENT)
+	{
+		return Ok(());
+	}
+	let message = format!(
+		"Channel reserve of about {} sats leaves only {} of the {} sats channel amount usable",
+		reserve_sats,
+		channel_amount_sats.saturating_sub(reserve_sats),
+		channel_amount_sats
+	);
+	if reject_reserve_shortfall {
+		return Err(LdkServerError::new(LdkServerErrorCode::InvalidRequestError, message));
+	}
+	warn!("{}", message);
+	Ok(())
+}
+
+/// Returns whether the channel is to be announced, falling back to the configured default if the
+/// request leaves it unset.
+///
+/// Channels without a counterparty reserve cannot be announced, so the default does not apply to
+/// them.
+fn resolve_announce_channel(
+	announce_channel: Option<bool>, disable_counterparty_reserve: bool,
+	announce_channels_by_default: bool,
+) -> Result<bool, LdkServerError> {
+	match announce_channel {
+		Some(true) if disable_counterparty_reserve => Err(LdkServerError::new(
+			LdkServerErrorCode::InvalidRequestError,
+			"Cannot set both `announce_channel` and `disable_counterparty_reserve`",
+		)),
+		Some(announce_channel) => Ok(announce_channel),
+		None => Ok(announce_channels_by_default && !disable_counterparty_reserve),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn channels_below_the_minimum_size_are_rejected() {
+		assert!(check_min_channel_size(None, 1).is_ok());
+		assert!(check_min_channel_size(Some(100_000), 100_000).is_ok());
+		let err = check_min_channel_size(Some(100_000), 99_999).unwrap_err();
+		assert_eq!(err.error_code, LdkServerErrorCode::InvalidRequestError);
+		assert_eq!(
+			err.message,
+			"Channel amount of 99999 sats is below the minimum channel size of 100000 sats"
+		);
+	}
+
+	#[test]
+	fn reserves_of_small_channels_are_a_shortfall() {
+		assert_eq!(estimated_reserve_sats(1_000_000), 10_000);
+		assert_eq!(estimated_reserve_sats(20_000), 1_000);
+		assert_eq!(estimated_reserve_sats(500), 500);
+
+		assert!(check_reserve_shortfall(1_000_000, 10_000, true).is_ok());
+		assert!(check_reserve_shortfall(20_000, 1_000, true).is_ok());
+		assert!(check_reserve_shortfall(19_999, 1_000, false).is_ok());
+		let err = check_reserve_shortfall(19_999, 1_000, true).unwrap_err();
+		assert_eq!(err.error_code, LdkServerErrorCode::InvalidRequestError);
+		assert_eq!(
+			err.message,
+			"Channel reserve of about 1000 sats leaves only 18999 of the 19999 sats channel amount usable"
+		);
+
+		let reserve_sats = estimated_reserve_sats(u64::MAX);
+		assert_eq!(reserve_sats, u64::MAX / 100);
+		assert!(check_reserve_shortfall(u64::MAX, reserve_sats, true).is_ok());
+		assert!(check_reserve_shortfall(u64::MAX, u64::MAX, false).is_ok());
+		assert!(check_reserve_shortfall(u64::MAX, u64::MAX, true).is_err());
+	}
+
+	#[test]
+	fn omitted_announce_channel_inherits_configured_default() {
+		assert!(!resolve_announce_channel(None, false, false).unwrap());
+		assert!(resolve_announce_channel(None, false, true).unwrap());
+		assert!(!resolve_announce_channel(None, true, true).unwrap());
+	}
+
+	#[test]
+	fn explicit_announce_channel_overrides_configured_default() {
+		assert!(!resolve_announce_channel(Some(false), false, true).unwrap());
+		assert!(resolve_announce_channel(Some(true), false, false).unwrap());
+		assert!(!resolve_announce_channel(Some(false), true, true).unwrap());
+		let err = resolve_announce_channel(Some(true), true, false).unwrap_err();
+		assert_eq!(err.error_code, LdkServerErrorCode::InvalidRequestError);
+	}
+}
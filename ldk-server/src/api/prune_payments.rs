@@ -0,0 +1,53 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_server_grpc::api::{PrunePaymentsRequest, PrunePaymentsResponse};
+use ldk_server_grpc::events::EventEnvelope;
+use tokio::sync::broadcast;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{InternalServerError, InvalidRequestError};
+use crate::service::Context;
+use crate::util::audit::current_timestamp;
+use crate::util::config::PaymentHistoryRetention;
+use crate::util::payment_history::prune_payments;
+
+pub(crate) async fn handle_prune_payments_request(
+	context: Arc<Context>, event_sender: broadcast::Sender<EventEnvelope>,
+	request: PrunePaymentsRequest,
+) -> Result<PrunePaymentsResponse, LdkServerError> {
+	let retention = match (request.max_age_secs, request.max_records) {
+		(None, None) => context.config.payment_history_retention.ok_or_else(|| {
+			LdkServerError::new(
+				InvalidRequestError,
+				"No payment history retention is configured. Please specify `max_age_secs` or \
+				`max_records`.",
+			)
+		})?,
+		(max_age_secs, max_records) => PaymentHistoryRetention { max_age_secs, max_records },
+	};
+
+	let summary = prune_payments(
+		context.paginated_kv_store.as_ref(),
+		&retention,
+		current_timestamp(),
+		&event_sender,
+	)
+	.map_err(|e| {
+		LdkServerError::new(InternalServerError, format!("Failed to prune payments: {}", e))
+	})?;
+
+	let response = PrunePaymentsResponse {
+		pruned_count: summary.pruned_count,
+		remaining_count: summary.remaining_count,
+	};
+	Ok(response)
+}
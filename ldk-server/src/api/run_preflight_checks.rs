@@ -0,0 +1,212 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ldk_node::{BalanceDetails, ChannelDetails, NodeStatus};
+use ldk_server_grpc::api::{RunPreflightChecksRequest, RunPreflightChecksResponse};
+use ldk_server_grpc::types::{PreflightCheck, PreflightCheckStatus};
+
+use crate::api::error::LdkServerError;
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	PREFLIGHT_PERSISTENCE_PRIMARY_NAMESPACE, PREFLIGHT_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::service::Context;
+
+/// Syncs longer ago than this are reported as stale. The background sync intervals are all well
+/// below it.
+const MAX_SYNC_AGE_SECS: u64 = 30 * 60;
+
+/// The key the persistence check writes to and removes again.
+const PERSISTENCE_PROBE_KEY: &str = "probe";
+
+pub(crate) async fn handle_run_preflight_checks_request(
+	context: Arc<Context>, _request: RunPreflightChecksRequest,
+) -> Result<RunPreflightChecksResponse, LdkServerError> {
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+	let status = context.node.status();
+	let channels = context.node.list_channels();
+	let balances = context.node.list_balances();
+
+	let mut checks = preflight_checks(&status, &channels, &balances, now);
+	checks.push(persistence_check(context.paginated_kv_store.as_ref(), now));
+	Ok(RunPreflightChecksResponse { checks })
+}
+
+/// Runs the checks against the given node state, which callers read beforehand.
+///
+/// The persistence check is left out, as it writes to the store, which read-only callers such as
+/// `GetSnapshot` must not do.
+pub(crate) fn preflight_checks(
+	status: &NodeStatus, channels: &[ChannelDetails], balances: &BalanceDetails, now: u64,
+) -> Vec<PreflightCheck> {
+	vec![
+		sync_check(
+			"chain_source",
+			"fee rate estimates",
+			status.latest_fee_rate_cache_update_timestamp,
+			now,
+		),
+		sync_check(
+			"onchain_wallet_synced",
+			"on-chain wallet",
+			status.latest_onchain_wallet_sync_timestamp,
+			now,
+		),
+		sync_check(
+			"lightning_wallet_synced",
+			"Lightning wallet",
+			status.latest_lightning_wallet_sync_timestamp,
+			now,
+		),
+		usable_channel_check(channels.len(), channels.iter().filter(|c| c.is_usable).count()),
+		anchor_reserve_check(
+			balances.total_onchain_balance_sats,
+			balances.spendable_onchain_balance_sats,
+			balances.total_anchor_channels_reserve_sats,
+		),
+	]
+}
+
+fn check(name: &str, status: PreflightCheckStatus, detail: String) -> PreflightCheck {
+	PreflightCheck { name: name.to_string(), status: status as i32, detail }
+}
+
+/// Checks that `what` was last synced recently. As syncing goes through the chain source, this
+/// also tells whether the chain source is reachable.
+fn sync_check(name: &str, what: &str, last_synced_at: Option<u64>, now: u64) -> PreflightCheck {
+	match last_synced_at {
+		None => check(name, PreflightCheckStatus::Fail, format!("The {} was never synced", what)),
+		Some(timestamp) => {
+			let age_secs = now.saturating_sub(timestamp);
+			if age_secs > MAX_SYNC_AGE_SECS {
+				let detail = format!("The {} was last synced {} seconds ago", what, age_secs);
+				check(name, PreflightCheckStatus::Warn, detail)
+			} else {
+				let detail = format!("The {} was synced {} seconds ago", what, age_secs);
+				check(name, PreflightCheckStatus::Pass, detail)
+			}
+		},
+	}
+}
+
+fn usable_channel_check(num_channels: usize, num_usable_channels: usize) -> PreflightCheck {
+	let name = "usable_channel";
+	if num_usable_channels > 0 {
+		let detail = format!("{} of {} channels are usable", num_usable_channels, num_channels);
+		check(name, PreflightCheckStatus::Pass, detail)
+	} else if num_channels == 0 {
+		check(name, PreflightCheckStatus::Fail, "No channels are open".to_string())
+	} else {
+		let detail = format!("None of the {} channels are usable", num_channels);
+		check(name, PreflightCheckStatus::Fail, detail)
+	}
+}
+
+/// Checks that the on-chain balance covers the reserve kept for fee-bumping force-closes of
+/// anchor channels.
+///
+/// The reserve ldk-node reports never exceeds the on-chain balance, so a reserve taking up the
+/// whole balance may be short of what is needed and is reported as a warning.
+fn anchor_reserve_check(
+	total_onchain_balance_sats: u64, spendable_onchain_balance_sats: u64, reserve_sats: u64,
+) -> PreflightCheck {
+	let name = "anchor_reserve";
+	if reserve_sats == 0 {
+		check(name, PreflightCheckStatus::Pass, "No anchor channels need a reserve".to_string())
+	} else if total_onchain_balance_sats < reserve_sats {
+		let detail = format!(
+			"The on-chain balance of {} sats is below the anchor channel reserve of {} sats",
+			total_onchain_balance_sats, reserve_sats
+		);
+		check(name, PreflightCheckStatus::Fail, detail)
+	} else if spendable_onchain_balance_sats == 0 {
+		let detail = format!(
+			"The anchor channel reserve takes up the whole on-chain balance of {} sats",
+			total_onchain_balance_sats
+		);
+		check(name, PreflightCheckStatus::Warn, detail)
+	} else {
+		let detail = format!(
+			"The on-chain balance of {} sats covers the anchor channel reserve of {} sats",
+			total_onchain_balance_sats, reserve_sats
+		);
+		check(name, PreflightCheckStatus::Pass, detail)
+	}
+}
+
+/// Checks that the store is writable by writing a probe and removing it again.
+fn persistence_check(store: &dyn PaginatedKVStore, now: u64) -> PreflightCheck {
+	let name = "persistence_writable";
+	let (primary_namespace, secondary_namespace) =
+		(PREFLIGHT_PERSISTENCE_PRIMARY_NAMESPACE, PREFLIGHT_PERSISTENCE_SECONDARY_NAMESPACE);
+	let result = store
+		.write(primary_namespace, secondary_namespace, PERSISTENCE_PROBE_KEY, now as i64, &[])
+		.and_then(|()| store.remove(primary_namespace, secondary_namespace, PERSISTENCE_PROBE_KEY));
+	match result {
+		Ok(()) => check(name, PreflightCheckStatus::Pass, "The store is writable".to_string()),
+		Err(e) => {
+			let detail = format!("Failed to write to the store: {}", e);
+			check(name, PreflightCheckStatus::Fail, detail)
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::random_storage_path;
+	use crate::io::persist::sqlite_store::SqliteStore;
+
+	fn status(check: &PreflightCheck) -> PreflightCheckStatus {
+		PreflightCheckStatus::from_i32(check.status).unwrap()
+	}
+
+	#[test]
+	fn sync_check_fails_when_never_synced_and_warns_when_stale() {
+		let now = 1_700_000_000;
+		assert_eq!(status(&sync_check("sync", "wallet", None, now)), PreflightCheckStatus::Fail);
+		let stale = sync_check("sync", "wallet", Some(now - MAX_SYNC_AGE_SECS - 1), now);
+		assert_eq!(status(&stale), PreflightCheckStatus::Warn);
+		let fresh = sync_check("sync", "wallet", Some(now - 30), now);
+		assert_eq!(status(&fresh), PreflightCheckStatus::Pass);
+		assert_eq!(fresh.detail, "The wallet was synced 30 seconds ago");
+	}
+
+	#[test]
+	fn usable_channel_check_requires_a_usable_channel() {
+		assert_eq!(status(&usable_channel_check(0, 0)), PreflightCheckStatus::Fail);
+		assert_eq!(status(&usable_channel_check(2, 0)), PreflightCheckStatus::Fail);
+		assert_eq!(status(&usable_channel_check(2, 1)), PreflightCheckStatus::Pass);
+	}
+
+	#[test]
+	fn anchor_reserve_check_compares_balance_to_reserve() {
+		assert_eq!(status(&anchor_reserve_check(0, 0, 0)), PreflightCheckStatus::Pass);
+		assert_eq!(status(&anchor_reserve_check(10_000, 0, 25_000)), PreflightCheckStatus::Fail);
+		assert_eq!(status(&anchor_reserve_check(25_000, 0, 25_000)), PreflightCheckStatus::Warn);
+		let covered = anchor_reserve_check(30_000, 5_000, 25_000);
+		assert_eq!(status(&covered), PreflightCheckStatus::Pass);
+	}
+
+	#[test]
+	fn persistence_check_leaves_no_probe_behind() {
+		let dir = random_storage_path();
+		let store = SqliteStore::new(dir.clone(), None, None).unwrap();
+
+		assert_eq!(status(&persistence_check(&store, 1_700_000_000)), PreflightCheckStatus::Pass);
+		let (primary_namespace, secondary_namespace) =
+			(PREFLIGHT_PERSISTENCE_PRIMARY_NAMESPACE, PREFLIGHT_PERSISTENCE_SECONDARY_NAMESPACE);
+		assert!(store.list(primary_namespace, secondary_namespace, None).unwrap().keys.is_empty());
+
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+}
@@ -20,6 +20,7 @@ use ldk_server_grpc::api::{
 use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::InvalidRequestError;
 use crate::service::Context;
+use crate::util::payment_limit::check_outbound_payment_amount;
 
 pub(crate) async fn handle_splice_in_request(
 	context: Arc<Context>, request: SpliceInRequest,
@@ -37,6 +38,10 @@ pub(crate) async fn handle_splice_out_request(
 ) -> Result<SpliceOutResponse, LdkServerError> {
 	let user_channel_id = parse_user_channel_id(&request.user_channel_id)?;
 	let counterparty_node_id = parse_counterparty_node_id(&request.counterparty_node_id)?;
+	check_outbound_payment_amount(
+		context.max_outbound_payment_msat,
+		request.splice_amount_sats.saturating_mul(1000),
+	)?;
 
 	let address = request
 		.address
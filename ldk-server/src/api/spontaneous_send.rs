@@ -17,6 +17,7 @@ use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::InvalidRequestError;
 use crate::api::{build_route_parameters_config_from_proto, proto_to_node_custom_tlv};
 use crate::service::Context;
+use crate::util::payment_limit::check_outbound_payment_amount;
 
 pub(crate) async fn handle_spontaneous_send_request(
 	context: Arc<Context>, request: SpontaneousSendRequest,
@@ -25,6 +26,8 @@ pub(crate) async fn handle_spontaneous_send_request(
 		LdkServerError::new(InvalidRequestError, "Invalid node_id provided.".to_string())
 	})?;
 
+	check_outbound_payment_amount(context.max_outbound_payment_msat, request.amount_msat)?;
+
 	let route_parameters = build_route_parameters_config_from_proto(request.route_parameters)?;
 
 	let payment_id = if request.custom_tlvs.is_empty() {
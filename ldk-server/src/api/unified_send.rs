@@ -7,19 +7,32 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
+use std::str::FromStr;
 use std::sync::Arc;
 
+use ldk_node::lightning_invoice::Bolt11Invoice;
 use ldk_node::payment::UnifiedPaymentResult;
 use ldk_server_grpc::api::unified_send_response::PaymentResult;
 use ldk_server_grpc::api::{UnifiedSendRequest, UnifiedSendResponse};
 
-use crate::api::build_route_parameters_config_from_proto;
 use crate::api::error::LdkServerError;
+use crate::api::{build_route_parameters_config_from_proto, check_bolt11_payment_metadata};
 use crate::service::Context;
+use crate::util::payment_limit::{check_optional_outbound_payment_amount, payment_uri_amount_msat};
 
 pub(crate) async fn handle_unified_send_request(
 	context: Arc<Context>, request: UnifiedSendRequest,
 ) -> Result<UnifiedSendResponse, LdkServerError> {
+	if context.max_outbound_payment_msat.is_some() {
+		// Which amount ends up being paid depends on the chosen payment method, so be conservative.
+		let amount_msat = request.amount_msat.max(payment_uri_amount_msat(&request.uri));
+		check_optional_outbound_payment_amount(context.max_outbound_payment_msat, amount_msat)?;
+	}
+
+	if let Ok(invoice) = Bolt11Invoice::from_str(request.uri.trim()) {
+		check_bolt11_payment_metadata(&invoice)?;
+	}
+
 	let route_parameters = build_route_parameters_config_from_proto(request.route_parameters)?;
 
 	let result = context
@@ -18,6 +18,7 @@ use crate::api::build_channel_config_from_proto;
 use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::{InvalidRequestError, LightningError};
 use crate::service::Context;
+use crate::util::channel_policy::apply_min_cltv_expiry_delta;
 
 pub(crate) async fn handle_update_channel_config_request(
 	context: Arc<Context>, request: UpdateChannelConfigRequest,
@@ -44,6 +45,12 @@ pub(crate) async fn handle_update_channel_config_request(
 			LdkServerError::new(InvalidRequestError, "Channel config must be provided.")
 		})?,
 	)?;
+	let updated_channel_config = match context.config.min_cltv_expiry_delta {
+		Some(min_cltv_expiry_delta) => {
+			apply_min_cltv_expiry_delta(updated_channel_config, min_cltv_expiry_delta)
+		},
+		None => updated_channel_config,
+	};
 
 	let counterparty_node_id = PublicKey::from_str(&request.counterparty_node_id).map_err(|e| {
 		LdkServerError::new(
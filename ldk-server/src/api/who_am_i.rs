@@ -0,0 +1,21 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use ldk_server_grpc::api::{WhoAmIRequest, WhoAmIResponse};
+
+use crate::api::error::LdkServerError;
+use crate::util::auth::ApiKey;
+
+pub(crate) async fn handle_who_am_i_request(
+	api_key: ApiKey, _request: WhoAmIRequest,
+) -> Result<WhoAmIResponse, LdkServerError> {
+	let scopes = api_key.scope.granted().map(|scope| scope.as_str().to_string()).collect();
+	let response = WhoAmIResponse { name: api_key.name, scopes };
+	Ok(response)
+}
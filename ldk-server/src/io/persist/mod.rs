@@ -10,10 +10,32 @@
 pub(crate) mod paginated_kv_store;
 pub(crate) mod sqlite_store;
 
+/// The audit log entries will be persisted under this prefix.
+pub(crate) const AUDIT_LOG_PERSISTENCE_PRIMARY_NAMESPACE: &str = "audit_log";
+pub(crate) const AUDIT_LOG_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
 /// The forwarded payments will be persisted under this prefix.
 pub(crate) const FORWARDED_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "forwarded_payments";
 pub(crate) const FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
 
+/// The BOLT11 invoices created by the node will be persisted under this prefix until they are paid
+/// or expire.
+pub(crate) const INVOICES_PERSISTENCE_PRIMARY_NAMESPACE: &str = "invoices";
+pub(crate) const INVOICES_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The LNURL-withdraws created by the node will be persisted under this prefix.
+pub(crate) const LNURL_WITHDRAWS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "lnurl_withdraws";
+pub(crate) const LNURL_WITHDRAWS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The BOLT12 offers created by the node will be persisted under this prefix.
+pub(crate) const OFFERS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "offers";
+pub(crate) const OFFERS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
 /// The payments will be persisted under this prefix.
 pub(crate) const PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "payments";
 pub(crate) const PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The probe written by the preflight checks to test that the store is writable will be persisted
+/// under this prefix.
+pub(crate) const PREFLIGHT_PERSISTENCE_PRIMARY_NAMESPACE: &str = "preflight";
+pub(crate) const PREFLIGHT_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
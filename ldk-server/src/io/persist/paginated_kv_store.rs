@@ -58,6 +58,13 @@ pub trait PaginatedKVStore: Send + Sync {
 		&self, primary_namespace: &str, secondary_namespace: &str, key: &str, time: i64, buf: &[u8],
 	) -> Result<(), io::Error>;
 
+	/// Removes any data that had previously been persisted under the given `key`.
+	///
+	/// Removing a `key` that does not exist is not an error.
+	fn remove(
+		&self, primary_namespace: &str, secondary_namespace: &str, key: &str,
+	) -> Result<(), io::Error>;
+
 	/// Returns a paginated list of keys that are stored under the given `secondary_namespace` in
 	/// `primary_namespace`, ordered in descending order of `time`.
 	///
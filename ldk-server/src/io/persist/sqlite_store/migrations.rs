@@ -0,0 +1,78 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::path::Path;
+use std::{fs, io};
+
+use rusqlite::{Connection, Transaction};
+
+/// Migrates the database schema from `from_version` up to `to_version`, one version at a time.
+///
+/// A copy of the database is written to `backup_path` before anything is changed. All migrations
+/// run in a single transaction, so a failed migration leaves the database at `from_version`.
+pub(super) fn migrate_schema(
+	connection: &mut Connection, kv_table_name: &str, from_version: u16, to_version: u16,
+	backup_path: &Path,
+) -> io::Result<()> {
+	assert!(from_version < to_version, "Schema migrations only run forward");
+
+	// A backup left behind by a previously failed migration was taken at the same version, as the
+	// changes were rolled back, so it is safe to replace.
+	match fs::remove_file(backup_path) {
+		Ok(()) => {},
+		Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+		Err(e) => return Err(e),
+	}
+	connection.execute("VACUUM INTO ?1", [backup_path.to_string_lossy()]).map_err(|e| {
+		let msg = format!("Failed to back up database to {}: {}", backup_path.display(), e);
+		io::Error::other(msg)
+	})?;
+
+	let tx = connection.transaction().map_err(|e| {
+		let msg = format!("Failed to start schema migration transaction: {}", e);
+		io::Error::other(msg)
+	})?;
+
+	for version in from_version..to_version {
+		match version {
+			1 => migrate_v1_to_v2(&tx, kv_table_name)?,
+			_ => {
+				let msg = format!("No schema migration from version {}", version);
+				return Err(io::Error::other(msg));
+			},
+		}
+	}
+
+	tx.pragma(Some(rusqlite::DatabaseName::Main), "user_version", to_version, |_| Ok(())).map_err(
+		|e| {
+			let msg = format!("Failed to set PRAGMA user_version: {}", e);
+			io::Error::other(msg)
+		},
+	)?;
+
+	tx.commit().map_err(|e| {
+		let msg = format!("Failed to commit schema migration: {}", e);
+		io::Error::other(msg)
+	})
+}
+
+/// Replaces the index on `creation_time` with one also covering the namespaces, as paginated
+/// listing filters on the namespaces before ordering by creation time.
+fn migrate_v1_to_v2(tx: &Transaction, kv_table_name: &str) -> io::Result<()> {
+	let sql = format!(
+		"DROP INDEX IF EXISTS idx_creation_time;
+		CREATE INDEX IF NOT EXISTS idx_namespace_creation_time
+		ON {} (primary_namespace, secondary_namespace, creation_time);",
+		kv_table_name
+	);
+	tx.execute_batch(&sql).map_err(|e| {
+		let msg = format!("Failed to migrate table {} to schema version 2: {}", kv_table_name, e);
+		io::Error::other(msg)
+	})
+}
@@ -7,11 +7,14 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
+mod migrations;
+
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::{fs, io};
 
 use ldk_node::lightning::types::string::PrintableString;
+use log::info;
 use rusqlite::{named_params, Connection};
 
 use crate::io::persist::paginated_kv_store::{ListResponse, PaginatedKVStore};
@@ -23,8 +26,9 @@ pub const DEFAULT_SQLITE_DB_FILE_NAME: &str = "ldk_server_data.sqlite";
 /// The default table in which we store all the paginated data.
 pub const DEFAULT_PAGINATED_KV_TABLE_NAME: &str = "ldk_paginated_data";
 
-// The current SQLite `user_version`, which we can use if we'd ever need to do a schema migration.
-const SCHEMA_USER_VERSION: u16 = 1;
+// The current SQLite `user_version`. Databases created with an older version are migrated forward
+// on startup, see `migrations::migrate_schema`.
+const SCHEMA_USER_VERSION: u16 = 2;
 
 // The maximum number of keys retrieved per page in paginated list operation.
 const LIST_KEYS_MAX_PAGE_SIZE: i32 = 100;
@@ -57,9 +61,9 @@ impl SqliteStore {
 			io::Error::other(msg)
 		})?;
 		let mut db_file_path = data_dir;
-		db_file_path.push(db_file_name);
+		db_file_path.push(&db_file_name);
 
-		let connection = Connection::open(db_file_path.clone()).map_err(|e| {
+		let mut connection = Connection::open(db_file_path.clone()).map_err(|e| {
 			let msg =
 				format!("Failed to open/create database file {}: {}", db_file_path.display(), e);
 			io::Error::other(msg)
@@ -87,6 +91,22 @@ impl SqliteStore {
 				version_res, SCHEMA_USER_VERSION
 			);
 			return Err(io::Error::other(msg));
+		} else if version_res < SCHEMA_USER_VERSION {
+			let backup_path =
+				db_file_path.with_file_name(format!("{}.v{}.bak", db_file_name, version_res));
+			migrations::migrate_schema(
+				&mut connection,
+				&paginated_kv_table_name,
+				version_res,
+				SCHEMA_USER_VERSION,
+				&backup_path,
+			)?;
+			info!(
+				"Migrated database schema from version {} to {}, backup written to {}",
+				version_res,
+				SCHEMA_USER_VERSION,
+				backup_path.display()
+			);
 		}
 
 		let create_paginated_kv_table_sql = format!(
@@ -106,7 +126,8 @@ impl SqliteStore {
 		})?;
 
 		let index_creation_time_sql = format!(
-			"CREATE INDEX IF NOT EXISTS idx_creation_time ON {} (creation_time);",
+			"CREATE INDEX IF NOT EXISTS idx_namespace_creation_time \
+			ON {} (primary_namespace, secondary_namespace, creation_time);",
 			paginated_kv_table_name
 		);
 
@@ -223,6 +244,41 @@ impl PaginatedKVStore for SqliteStore {
 		})
 	}
 
+	fn remove(
+		&self, primary_namespace: &str, secondary_namespace: &str, key: &str,
+	) -> io::Result<()> {
+		check_namespace_key_validity(primary_namespace, secondary_namespace, Some(key), "remove")?;
+
+		let locked_conn = self.connection.lock().unwrap();
+
+		let sql = format!(
+			"DELETE FROM {} WHERE primary_namespace=:primary_namespace AND secondary_namespace=:secondary_namespace AND key=:key;",
+			self.paginated_kv_table_name
+		);
+
+		let mut stmt = locked_conn.prepare_cached(&sql).map_err(|e| {
+			let msg = format!("Failed to prepare statement: {}", e);
+			io::Error::other(msg)
+		})?;
+
+		stmt.execute(named_params! {
+			":primary_namespace": primary_namespace,
+			":secondary_namespace": secondary_namespace,
+			":key": key,
+		})
+		.map(|_| ())
+		.map_err(|e| {
+			let msg = format!(
+				"Failed to delete key {}/{}/{}: {}",
+				PrintableString(primary_namespace),
+				PrintableString(secondary_namespace),
+				PrintableString(key),
+				e
+			);
+			io::Error::other(msg)
+		})
+	}
+
 	fn list(
 		&self, primary_namespace: &str, secondary_namespace: &str,
 		page_token: Option<(String, i64)>,
@@ -288,7 +344,7 @@ impl PaginatedKVStore for SqliteStore {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
 	use std::panic::RefUnwindSafe;
 
 	use hex::DisplayHex;
@@ -309,6 +365,74 @@ mod tests {
 		do_read_write_remove_list_persist(&store);
 	}
 
+	#[test]
+	fn migrates_v1_database_forward() {
+		let mut temp_path = random_storage_path();
+		temp_path.push("migrates_v1_database_forward");
+		fs::create_dir_all(&temp_path).unwrap();
+		let db_file_path = temp_path.join("test_db");
+
+		// A database as created with schema version 1.
+		{
+			let connection = Connection::open(&db_file_path).unwrap();
+			connection.pragma_update(None, "user_version", 1u16).unwrap();
+			connection
+				.execute_batch(
+					"CREATE TABLE test_table (
+					primary_namespace TEXT NOT NULL,
+					secondary_namespace TEXT DEFAULT \"\" NOT NULL,
+					key TEXT NOT NULL CHECK (key <> ''),
+					creation_time INTEGER NOT NULL,
+					value BLOB, PRIMARY KEY ( primary_namespace, secondary_namespace, key )
+					);
+					CREATE INDEX idx_creation_time ON test_table (creation_time);
+					INSERT INTO test_table VALUES ('payments', '', 'payment_0', 1, x'2a');",
+				)
+				.unwrap();
+		}
+
+		let open_store = || {
+			SqliteStore::new(
+				temp_path.clone(),
+				Some("test_db".to_string()),
+				Some("test_table".to_string()),
+			)
+		};
+		let store = open_store().unwrap();
+		assert_eq!(store.read("payments", "", "payment_0").unwrap(), vec![42]);
+		{
+			let connection = store.connection.lock().unwrap();
+			assert_eq!(user_version(&connection), SCHEMA_USER_VERSION);
+			let mut stmt = connection
+				.prepare("SELECT name FROM sqlite_master WHERE type = 'index' AND sql IS NOT NULL")
+				.unwrap();
+			let indexes: Vec<String> =
+				stmt.query_map([], |row| row.get(0)).unwrap().map(Result::unwrap).collect();
+			assert_eq!(indexes, vec!["idx_namespace_creation_time".to_string()]);
+		}
+		drop(store);
+
+		// The backup holds the database as it was before the migration.
+		let backup = Connection::open(temp_path.join("test_db.v1.bak")).unwrap();
+		assert_eq!(user_version(&backup), 1);
+		let value: Vec<u8> =
+			backup.query_row("SELECT value FROM test_table", [], |row| row.get(0)).unwrap();
+		assert_eq!(value, vec![42]);
+
+		// Reopening a migrated database is a no-op, but newer schema versions are refused.
+		assert!(open_store().is_ok());
+		let connection = Connection::open(&db_file_path).unwrap();
+		connection.pragma_update(None, "user_version", SCHEMA_USER_VERSION + 1).unwrap();
+		drop(connection);
+		assert!(open_store().is_err());
+	}
+
+	fn user_version(connection: &Connection) -> u16 {
+		connection
+			.query_row("SELECT user_version FROM pragma_user_version", [], |row| row.get(0))
+			.unwrap()
+	}
+
 	pub(crate) fn random_storage_path() -> PathBuf {
 		let mut temp_path = std::env::temp_dir();
 		let mut bytes = [0u8; 8];
@@ -368,6 +492,13 @@ mod tests {
 		let read_data = kv_store.read(primary_namespace, secondary_namespace, testkey).unwrap();
 		assert_eq!(data, &*read_data);
 
+		kv_store.remove(primary_namespace, secondary_namespace, testkey).unwrap();
+		let err = kv_store.read(primary_namespace, secondary_namespace, testkey).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::NotFound);
+		assert_eq!(list_all_keys(primary_namespace, secondary_namespace).len(), 109);
+		// Removing a key that does not exist succeeds.
+		kv_store.remove(primary_namespace, secondary_namespace, testkey).unwrap();
+
 		// Ensure we have no issue operating with primary_namespace/secondary_namespace/key being KVSTORE_NAMESPACE_KEY_MAX_LEN
 		let max_chars: String = "A".repeat(KVSTORE_NAMESPACE_KEY_MAX_LEN);
 		kv_store.write(&max_chars, &max_chars, &max_chars, 0, &data).unwrap();
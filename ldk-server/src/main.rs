@@ -13,10 +13,10 @@ mod service;
 mod util;
 
 use std::collections::HashSet;
-use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fs, io};
 
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
@@ -25,34 +25,47 @@ use hex::DisplayHex;
 use hyper::server::conn::http2;
 use hyper_util::rt::{TokioExecutor, TokioIo};
 use ldk_node::bitcoin::Network;
-use ldk_node::config::{Config, ElectrumSyncConfig, EsploraSyncConfig};
+use ldk_node::config::{BackgroundSyncConfig, Config, ElectrumSyncConfig, EsploraSyncConfig};
+use ldk_node::entropy::NodeEntropy;
 use ldk_node::lightning::events::ClosureReason;
 use ldk_node::lightning::ln::channelmanager::PaymentId;
 use ldk_node::lightning::ln::types::ChannelId;
+use ldk_node::lightning_types::payment::PaymentHash;
+use ldk_node::payment::PaymentStatus;
 use ldk_node::{Builder, CustomTlvRecord, Event, Node};
 use ldk_server_grpc::events;
 use ldk_server_grpc::events::{event_envelope, EventEnvelope};
-use ldk_server_grpc::types::{HtlcLocator, Payment};
-use log::{debug, error, info};
+use ldk_server_grpc::types::{ForwardedPayment, HtlcLocator, Payment};
+use log::{debug, error, info, warn};
 use prost::Message;
 use tokio::net::TcpListener;
 use tokio::select;
 use tokio::signal::unix::SignalKind;
 use tokio::sync::broadcast;
 
+use crate::api::get_api_version::enabled_features;
+use crate::api::get_wallet_descriptor::wallet_descriptors;
 use crate::api::node_to_proto_custom_tlv;
 use crate::io::persist::paginated_kv_store::PaginatedKVStore;
-use crate::io::persist::sqlite_store::SqliteStore;
+use crate::io::persist::sqlite_store::{SqliteStore, DEFAULT_SQLITE_DB_FILE_NAME};
 use crate::io::persist::{
 	FORWARDED_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
 	FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE, PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
 	PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
 };
-use crate::service::NodeService;
+use crate::service::{NodeService, ServiceConfig};
+use crate::util::audit::current_timestamp;
+use crate::util::auth::{ApiKey, ApiKeyScope, ADMIN_API_KEY_NAME};
+use crate::util::channel_policy::enforce_min_cltv_expiry_delta;
 use crate::util::config::{load_config, ArgsConfig, ChainSource};
+use crate::util::invoices::{expire_unpaid_invoices, INVOICE_EXPIRY_INTERVAL};
 use crate::util::logger::{LogConfig, ServerLogger};
 use crate::util::metrics::Metrics;
-use crate::util::proto_adapter::{forwarded_payment_to_proto, payment_to_proto};
+use crate::util::offers::{deactivate_expired_offers, OFFER_EXPIRY_INTERVAL};
+use crate::util::payment_history::{prune_payments, PAYMENT_PRUNING_INTERVAL};
+use crate::util::payment_log::PaymentLogLine;
+use crate::util::proto_adapter::payment_to_proto;
+use crate::util::rate_limit::RateLimits;
 use crate::util::tls::get_or_generate_tls_config;
 use crate::util::{systemd, write_new};
 
@@ -87,6 +100,9 @@ fn main() {
 			std::process::exit(-1);
 		},
 	};
+	// Computed up front, as fields of the config are moved out below.
+	let server_features = enabled_features(&config_file);
+	let redacted_config = config_file.redacted_entries();
 
 	let storage_dir: PathBuf = match config_file.storage_dir_path {
 		None => {
@@ -105,13 +121,17 @@ fn main() {
 		Some(configured_path) => PathBuf::from(configured_path),
 	};
 
-	let network_dir: PathBuf = match config_file.network {
-		Network::Bitcoin => storage_dir.join("bitcoin"),
-		Network::Testnet => storage_dir.join("testnet"),
-		Network::Testnet4 => storage_dir.join("testnet4"),
-		Network::Signet => storage_dir.join("signet"),
-		Network::Regtest => storage_dir.join("regtest"),
+	let network_dir = network_subdir(&storage_dir, config_file.network);
+	let history_dir = match &config_file.history_dir_path {
+		Some(history_dir_path) => network_subdir(Path::new(history_dir_path), config_file.network),
+		None => network_dir.clone(),
 	};
+	for (name, dir) in [("storage", &network_dir), ("history", &history_dir)] {
+		if let Err(e) = check_dir_writable(dir) {
+			eprintln!("The {} directory {} is not writable: {}", name, dir.display(), e);
+			std::process::exit(-1);
+		}
+	}
 
 	let log_file_path = if config_file.log_to_file {
 		let path = config_file.log_file_path.map(PathBuf::from).unwrap_or_else(|| {
@@ -120,7 +140,7 @@ fn main() {
 			default_log_path
 		});
 
-		if path == storage_dir || path == network_dir {
+		if path == storage_dir || path == network_dir || path == history_dir {
 			eprintln!("Log file path cannot be the same as storage directory path.");
 			std::process::exit(-1);
 		}
@@ -143,6 +163,15 @@ fn main() {
 		},
 	};
 
+	if let Some(store_path) = stale_history_store(&network_dir, &history_dir) {
+		warn!(
+			"Found payment history in {}, which is not migrated to history_dir_path {}. Its \
+			 payments, forwards and audit entries are no longer listed.",
+			store_path.display(),
+			history_dir.display()
+		);
+	}
+
 	let api_key = match load_or_generate_api_key(&network_dir) {
 		Ok(key) => key,
 		Err(e) => {
@@ -150,6 +179,18 @@ fn main() {
 			std::process::exit(-1);
 		},
 	};
+	let admin_api_key =
+		ApiKey { name: ADMIN_API_KEY_NAME.to_string(), key: api_key, scope: ApiKeyScope::Admin };
+	let mut api_keys = vec![admin_api_key];
+	api_keys.extend(config_file.api_keys.iter().cloned());
+	let payment_history_retention = config_file.payment_history_retention;
+	let min_cltv_expiry_delta = config_file.min_cltv_expiry_delta;
+	let notify_expired_invoices = config_file.notify_expired_invoices;
+	let payment_log_detail = config_file.payment_log_detail;
+	let rate_limits = Arc::new(RateLimits::new(
+		config_file.read_requests_per_minute,
+		config_file.send_requests_per_minute,
+	));
 
 	ldk_node_config.storage_dir_path = network_dir.to_str().unwrap().to_string();
 	ldk_node_config.listening_addresses = config_file.listening_addrs;
@@ -183,19 +224,21 @@ fn main() {
 				wallet_rescan_from_height,
 			);
 		},
-		ChainSource::Electrum { server_url, force_wallet_full_scan } => {
-			let sync_config = force_wallet_full_scan.then(|| ElectrumSyncConfig {
-				force_wallet_full_scan: true,
+		ChainSource::Electrum { server_url, force_wallet_full_scan, chain_poll_interval_secs } => {
+			let sync_config = ElectrumSyncConfig {
+				background_sync_config: Some(background_sync_config(chain_poll_interval_secs)),
+				force_wallet_full_scan,
 				..ElectrumSyncConfig::default()
-			});
-			builder.set_chain_source_electrum(server_url, sync_config);
+			};
+			builder.set_chain_source_electrum(server_url, Some(sync_config));
 		},
-		ChainSource::Esplora { server_url, force_wallet_full_scan } => {
-			let sync_config = force_wallet_full_scan.then(|| EsploraSyncConfig {
-				force_wallet_full_scan: true,
+		ChainSource::Esplora { server_url, force_wallet_full_scan, chain_poll_interval_secs } => {
+			let sync_config = EsploraSyncConfig {
+				background_sync_config: Some(background_sync_config(chain_poll_interval_secs)),
+				force_wallet_full_scan,
 				..EsploraSyncConfig::default()
-			});
-			builder.set_chain_source_esplora(server_url, sync_config);
+			};
+			builder.set_chain_source_esplora(server_url, Some(sync_config));
 		},
 	}
 
@@ -249,13 +292,21 @@ fn main() {
 
 	builder.set_runtime(runtime.handle().clone());
 
-	let node_entropy = match crate::util::entropy::load_or_generate_node_entropy(&storage_dir) {
-		Ok(entropy) => entropy,
+	let mnemonic = match crate::util::entropy::load_or_generate_mnemonic(&storage_dir) {
+		Ok(mnemonic) => mnemonic,
 		Err(e) => {
 			error!("Failed to load or generate node entropy: {e}");
 			std::process::exit(-1);
 		},
 	};
+	let wallet_descriptor = match wallet_descriptors(&mnemonic.to_seed(""), config_file.network) {
+		Ok(wallet_descriptor) => wallet_descriptor,
+		Err(e) => {
+			error!("Failed to derive on-chain wallet descriptors: {e}");
+			std::process::exit(-1);
+		},
+	};
+	let node_entropy = NodeEntropy::from_bip39_mnemonic(mnemonic, None);
 
 	let node = match builder.build(node_entropy) {
 		Ok(node) => Arc::new(node),
@@ -266,10 +317,10 @@ fn main() {
 	};
 
 	let paginated_store: Arc<dyn PaginatedKVStore> =
-		Arc::new(match SqliteStore::new(network_dir.clone(), None, None) {
+		Arc::new(match SqliteStore::new(history_dir.clone(), None, None) {
 			Ok(store) => store,
 			Err(e) => {
-				error!("Failed to create SqliteStore: {e:?}");
+				error!("Failed to create SqliteStore in {}: {e:?}", history_dir.display());
 				std::process::exit(-1);
 			},
 		});
@@ -286,6 +337,10 @@ fn main() {
 		},
 	}
 
+	if let Some(min_cltv_expiry_delta) = min_cltv_expiry_delta {
+		enforce_min_cltv_expiry_delta(&node, min_cltv_expiry_delta);
+	}
+
 	let addrs = node
 		.config()
 		.announcement_addresses
@@ -343,6 +398,69 @@ fn main() {
 			None
 		};
 
+		if let Some(retention) = payment_history_retention {
+			let pruning_store = Arc::clone(&paginated_store);
+			let pruning_event_sender = event_sender.clone();
+			let mut interval = tokio::time::interval(PAYMENT_PRUNING_INTERVAL);
+			runtime.spawn(async move {
+				loop {
+					interval.tick().await;
+					if let Err(e) = prune_payments(
+						pruning_store.as_ref(),
+						&retention,
+						current_timestamp(),
+						&pruning_event_sender,
+					) {
+						error!("Failed to prune payment history: {e}");
+					}
+				}
+			});
+		}
+
+		if config_file.deactivate_expired_offers {
+			let offers_store = Arc::clone(&paginated_store);
+			let offers_event_sender = event_sender.clone();
+			let mut interval = tokio::time::interval(OFFER_EXPIRY_INTERVAL);
+			runtime.spawn(async move {
+				loop {
+					interval.tick().await;
+					if let Err(e) = deactivate_expired_offers(
+						offers_store.as_ref(),
+						current_timestamp(),
+						&offers_event_sender,
+					) {
+						error!("Failed to deactivate expired offers: {e}");
+					}
+				}
+			});
+		}
+
+		if notify_expired_invoices {
+			let invoices_node = Arc::clone(&node);
+			let invoices_store = Arc::clone(&paginated_store);
+			let invoices_event_sender = event_sender.clone();
+			let mut interval = tokio::time::interval(INVOICE_EXPIRY_INTERVAL);
+			runtime.spawn(async move {
+				let is_paid = |payment_hash: &PaymentHash| {
+					invoices_node
+						.payment(&PaymentId(payment_hash.0))
+						.is_some_and(|payment| payment.status == PaymentStatus::Succeeded)
+				};
+				loop {
+					interval.tick().await;
+					if let Err(e) = expire_unpaid_invoices(
+						invoices_store.as_ref(),
+						current_timestamp(),
+						is_paid,
+						&invoices_event_sender,
+						payment_log_detail,
+					) {
+						error!("Failed to check for expired invoices: {e}");
+					}
+				}
+			});
+		}
+
 		let metrics_auth_header = if let (Some(username), Some(password)) =
 			(config_file.metrics_username.as_ref(), config_file.metrics_password.as_ref())
 		{
@@ -352,6 +470,31 @@ fn main() {
 			None
 		};
 
+		let service_config = Arc::new(ServiceConfig {
+			api_keys,
+			max_outbound_payment_msat: config_file.max_outbound_payment_msat,
+			max_description_length: config_file.max_description_length,
+			large_payment_warning_msat: config_file.large_payment_warning_msat,
+			max_skimmed_fee_msat: config_file
+				.lsps2_client_config
+				.as_ref()
+				.and_then(|config| config.max_skimmed_fee_msat),
+			announce_channels_by_default: config_file.announce_channels_by_default,
+			min_channel_size_sat: config_file.min_channel_size_sat,
+			reject_reserve_shortfall: config_file.reject_reserve_shortfall,
+			min_cltv_expiry_delta,
+			max_offer_quantity: config_file.max_offer_quantity,
+			notify_expired_invoices,
+			lnurl_base_url: config_file.lnurl_base_url.clone(),
+			payment_log_detail,
+			payment_history_retention,
+			max_request_body_bytes: config_file.max_request_body_bytes,
+			server_features,
+			redacted_config,
+			wallet_descriptor,
+			metrics_auth_header,
+		});
+
 		let grpc_listener = TcpListener::bind(config_file.grpc_service_addr)
 			.await
 			.expect("Failed to bind listening port");
@@ -418,6 +561,11 @@ fn main() {
 								let channel_id_hex = channel_id.0.to_lower_hex_string();
 								ready_channel_ids.insert(channel_id);
 
+								// Inbound channels start out with the default channel config.
+								if let Some(min_delta) = min_cltv_expiry_delta {
+									enforce_min_cltv_expiry_delta(&event_node, min_delta);
+								}
+
 								send_channel_state_event(
 									event_envelope::Event::ChannelStateChanged(events::ChannelStateChanged {
 										channel_id: channel_id_hex,
@@ -488,11 +636,14 @@ fn main() {
 							custom_records,
 							..
 						} => {
+							let payment_id = payment_id.expect("PaymentId expected for ldk-server >=0.1");
 							info!(
-								"PAYMENT_RECEIVED: with id {:?}, hash {}, amount_msat {}",
-								payment_id, payment_hash, amount_msat
+								"{}",
+								PaymentLogLine::new(payment_log_detail, "PAYMENT_RECEIVED")
+									.metadata("id", payment_id)
+									.metadata("hash", payment_hash)
+									.amount("amount_msat", amount_msat)
 							);
-							let payment_id = payment_id.expect("PaymentId expected for ldk-server >=0.1");
 
 							let proto_custom_records: Vec<_> = custom_records
 								.iter()
@@ -518,6 +669,13 @@ fn main() {
 						},
 						Event::PaymentSuccessful {payment_id, ..} => {
 							let payment_id = payment_id.expect("PaymentId expected for ldk-server >=0.1");
+							if let Some(payment) = event_node.payment(&payment_id) {
+								info!(
+									"{}",
+									PaymentLogLine::new(payment_log_detail, "PAYMENT_SUCCESSFUL")
+										.payment(&payment)
+								);
+							}
 
 							send_event_and_upsert_payment(&payment_id,
 								|payment_ref| event_envelope::Event::PaymentSuccessful(events::PaymentSuccessful {
@@ -534,6 +692,13 @@ fn main() {
 						},
 						Event::PaymentFailed {payment_id, ..} => {
 							let payment_id = payment_id.expect("PaymentId expected for ldk-server >=0.1");
+							if let Some(payment) = event_node.payment(&payment_id) {
+								info!(
+									"{}",
+									PaymentLogLine::new(payment_log_detail, "PAYMENT_FAILED")
+										.payment(&payment)
+								);
+							}
 
 							send_event_and_upsert_payment(&payment_id,
 								|payment_ref| event_envelope::Event::PaymentFailed(events::PaymentFailed {
@@ -569,11 +734,18 @@ fn main() {
 							outbound_amount_forwarded_msat
 						} => {
 							info!(
-								"PAYMENT_FORWARDED: outbound_amount_forwarded_msat {}, total_fee_earned_msat: {}, inbound HTLCs: {}, outbound HTLCs: {}",
-								outbound_amount_forwarded_msat.unwrap_or(0),
-								total_fee_earned_msat.unwrap_or(0),
-								prev_htlcs.len(),
-								next_htlcs.len(),
+								"{}",
+								PaymentLogLine::new(payment_log_detail, "PAYMENT_FORWARDED")
+									.metadata("inbound HTLCs", prev_htlcs.len())
+									.metadata("outbound HTLCs", next_htlcs.len())
+									.amount(
+										"outbound_amount_forwarded_msat",
+										outbound_amount_forwarded_msat.unwrap_or(0),
+									)
+									.amount(
+										"total_fee_earned_msat",
+										total_fee_earned_msat.unwrap_or(0),
+									)
 							);
 
 							let prev_htlcs = prev_htlcs
@@ -593,19 +765,22 @@ fn main() {
 								})
 								.collect();
 
-							let forwarded_payment = forwarded_payment_to_proto(
-								prev_htlcs,
-								next_htlcs,
-								total_fee_earned_msat,
-								skimmed_fee_msat,
-								claim_from_onchain_tx,
-								outbound_amount_forwarded_msat
-							);
+							let forwarded_payment_creation_time = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time must be > 1970").as_secs();
 
 							let mut forwarded_payment_id = [0u8; 32];
 							getrandom::getrandom(&mut forwarded_payment_id).expect("Failed to generate random bytes");
+							let forwarded_payment_id = forwarded_payment_id.to_lower_hex_string();
 
-							let forwarded_payment_creation_time = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time must be > 1970").as_secs() as i64;
+							let forwarded_payment = ForwardedPayment {
+								id: forwarded_payment_id.clone(),
+								total_fee_earned_msat,
+								skimmed_fee_msat,
+								claim_from_onchain_tx,
+								outbound_amount_forwarded_msat,
+								prev_htlcs,
+								next_htlcs,
+								forwarded_at: Some(forwarded_payment_creation_time),
+							};
 
 							if let Err(e) = event_sender.send(EventEnvelope {
 								event: Some(event_envelope::Event::PaymentForwarded(events::PaymentForwarded {
@@ -616,8 +791,8 @@ fn main() {
 							}
 
 							match paginated_store.write(FORWARDED_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
-								&forwarded_payment_id.to_lower_hex_string(),
-								forwarded_payment_creation_time,
+								&forwarded_payment_id,
+								forwarded_payment_creation_time as i64,
 								&forwarded_payment.encode_to_vec(),
 							) {
 								Ok(_) => {
@@ -643,9 +818,9 @@ fn main() {
 							let node_service = NodeService::new(
 								Arc::clone(&node),
 								Arc::clone(&paginated_store),
-								api_key.clone(),
+								Arc::clone(&service_config),
+								Arc::clone(&rate_limits),
 								metrics.clone(),
-								metrics_auth_header.clone(),
 								event_sender.clone(),
 								shutdown_rx.clone(),
 							);
@@ -886,6 +1061,46 @@ fn upsert_payment_details(
 	}
 }
 
+/// Returns the background sync intervals, polling the chain source for new blocks at the given
+/// interval if set.
+fn background_sync_config(chain_poll_interval_secs: Option<u64>) -> BackgroundSyncConfig {
+	let default_config = BackgroundSyncConfig::default();
+	match chain_poll_interval_secs {
+		Some(interval_secs) => BackgroundSyncConfig {
+			onchain_wallet_sync_interval_secs: interval_secs,
+			lightning_wallet_sync_interval_secs: interval_secs,
+			..default_config
+		},
+		None => default_config,
+	}
+}
+
+/// Returns the directory data for the given network is kept in below `dir`.
+fn network_subdir(dir: &Path, network: Network) -> PathBuf {
+	match network {
+		Network::Bitcoin => dir.join("bitcoin"),
+		Network::Testnet => dir.join("testnet"),
+		Network::Testnet4 => dir.join("testnet4"),
+		Network::Signet => dir.join("signet"),
+		Network::Regtest => dir.join("regtest"),
+	}
+}
+
+/// Checks that files can be created in `dir`, creating the directory if needed.
+fn check_dir_writable(dir: &Path) -> io::Result<()> {
+	fs::create_dir_all(dir)?;
+	let probe_path = dir.join(".ldk-server-write-check");
+	fs::write(&probe_path, [])?;
+	fs::remove_file(&probe_path)
+}
+
+/// Returns the path of a history store left behind in `network_dir` after the history was moved
+/// to a separate `history_dir`. Its records are not migrated, so they are no longer served.
+fn stale_history_store(network_dir: &Path, history_dir: &Path) -> Option<PathBuf> {
+	let store_path = network_dir.join(DEFAULT_SQLITE_DB_FILE_NAME);
+	(history_dir != network_dir && store_path.exists()).then_some(store_path)
+}
+
 /// Loads the API key from a file, or generates a new one if it doesn't exist.
 /// The API key file is stored with 0400 permissions (read-only for owner).
 fn load_or_generate_api_key(storage_dir: &Path) -> std::io::Result<String> {
@@ -926,6 +1141,7 @@ mod tests {
 	use ldk_server_grpc::events::channel_state_change_reason::Details;
 
 	use super::*;
+	use crate::io::persist::sqlite_store::tests::random_storage_path;
 
 	#[test]
 	fn test_is_channel_open_failure_classification() {
@@ -1036,4 +1252,44 @@ mod tests {
 		assert_eq!(proto.custom_records[1].type_num, 65538);
 		assert!(proto.custom_records[1].value.to_vec().is_empty());
 	}
+
+	#[test]
+	fn history_records_land_in_the_history_dir() {
+		let root = random_storage_path();
+		let network_dir = network_subdir(&root.join("node"), Network::Regtest);
+		let history_dir = network_subdir(&root.join("history"), Network::Regtest);
+		assert_eq!(history_dir, root.join("history").join("regtest"));
+		check_dir_writable(&history_dir).unwrap();
+		assert_eq!(fs::read_dir(&history_dir).unwrap().count(), 0);
+
+		let store = SqliteStore::new(history_dir.clone(), None, None).unwrap();
+		store
+			.write(
+				PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+				PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+				"payment",
+				0,
+				&[1, 2, 3],
+			)
+			.unwrap();
+
+		assert!(history_dir.join(DEFAULT_SQLITE_DB_FILE_NAME).exists());
+		assert!(!network_dir.join(DEFAULT_SQLITE_DB_FILE_NAME).exists());
+		assert_eq!(stale_history_store(&network_dir, &history_dir), None);
+
+		// A history store kept in the node's directory before is reported once it is moved.
+		SqliteStore::new(network_dir.clone(), None, None).unwrap();
+		assert_eq!(
+			stale_history_store(&network_dir, &history_dir),
+			Some(network_dir.join(DEFAULT_SQLITE_DB_FILE_NAME))
+		);
+		assert_eq!(stale_history_store(&network_dir, &network_dir), None);
+
+		// A directory cannot be created below a file.
+		let file_path = root.join("file");
+		fs::write(&file_path, []).unwrap();
+		assert!(check_dir_writable(&file_path.join("regtest")).is_err());
+
+		fs::remove_dir_all(root).unwrap();
+	}
 }
@@ -7,35 +7,45 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use http_body_util::{BodyExt, Limited};
+use hex::DisplayHex;
+use http_body_util::{BodyExt, LengthLimitError, Limited};
 use hyper::body::Incoming;
 use hyper::service::Service;
-use hyper::{HeaderMap, Request, Response};
+use hyper::{HeaderMap, HeaderValue, Request, Response};
 use ldk_node::bitcoin::hashes::hmac::{Hmac, HmacEngine};
 use ldk_node::bitcoin::hashes::{sha256, Hash, HashEngine};
 use ldk_node::Node;
+use ldk_server_grpc::api::GetWalletDescriptorResponse;
 use ldk_server_grpc::endpoints::{
-	BOLT11_CLAIM_FOR_HASH_PATH, BOLT11_FAIL_FOR_HASH_PATH, BOLT11_RECEIVE_FOR_HASH_PATH,
-	BOLT11_RECEIVE_PATH, BOLT11_RECEIVE_VARIABLE_AMOUNT_VIA_JIT_CHANNEL_PATH,
-	BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH, BOLT11_SEND_PATH, BOLT12_RECEIVE_PATH, BOLT12_SEND_PATH,
-	CLOSE_CHANNEL_PATH, CONNECT_PEER_PATH, DECODE_INVOICE_PATH, DECODE_OFFER_PATH,
-	DISCONNECT_PEER_PATH, EXPORT_PATHFINDING_SCORES_PATH, FORCE_CLOSE_CHANNEL_PATH,
-	GET_BALANCES_PATH, GET_METRICS_PATH, GET_NODE_INFO_PATH, GET_PAYMENT_DETAILS_PATH,
-	GRAPH_GET_CHANNEL_PATH, GRAPH_GET_NODE_PATH, GRAPH_LIST_CHANNELS_PATH, GRAPH_LIST_NODES_PATH,
-	LIST_CHANNELS_PATH, LIST_FORWARDED_PAYMENTS_PATH, LIST_PAYMENTS_PATH, LIST_PEERS_PATH,
-	ONCHAIN_RECEIVE_PATH, ONCHAIN_SEND_PATH, OPEN_CHANNEL_PATH, SIGN_MESSAGE_PATH, SPLICE_IN_PATH,
+	API_VERSION, API_VERSION_HEADER, BOLT11_CLAIM_FOR_HASH_PATH, BOLT11_FAIL_FOR_HASH_PATH,
+	BOLT11_RECEIVE_FOR_HASH_PATH, BOLT11_RECEIVE_PATH,
+	BOLT11_RECEIVE_VARIABLE_AMOUNT_VIA_JIT_CHANNEL_PATH, BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH,
+	BOLT11_SEND_PATH, BOLT12_RECEIVE_PATH, BOLT12_SEND_PATH, CLOSE_CHANNEL_PATH, CONNECT_PEER_PATH,
+	CREATE_LNURL_WITHDRAW_PATH, DECODE_INVOICE_PATH, DECODE_OFFER_PATH, DISCONNECT_PEER_PATH,
+	EXPORT_PATHFINDING_SCORES_PATH, FORCE_CLOSE_CHANNEL_PATH, GET_API_VERSION_PATH,
+	GET_BALANCES_PATH, GET_CHANNEL_ANNOUNCEMENT_STATUS_PATH, GET_CHANNEL_ROUTING_STATS_PATH,
+	GET_CLAIMABLE_BALANCES_PATH, GET_CONFIG_PATH, GET_FORWARDED_PAYMENT_PATH, GET_METRICS_PATH,
+	GET_NODE_INFO_PATH, GET_PAYMENT_DETAILS_PATH, GET_PEER_DETAILS_PATH, GET_SNAPSHOT_PATH,
+	GET_WALLET_DESCRIPTOR_PATH, GRAPH_GET_CHANNEL_PATH, GRAPH_GET_NODE_PATH,
+	GRAPH_LIST_CHANNELS_PATH, GRAPH_LIST_NODES_PATH, IS_OWN_INVOICE_PATH, LIST_AUDIT_LOG_PATH,
+	LIST_CHANNELS_PATH, LIST_FORWARDED_PAYMENTS_PATH, LIST_OFFERS_PATH, LIST_PAYMENTS_PATH,
+	LIST_PEERS_PATH, ONCHAIN_RECEIVE_PATH, ONCHAIN_SEND_PATH, OPEN_CHANNEL_PATH,
+	PRUNE_PAYMENTS_PATH, RUN_PREFLIGHT_CHECKS_PATH, SIGN_MESSAGE_PATH, SPLICE_IN_PATH,
 	SPLICE_OUT_PATH, SPONTANEOUS_SEND_PATH, SUBSCRIBE_EVENTS_PATH, UNIFIED_SEND_PATH,
-	UPDATE_CHANNEL_CONFIG_PATH, VERIFY_SIGNATURE_PATH,
+	UPDATE_CHANNEL_CONFIG_PATH, VERIFY_SIGNATURE_PATH, WHO_AM_I_PATH,
 };
 use ldk_server_grpc::events::EventEnvelope;
 use ldk_server_grpc::grpc::{
 	decode_grpc_body, encode_grpc_frame, grpc_error_response, grpc_response, parse_grpc_timeout,
 	validate_grpc_request, GrpcBody, GrpcStatus, GRPC_STATUS_DEADLINE_EXCEEDED,
 	GRPC_STATUS_FAILED_PRECONDITION, GRPC_STATUS_INTERNAL, GRPC_STATUS_INVALID_ARGUMENT,
+	GRPC_STATUS_NOT_FOUND, GRPC_STATUS_PERMISSION_DENIED, GRPC_STATUS_RESOURCE_EXHAUSTED,
 	GRPC_STATUS_UNAUTHENTICATED, GRPC_STATUS_UNAVAILABLE, GRPC_STATUS_UNIMPLEMENTED,
 };
 use prost::Message;
@@ -54,59 +64,80 @@ use crate::api::bolt12_receive::handle_bolt12_receive_request;
 use crate::api::bolt12_send::handle_bolt12_send_request;
 use crate::api::close_channel::{handle_close_channel_request, handle_force_close_channel_request};
 use crate::api::connect_peer::handle_connect_peer;
+use crate::api::create_lnurl_withdraw::handle_create_lnurl_withdraw_request;
 use crate::api::decode_invoice::handle_decode_invoice_request;
 use crate::api::decode_offer::handle_decode_offer_request;
 use crate::api::disconnect_peer::handle_disconnect_peer;
 use crate::api::error::{LdkServerError, LdkServerErrorCode};
 use crate::api::export_pathfinding_scores::handle_export_pathfinding_scores_request;
+use crate::api::get_api_version::handle_get_api_version_request;
 use crate::api::get_balances::handle_get_balances_request;
+use crate::api::get_channel_announcement_status::handle_get_channel_announcement_status_request;
+use crate::api::get_channel_routing_stats::handle_get_channel_routing_stats_request;
+use crate::api::get_claimable_balances::handle_get_claimable_balances_request;
+use crate::api::get_config::handle_get_config_request;
+use crate::api::get_forwarded_payment::handle_get_forwarded_payment_request;
 use crate::api::get_node_info::handle_get_node_info_request;
 use crate::api::get_payment_details::handle_get_payment_details_request;
+use crate::api::get_peer_details::handle_get_peer_details_request;
+use crate::api::get_snapshot::handle_get_snapshot_request;
+use crate::api::get_wallet_descriptor::handle_get_wallet_descriptor_request;
 use crate::api::graph_get_channel::handle_graph_get_channel_request;
 use crate::api::graph_get_node::handle_graph_get_node_request;
 use crate::api::graph_list_channels::handle_graph_list_channels_request;
 use crate::api::graph_list_nodes::handle_graph_list_nodes_request;
+use crate::api::is_own_invoice::handle_is_own_invoice_request;
+use crate::api::list_audit_log::handle_list_audit_log_request;
 use crate::api::list_channels::handle_list_channels_request;
 use crate::api::list_forwarded_payments::handle_list_forwarded_payments_request;
+use crate::api::list_offers::handle_list_offers_request;
 use crate::api::list_payments::handle_list_payments_request;
 use crate::api::list_peers::handle_list_peers_request;
 use crate::api::onchain_receive::handle_onchain_receive_request;
 use crate::api::onchain_send::handle_onchain_send_request;
 use crate::api::open_channel::handle_open_channel;
+use crate::api::prune_payments::handle_prune_payments_request;
+use crate::api::run_preflight_checks::handle_run_preflight_checks_request;
 use crate::api::sign_message::handle_sign_message_request;
 use crate::api::splice_channel::{handle_splice_in_request, handle_splice_out_request};
 use crate::api::spontaneous_send::handle_spontaneous_send_request;
 use crate::api::unified_send::handle_unified_send_request;
 use crate::api::update_channel_config::handle_update_channel_config_request;
 use crate::api::verify_signature::handle_verify_signature_request;
+use crate::api::who_am_i::handle_who_am_i_request;
 use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::util::audit::{current_timestamp, record_audit_entry, AuditContext, AuditedRequest};
+use crate::util::auth::{authorize, ApiKey};
+use crate::util::config::PaymentHistoryRetention;
+use crate::util::lnurl::{handle_lnurl_withdraw_request, LNURL_WITHDRAW_PATH_PREFIX};
 use crate::util::metrics::Metrics;
+use crate::util::payment_limit::outbound_payment_limit_msat;
+use crate::util::payment_log::PaymentLogDetail;
+use crate::util::rate_limit::RateLimits;
 
 /// gRPC path prefix for the LightningNode service.
 const GRPC_SERVICE_PREFIX: &str = "/api.LightningNode/";
 
-// Maximum request body size: 10 MB
-const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
-
 #[derive(Clone)]
 pub(crate) struct NodeService {
 	context: Arc<Context>,
-	api_key: String,
+	rate_limits: Arc<RateLimits>,
 	metrics: Option<Arc<Metrics>>,
-	metrics_auth_header: Option<String>,
 	event_sender: broadcast::Sender<EventEnvelope>,
 	shutdown_rx: tokio::sync::watch::Receiver<bool>,
 }
 
 impl NodeService {
 	pub(crate) fn new(
-		node: Arc<Node>, paginated_kv_store: Arc<dyn PaginatedKVStore>, api_key: String,
-		metrics: Option<Arc<Metrics>>, metrics_auth_header: Option<String>,
+		node: Arc<Node>, paginated_kv_store: Arc<dyn PaginatedKVStore>, config: Arc<ServiceConfig>,
+		rate_limits: Arc<RateLimits>, metrics: Option<Arc<Metrics>>,
 		event_sender: broadcast::Sender<EventEnvelope>,
 		shutdown_rx: tokio::sync::watch::Receiver<bool>,
 	) -> Self {
-		let context = Arc::new(Context { node, paginated_kv_store });
-		Self { context, api_key, metrics, metrics_auth_header, event_sender, shutdown_rx }
+		let max_outbound_payment_msat = config.max_outbound_payment_msat;
+		let context =
+			Arc::new(Context { node, paginated_kv_store, config, max_outbound_payment_msat });
+		Self { context, rate_limits, metrics, event_sender, shutdown_rx }
 	}
 }
 
@@ -120,9 +151,12 @@ fn compute_auth_hmac(api_key: &str, timestamp: u64, body: &[u8]) -> Hmac<sha256:
 	Hmac::<sha256::Hash>::from_engine(hmac_engine)
 }
 
-/// Validates HMAC authentication from request headers.
+/// Validates HMAC authentication from request headers, returning the API key the request was
+/// signed with.
 /// The signature covers the timestamp and raw gRPC request body bytes.
-fn validate_auth<B>(req: &Request<B>, api_key: &str, body: &[u8]) -> Result<(), LdkServerError> {
+fn validate_auth<'a, B>(
+	req: &Request<B>, api_keys: &'a [ApiKey], body: &[u8],
+) -> Result<&'a ApiKey, LdkServerError> {
 	let auth_err = |msg: &str| LdkServerError::new(LdkServerErrorCode::AuthError, msg.to_string());
 
 	let auth_header = req
@@ -148,36 +182,118 @@ fn validate_auth<B>(req: &Request<B>, api_key: &str, body: &[u8]) -> Result<(),
 		return Err(auth_err("Request timestamp expired"));
 	}
 
-	let expected_hmac = compute_auth_hmac(api_key, timestamp, body);
-
 	let provided_hmac = provided_hmac_hex
 		.parse::<Hmac<sha256::Hash>>()
 		.map_err(|_| auth_err("Invalid HMAC in x-auth"))?;
 
-	if expected_hmac != provided_hmac {
-		return Err(auth_err("Invalid credentials"));
+	api_keys
+		.iter()
+		.find(|api_key| compute_auth_hmac(&api_key.key, timestamp, body) == provided_hmac)
+		.ok_or_else(|| auth_err("Invalid credentials"))
+}
+
+const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+const MAX_CORRELATION_ID_LEN: usize = 64;
+
+/// Returns the correlation id provided by the client, or a freshly generated one if none (or an
+/// invalid one) was provided.
+fn correlation_id(headers: &HeaderMap) -> String {
+	let provided = headers.get(CORRELATION_ID_HEADER).and_then(|v| v.to_str().ok()).filter(|id| {
+		!id.is_empty()
+			&& id.len() <= MAX_CORRELATION_ID_LEN
+			&& id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+	});
+	if let Some(id) = provided {
+		return id.to_string();
 	}
 
-	Ok(())
+	let mut bytes = [0u8; 16];
+	getrandom::getrandom(&mut bytes).expect("Failed to generate random bytes");
+	bytes.to_lower_hex_string()
 }
 
+/// The configuration the service and its handlers run with, built once at startup and shared by
+/// every connection.
+pub(crate) struct ServiceConfig {
+	pub(crate) api_keys: Vec<ApiKey>,
+	/// The maximum amount a single outbound payment may send, if configured. Requests made with an
+	/// `admin` scoped API key are exempt, see [`Context::max_outbound_payment_msat`].
+	pub(crate) max_outbound_payment_msat: Option<u64>,
+	/// The maximum length, in bytes, of invoice and offer descriptions, if configured.
+	pub(crate) max_description_length: Option<usize>,
+	/// The amount above which paying a zero-amount invoice must be confirmed, if configured.
+	pub(crate) large_payment_warning_msat: Option<u64>,
+	/// The most an LSP may skim off a payment received via a fixed-amount JIT channel invoice,
+	/// if configured.
+	pub(crate) max_skimmed_fee_msat: Option<u64>,
+	/// Whether channels are opened as public if the request does not say.
+	pub(crate) announce_channels_by_default: bool,
+	/// The smallest channel the node opens, if configured.
+	pub(crate) min_channel_size_sat: Option<u64>,
+	/// Whether opening channels whose estimated reserve is a large share of their amount is
+	/// rejected rather than only warned about.
+	pub(crate) reject_reserve_shortfall: bool,
+	/// The smallest CLTV expiry delta channels may be opened or updated with, if configured.
+	pub(crate) min_cltv_expiry_delta: Option<u16>,
+	/// The largest quantity an offer may support, if configured.
+	pub(crate) max_offer_quantity: Option<u64>,
+	/// Whether invoices created by the node are tracked to report them once they expire unpaid.
+	pub(crate) notify_expired_invoices: bool,
+	/// The public URL the LNURL-withdraw endpoints are reachable under, if configured.
+	pub(crate) lnurl_base_url: Option<String>,
+	/// How much detail about payments is written to the log.
+	pub(crate) payment_log_detail: PaymentLogDetail,
+	/// The limits payment history is pruned to, if configured.
+	pub(crate) payment_history_retention: Option<PaymentHistoryRetention>,
+	/// The largest request body accepted, in bytes.
+	pub(crate) max_request_body_bytes: usize,
+	/// The optional features enabled on this server, as reported by `GetApiVersion`.
+	pub(crate) server_features: Vec<String>,
+	/// The configuration with its secrets redacted, as returned by `GetConfig`.
+	pub(crate) redacted_config: BTreeMap<String, String>,
+	/// The watch-only descriptors of the on-chain wallet, as returned by `GetWalletDescriptor`.
+	pub(crate) wallet_descriptor: GetWalletDescriptorResponse,
+	/// The `authorization` header the metrics endpoint requires, if configured.
+	pub(crate) metrics_auth_header: Option<String>,
+}
+
+#[derive(Clone)]
 pub(crate) struct Context {
 	pub(crate) node: Arc<Node>,
 	pub(crate) paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	pub(crate) config: Arc<ServiceConfig>,
+	/// The maximum amount a single outbound payment may send, if limited for this request.
+	pub(crate) max_outbound_payment_msat: Option<u64>,
 }
 
+type ServiceFuture = Pin<Box<dyn Future<Output = Result<Response<GrpcBody>, hyper::Error>> + Send>>;
+
 impl Service<Request<Incoming>> for NodeService {
 	type Response = Response<GrpcBody>;
 	type Error = hyper::Error;
-	type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+	type Future = ServiceFuture;
 
 	fn call(&self, req: Request<Incoming>) -> Self::Future {
+		let future = self.route(req);
+		Box::pin(async move { future.await.map(with_api_version_header) })
+	}
+}
+
+/// Adds the [`API_VERSION_HEADER`] to a response, so that clients can detect the API version of
+/// the server from any response.
+fn with_api_version_header(mut response: Response<GrpcBody>) -> Response<GrpcBody> {
+	response.headers_mut().insert(API_VERSION_HEADER, HeaderValue::from(API_VERSION));
+	response
+}
+
+impl NodeService {
+	fn route(&self, req: Request<Incoming>) -> ServiceFuture {
 		// Handle metrics endpoint (plain HTTP GET, not gRPC)
 		if req.method() == hyper::Method::GET
 			&& req.uri().path().len() > 1
 			&& &req.uri().path()[1..] == GET_METRICS_PATH
 		{
-			if let Some(expected_header) = &self.metrics_auth_header {
+			if let Some(expected_header) = &self.context.config.metrics_auth_header {
 				let auth_header = req.headers().get("authorization").and_then(|h| h.to_str().ok());
 				if auth_header != Some(expected_header) {
 					return Box::pin(async move {
@@ -212,6 +328,32 @@ impl Service<Request<Incoming>> for NodeService {
 			}
 		}
 
+		// Handle LNURL-withdraw endpoints (plain HTTP GET requests of wallets, not gRPC)
+		if req.method() == hyper::Method::GET
+			&& req.uri().path().starts_with(LNURL_WITHDRAW_PATH_PREFIX)
+		{
+			let context = Arc::clone(&self.context);
+			let rate_limits = Arc::clone(&self.rate_limits);
+			let event_sender = self.event_sender.clone();
+			let path = req.uri().path().to_string();
+			let query = req.uri().query().unwrap_or_default().to_string();
+			let correlation_id = correlation_id(req.headers());
+			return Box::pin(async move {
+				let body = handle_lnurl_withdraw_request(
+					&context,
+					&rate_limits,
+					&path,
+					&query,
+					correlation_id,
+					&event_sender,
+				);
+				Ok(Response::builder()
+					.header("content-type", "application/json")
+					.body(GrpcBody::Plain { data: Some(bytes::Bytes::from(body)) })
+					.unwrap())
+			});
+		}
+
 		// Validate gRPC prerequisites
 		if let Err(status) = validate_grpc_request(&req) {
 			return Box::pin(async move { Ok(grpc_error_response(status)) });
@@ -251,25 +393,51 @@ impl Service<Request<Incoming>> for NodeService {
 		};
 
 		let is_streaming = method == SUBSCRIBE_EVENTS_PATH;
-		let api_key = self.api_key.clone();
+		let rate_limits = Arc::clone(&self.rate_limits);
 		let event_sender = self.event_sender.clone();
 		let shutdown_rx = self.shutdown_rx.clone();
 		let (request_parts, request_body) = req.into_parts();
-		let future: Self::Future = Box::pin(async move {
-			let content_length = match request_content_length(&request_parts.headers) {
-				Ok(content_length) => content_length,
-				Err(status) => return Ok(grpc_error_response(status)),
-			};
-			let body_bytes = match read_request_body(request_body, content_length).await {
+		let future: ServiceFuture = Box::pin(async move {
+			// The size limit is enforced before the body is authenticated or decoded.
+			let max_request_body_bytes = context.config.max_request_body_bytes;
+			let content_length =
+				match request_content_length(&request_parts.headers, max_request_body_bytes) {
+					Ok(content_length) => content_length,
+					Err(status) => return Ok(grpc_error_response(status)),
+				};
+			let body = read_request_body(request_body, content_length, max_request_body_bytes);
+			let body_bytes = match body.await {
 				Ok(bytes) => bytes,
 				Err(status) => return Ok(grpc_error_response(status)),
 			};
 
 			let auth_req = Request::from_parts(request_parts, ());
-			if let Err(e) = validate_auth(&auth_req, &api_key, &body_bytes) {
+			let api_key = match validate_auth(&auth_req, &context.config.api_keys, &body_bytes) {
+				Ok(api_key) => api_key.clone(),
+				Err(e) => {
+					let status = ldk_error_to_grpc_status(e);
+					return Ok(grpc_error_response(status));
+				},
+			};
+			if let Err(e) = authorize(&api_key, &method) {
 				let status = ldk_error_to_grpc_status(e);
 				return Ok(grpc_error_response(status));
 			}
+			if let Err(retry_after) = rate_limits.check(&api_key.name, &method, Instant::now()) {
+				return Ok(rate_limited_response(retry_after));
+			}
+			let max_outbound_payment_msat =
+				outbound_payment_limit_msat(context.config.max_outbound_payment_msat, &api_key);
+			let context = if max_outbound_payment_msat != context.max_outbound_payment_msat {
+				Arc::new(Context { max_outbound_payment_msat, ..Context::clone(&context) })
+			} else {
+				context
+			};
+			let audit_context = AuditContext {
+				operation: method.clone(),
+				actor: api_key.name.clone(),
+				correlation_id: correlation_id(auth_req.headers()),
+			};
 
 			match method.as_str() {
 				GET_NODE_INFO_PATH => {
@@ -282,7 +450,13 @@ impl Service<Request<Incoming>> for NodeService {
 					handle_grpc_unary(context, body_bytes, handle_onchain_receive_request).await
 				},
 				ONCHAIN_SEND_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_onchain_send_request).await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_onchain_send_request,
+					)
+					.await
 				},
 				BOLT11_RECEIVE_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_bolt11_receive_request).await
@@ -292,12 +466,22 @@ impl Service<Request<Incoming>> for NodeService {
 						.await
 				},
 				BOLT11_CLAIM_FOR_HASH_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_bolt11_claim_for_hash_request)
-						.await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_bolt11_claim_for_hash_request,
+					)
+					.await
 				},
 				BOLT11_FAIL_FOR_HASH_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_bolt11_fail_for_hash_request)
-						.await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_bolt11_fail_for_hash_request,
+					)
+					.await
 				},
 				BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH => {
 					handle_grpc_unary(
@@ -316,28 +500,70 @@ impl Service<Request<Incoming>> for NodeService {
 					.await
 				},
 				BOLT11_SEND_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_bolt11_send_request).await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_bolt11_send_request,
+					)
+					.await
 				},
 				BOLT12_RECEIVE_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_bolt12_receive_request).await
 				},
 				BOLT12_SEND_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_bolt12_send_request).await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_bolt12_send_request,
+					)
+					.await
 				},
 				OPEN_CHANNEL_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_open_channel).await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_open_channel,
+					)
+					.await
 				},
 				SPLICE_IN_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_splice_in_request).await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_splice_in_request,
+					)
+					.await
 				},
 				SPLICE_OUT_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_splice_out_request).await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_splice_out_request,
+					)
+					.await
 				},
 				CLOSE_CHANNEL_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_close_channel_request).await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_close_channel_request,
+					)
+					.await
 				},
 				FORCE_CLOSE_CHANNEL_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_force_close_channel_request).await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_force_close_channel_request,
+					)
+					.await
 				},
 				LIST_CHANNELS_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_list_channels_request).await
@@ -366,10 +592,22 @@ impl Service<Request<Incoming>> for NodeService {
 					handle_grpc_unary(context, body_bytes, handle_list_peers_request).await
 				},
 				SPONTANEOUS_SEND_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_spontaneous_send_request).await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_spontaneous_send_request,
+					)
+					.await
 				},
 				UNIFIED_SEND_PATH => {
-					handle_grpc_unary(context, body_bytes, handle_unified_send_request).await
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_unified_send_request,
+					)
+					.await
 				},
 				SIGN_MESSAGE_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_sign_message_request).await
@@ -399,6 +637,74 @@ impl Service<Request<Incoming>> for NodeService {
 				DECODE_OFFER_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_decode_offer_request).await
 				},
+				LIST_AUDIT_LOG_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_list_audit_log_request).await
+				},
+				WHO_AM_I_PATH => {
+					let handler = |_, request| handle_who_am_i_request(api_key.clone(), request);
+					handle_grpc_unary(context, body_bytes, handler).await
+				},
+				GET_CLAIMABLE_BALANCES_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_get_claimable_balances_request)
+						.await
+				},
+				GET_CHANNEL_ROUTING_STATS_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_get_channel_routing_stats_request)
+						.await
+				},
+				GET_API_VERSION_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_get_api_version_request).await
+				},
+				GET_CONFIG_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_get_config_request).await
+				},
+				LIST_OFFERS_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_list_offers_request).await
+				},
+				PRUNE_PAYMENTS_PATH => {
+					let handler = |context, request| {
+						handle_prune_payments_request(context, event_sender.clone(), request)
+					};
+					handle_grpc_unary(context, body_bytes, handler).await
+				},
+				GET_CHANNEL_ANNOUNCEMENT_STATUS_PATH => {
+					handle_grpc_unary(
+						context,
+						body_bytes,
+						handle_get_channel_announcement_status_request,
+					)
+					.await
+				},
+				GET_PEER_DETAILS_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_get_peer_details_request).await
+				},
+				IS_OWN_INVOICE_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_is_own_invoice_request).await
+				},
+				RUN_PREFLIGHT_CHECKS_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_run_preflight_checks_request)
+						.await
+				},
+				GET_FORWARDED_PAYMENT_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_get_forwarded_payment_request)
+						.await
+				},
+				GET_WALLET_DESCRIPTOR_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_get_wallet_descriptor_request)
+						.await
+				},
+				GET_SNAPSHOT_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_get_snapshot_request).await
+				},
+				CREATE_LNURL_WITHDRAW_PATH => {
+					handle_audited_grpc_unary(
+						context,
+						body_bytes,
+						audit_context,
+						handle_create_lnurl_withdraw_request,
+					)
+					.await
+				},
 				SUBSCRIBE_EVENTS_PATH => {
 					let mut shutdown_rx = shutdown_rx;
 					let mut rx = event_sender.subscribe();
@@ -476,14 +782,7 @@ async fn handle_grpc_unary<
 >(
 	context: Arc<Context>, body_bytes: bytes::Bytes, handler: F,
 ) -> Result<Response<GrpcBody>, hyper::Error> {
-	// Decode gRPC framing then protobuf
-	let req_msg = decode_grpc_body(&body_bytes)
-		.and_then(|b| {
-			T::decode(b)
-				.map_err(|_| GrpcStatus::new(GRPC_STATUS_INVALID_ARGUMENT, "Malformed request"))
-		})
-		.map_err(grpc_error_response);
-	let req_msg = match req_msg {
+	let req_msg = match decode_grpc_request::<T>(&body_bytes) {
 		Ok(m) => m,
 		Err(resp) => return Ok(resp),
 	};
@@ -494,31 +793,97 @@ async fn handle_grpc_unary<
 	tokio::task::yield_now().await;
 
 	// Call handler
-	match handler(context, req_msg).await {
+	Ok(encode_grpc_result(handler(context, req_msg).await))
+}
+
+/// Like [`handle_grpc_unary`], but additionally records the outcome of the request in the audit
+/// log.
+async fn handle_audited_grpc_unary<
+	T: Message + Default + AuditedRequest,
+	R: Message,
+	Fut: Future<Output = Result<R, LdkServerError>> + Send,
+	F: Fn(Arc<Context>, T) -> Fut + Send,
+>(
+	context: Arc<Context>, body_bytes: bytes::Bytes, audit_context: AuditContext, handler: F,
+) -> Result<Response<GrpcBody>, hyper::Error> {
+	let req_msg = match decode_grpc_request::<T>(&body_bytes) {
+		Ok(m) => m,
+		Err(resp) => return Ok(resp),
+	};
+
+	// See `handle_grpc_unary` for why we yield here.
+	tokio::task::yield_now().await;
+
+	let timestamp = current_timestamp();
+	let request_summary = req_msg.audit_summary();
+	let store = Arc::clone(&context.paginated_kv_store);
+	let result = handler(context, req_msg).await;
+	record_audit_entry(
+		&*store,
+		audit_context,
+		timestamp,
+		request_summary,
+		result.as_ref().map(|_| ()).map_err(|e| e.message.as_str()),
+	);
+
+	Ok(encode_grpc_result(result))
+}
+
+fn decode_grpc_request<T: Message + Default>(
+	body_bytes: &bytes::Bytes,
+) -> Result<T, Response<GrpcBody>> {
+	// Decode gRPC framing then protobuf
+	decode_grpc_body(body_bytes)
+		.and_then(|b| {
+			T::decode(b)
+				.map_err(|_| GrpcStatus::new(GRPC_STATUS_INVALID_ARGUMENT, "Malformed request"))
+		})
+		.map_err(grpc_error_response)
+}
+
+fn encode_grpc_result<R: Message>(result: Result<R, LdkServerError>) -> Response<GrpcBody> {
+	match result {
 		Ok(response) => {
 			let encoded = encode_grpc_frame(&response.encode_to_vec());
-			Ok(grpc_response(GrpcBody::Unary { data: Some(encoded), trailers_sent: false }))
+			grpc_response(GrpcBody::Unary { data: Some(encoded), trailers_sent: false })
 		},
-		Err(e) => Ok(grpc_error_response(ldk_error_to_grpc_status(e))),
+		Err(e) => grpc_error_response(ldk_error_to_grpc_status(e)),
 	}
 }
 
-fn request_content_length(headers: &HeaderMap) -> Result<Option<u64>, GrpcStatus> {
+/// The gRPC counterpart of an HTTP 429, including a `retry-after` header in whole seconds.
+fn rate_limited_response(retry_after: Duration) -> Response<GrpcBody> {
+	let retry_after_secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+	let mut response = grpc_error_response(GrpcStatus::new(
+		GRPC_STATUS_RESOURCE_EXHAUSTED,
+		format!("Rate limit exceeded, retry after {} seconds", retry_after_secs),
+	));
+	response.headers_mut().insert("retry-after", HeaderValue::from(retry_after_secs));
+	response
+}
+
+fn request_content_length(
+	headers: &HeaderMap, max_body_bytes: usize,
+) -> Result<Option<u64>, GrpcStatus> {
 	let Some(content_length) = headers.get("content-length") else {
 		return Ok(None);
 	};
 	let len = content_length.to_str().ok().and_then(|value| value.parse::<u64>().ok()).ok_or_else(
 		|| GrpcStatus::new(GRPC_STATUS_INVALID_ARGUMENT, "Invalid content-length header"),
 	)?;
-	if len > MAX_BODY_SIZE as u64 {
-		return Err(GrpcStatus::new(
-			GRPC_STATUS_INVALID_ARGUMENT,
-			"Request body too large or failed to read",
-		));
+	if len > max_body_bytes as u64 {
+		return Err(body_too_large(max_body_bytes));
 	}
 	Ok(Some(len))
 }
 
+fn body_too_large(max_body_bytes: usize) -> GrpcStatus {
+	GrpcStatus::new(
+		GRPC_STATUS_RESOURCE_EXHAUSTED,
+		format!("Request body exceeds the limit of {} bytes", max_body_bytes),
+	)
+}
+
 fn validate_request_body_len(
 	content_length: Option<u64>, actual_len: usize,
 ) -> Result<(), GrpcStatus> {
@@ -534,11 +899,12 @@ fn validate_request_body_len(
 }
 
 async fn read_request_body(
-	body: Incoming, content_length: Option<u64>,
+	body: Incoming, content_length: Option<u64>, max_body_bytes: usize,
 ) -> Result<bytes::Bytes, GrpcStatus> {
-	let limited_body = Limited::new(body, MAX_BODY_SIZE);
+	let limited_body = Limited::new(body, max_body_bytes);
 	let bytes = match limited_body.collect().await {
 		Ok(collected) => collected.to_bytes(),
+		Err(e) if e.is::<LengthLimitError>() => return Err(body_too_large(max_body_bytes)),
 		Err(_) => {
 			return Err(GrpcStatus::new(
 				GRPC_STATUS_INVALID_ARGUMENT,
@@ -557,6 +923,8 @@ pub(crate) fn ldk_error_to_grpc_status(e: LdkServerError) -> GrpcStatus {
 		LdkServerErrorCode::AuthError => GRPC_STATUS_UNAUTHENTICATED,
 		LdkServerErrorCode::LightningError => GRPC_STATUS_FAILED_PRECONDITION,
 		LdkServerErrorCode::InternalServerError => GRPC_STATUS_INTERNAL,
+		LdkServerErrorCode::PermissionDeniedError => GRPC_STATUS_PERMISSION_DENIED,
+		LdkServerErrorCode::NotFoundError => GRPC_STATUS_NOT_FOUND,
 	};
 	GrpcStatus { code, message: e.message }
 }
@@ -564,11 +932,17 @@ pub(crate) fn ldk_error_to_grpc_status(e: LdkServerError) -> GrpcStatus {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::api::get_api_version::api_version_response;
+	use crate::util::auth::ApiKeyScope;
 
 	fn compute_hmac(api_key: &str, timestamp: u64, body: &[u8]) -> String {
 		compute_auth_hmac(api_key, timestamp, body).to_string()
 	}
 
+	fn test_api_key(name: &str, key: &str) -> ApiKey {
+		ApiKey { name: name.to_string(), key: key.to_string(), scope: ApiKeyScope::Admin }
+	}
+
 	fn create_test_request(auth_header: Option<String>) -> Request<()> {
 		let mut builder =
 			Request::builder().method("POST").header("content-type", "application/grpc+proto");
@@ -580,21 +954,21 @@ mod tests {
 
 	#[test]
 	fn test_validate_auth_success() {
-		let api_key = "test_api_key";
+		let api_keys = [test_api_key("admin", "test_api_key")];
 		let body = b"test body";
 		let timestamp =
 			std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
-		let hmac = compute_hmac(api_key, timestamp, body);
+		let hmac = compute_hmac("test_api_key", timestamp, body);
 		let auth_header = format!("HMAC {timestamp}:{hmac}");
 		let req = create_test_request(Some(auth_header));
 
-		assert!(validate_auth(&req, api_key, body).is_ok());
+		assert_eq!(validate_auth(&req, &api_keys, body).unwrap().name, "admin");
 	}
 
 	#[test]
 	fn test_validate_auth_missing_header() {
 		let req = create_test_request(None);
-		let result = validate_auth(&req, "test_key", b"test body");
+		let result = validate_auth(&req, &[test_api_key("admin", "test_key")], b"test body");
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
 	}
@@ -602,7 +976,7 @@ mod tests {
 	#[test]
 	fn test_validate_auth_invalid_format() {
 		let req = create_test_request(Some("12345:deadbeef".to_string()));
-		let result = validate_auth(&req, "test_key", b"test body");
+		let result = validate_auth(&req, &[test_api_key("admin", "test_key")], b"test body");
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
 	}
@@ -614,7 +988,7 @@ mod tests {
 		let hmac = compute_hmac("wrong_key", timestamp, b"test body");
 		let req = create_test_request(Some(format!("HMAC {timestamp}:{hmac}")));
 
-		let result = validate_auth(&req, "test_api_key", b"test body");
+		let result = validate_auth(&req, &[test_api_key("admin", "test_api_key")], b"test body");
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
 	}
@@ -626,7 +1000,8 @@ mod tests {
 		let hmac = compute_hmac("test_api_key", timestamp, b"signed body");
 		let req = create_test_request(Some(format!("HMAC {timestamp}:{hmac}")));
 
-		let result = validate_auth(&req, "test_api_key", b"modified body");
+		let result =
+			validate_auth(&req, &[test_api_key("admin", "test_api_key")], b"modified body");
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
 	}
@@ -639,15 +1014,22 @@ mod tests {
 		let hmac = compute_hmac("test_api_key", timestamp, b"test body");
 		let req = create_test_request(Some(format!("HMAC {timestamp}:{hmac}")));
 
-		let result = validate_auth(&req, "test_api_key", b"test body");
+		let result = validate_auth(&req, &[test_api_key("admin", "test_api_key")], b"test body");
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
 	}
 
+	#[test]
+	fn test_rate_limited_response_rounds_retry_after_up() {
+		let response = rate_limited_response(Duration::from_millis(1_500));
+		assert_eq!(response.headers()["retry-after"], "2");
+		assert_eq!(response.headers()["grpc-status"], GRPC_STATUS_RESOURCE_EXHAUSTED.to_string());
+	}
+
 	#[test]
 	fn test_request_content_length_missing() {
 		let headers = HeaderMap::new();
-		assert_eq!(request_content_length(&headers).unwrap(), None);
+		assert_eq!(request_content_length(&headers, 1024).unwrap(), None);
 	}
 
 	#[test]
@@ -655,7 +1037,7 @@ mod tests {
 		let mut headers = HeaderMap::new();
 		headers.insert("content-length", "42".parse().unwrap());
 
-		assert_eq!(request_content_length(&headers).unwrap(), Some(42));
+		assert_eq!(request_content_length(&headers, 1024).unwrap(), Some(42));
 	}
 
 	#[test]
@@ -663,7 +1045,7 @@ mod tests {
 		let mut headers = HeaderMap::new();
 		headers.insert("content-length", "not-a-number".parse().unwrap());
 
-		let err = request_content_length(&headers).unwrap_err();
+		let err = request_content_length(&headers, 1024).unwrap_err();
 		assert_eq!(err.code, GRPC_STATUS_INVALID_ARGUMENT);
 		assert_eq!(err.message, "Invalid content-length header");
 	}
@@ -671,11 +1053,12 @@ mod tests {
 	#[test]
 	fn test_request_content_length_rejects_oversized_value() {
 		let mut headers = HeaderMap::new();
-		headers.insert("content-length", (MAX_BODY_SIZE as u64 + 1).to_string().parse().unwrap());
+		headers.insert("content-length", "1025".parse().unwrap());
 
-		let err = request_content_length(&headers).unwrap_err();
-		assert_eq!(err.code, GRPC_STATUS_INVALID_ARGUMENT);
-		assert_eq!(err.message, "Request body too large or failed to read");
+		let err = request_content_length(&headers, 1024).unwrap_err();
+		assert_eq!(err.code, GRPC_STATUS_RESOURCE_EXHAUSTED);
+		assert_eq!(err.message, "Request body exceeds the limit of 1024 bytes");
+		assert_eq!(request_content_length(&headers, 1025).unwrap(), Some(1025));
 	}
 
 	#[test]
@@ -694,4 +1077,46 @@ mod tests {
 		assert_eq!(err.code, GRPC_STATUS_INVALID_ARGUMENT);
 		assert_eq!(err.message, "Request body length does not match content-length");
 	}
+
+	#[test]
+	fn test_validate_auth_identifies_signing_key() {
+		let api_keys = [test_api_key("admin", "admin_key"), test_api_key("reader", "reader_key")];
+		let timestamp =
+			std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+		let hmac = compute_hmac("reader_key", timestamp, b"test body");
+		let req = create_test_request(Some(format!("HMAC {timestamp}:{hmac}")));
+
+		let api_key = validate_auth(&req, &api_keys, b"test body").unwrap();
+		assert_eq!(api_key.name, "reader");
+	}
+
+	#[test]
+	fn test_correlation_id_uses_provided_header() {
+		let mut headers = HeaderMap::new();
+		headers.insert(CORRELATION_ID_HEADER, "req-123_abc".parse().unwrap());
+		assert_eq!(correlation_id(&headers), "req-123_abc");
+	}
+
+	#[test]
+	fn test_correlation_id_replaces_invalid_header() {
+		let mut headers = HeaderMap::new();
+		headers.insert(CORRELATION_ID_HEADER, "not valid!".parse().unwrap());
+		let id = correlation_id(&headers);
+		assert_ne!(id, "not valid!");
+		assert_eq!(id.len(), 32);
+
+		let generated = correlation_id(&HeaderMap::new());
+		assert_eq!(generated.len(), 32);
+	}
+
+	#[test]
+	fn test_api_version_header_matches_endpoint() {
+		let status = GrpcStatus::new(GRPC_STATUS_UNIMPLEMENTED, "Unknown method: Test");
+		let response = with_api_version_header(grpc_error_response(status));
+		let header = response.headers().get(API_VERSION_HEADER).unwrap().to_str().unwrap();
+
+		let version = api_version_response(vec!["metrics".to_string()]);
+		assert_eq!(header, version.api_version.to_string());
+		assert_eq!(version.features, vec!["metrics".to_string()]);
+	}
 }
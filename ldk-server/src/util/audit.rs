@@ -0,0 +1,295 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hex::DisplayHex;
+use ldk_server_grpc::api::{
+	Bolt11ClaimForHashRequest, Bolt11FailForHashRequest, Bolt11SendRequest, Bolt12SendRequest,
+	CloseChannelRequest, CreateLnurlWithdrawRequest, ForceCloseChannelRequest, OnchainSendRequest,
+	OpenChannelRequest, SpliceInRequest, SpliceOutRequest, SpontaneousSendRequest,
+	UnifiedSendRequest,
+};
+use ldk_server_grpc::types::AuditLogEntry;
+use log::error;
+use prost::Message;
+
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	AUDIT_LOG_PERSISTENCE_PRIMARY_NAMESPACE, AUDIT_LOG_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+
+/// A request whose execution is recorded in the audit log.
+pub(crate) trait AuditedRequest {
+	/// Returns a summary of the request suitable for the audit log.
+	///
+	/// Implementations must only include amounts, destinations and identifiers, and never any
+	/// secrets such as preimages or free-form payer-provided data.
+	fn audit_summary(&self) -> BTreeMap<String, String>;
+}
+
+/// Identifies who issued an audited request and how it can be correlated with other records.
+pub(crate) struct AuditContext {
+	pub(crate) operation: String,
+	pub(crate) actor: String,
+	pub(crate) correlation_id: String,
+}
+
+/// Appends an entry for a completed operation to the audit log.
+///
+/// Failing to persist the entry is logged but does not fail the operation, as by the time the
+/// entry is written the operation has already been executed.
+pub(crate) fn record_audit_entry(
+	store: &dyn PaginatedKVStore, audit_context: AuditContext, timestamp: u64,
+	request_summary: BTreeMap<String, String>, result: Result<(), &str>,
+) {
+	let mut id_bytes = [0u8; 16];
+	if let Err(e) = getrandom::getrandom(&mut id_bytes) {
+		error!("Failed to generate audit log entry id: {e}");
+		return;
+	}
+	let id = id_bytes.to_lower_hex_string();
+
+	let entry = AuditLogEntry {
+		id: id.clone(),
+		timestamp,
+		operation: audit_context.operation,
+		actor: audit_context.actor,
+		correlation_id: audit_context.correlation_id,
+		request_summary,
+		success: result.is_ok(),
+		error: result.err().map(str::to_string),
+	};
+
+	if let Err(e) = store.write(
+		AUDIT_LOG_PERSISTENCE_PRIMARY_NAMESPACE,
+		AUDIT_LOG_PERSISTENCE_SECONDARY_NAMESPACE,
+		&id,
+		timestamp as i64,
+		&entry.encode_to_vec(),
+	) {
+		error!("Failed to write audit log entry for {}: {e}", entry.operation);
+	}
+}
+
+pub(crate) fn current_timestamp() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn summary<const N: usize>(fields: [(&str, Option<String>); N]) -> BTreeMap<String, String> {
+	fields.into_iter().filter_map(|(k, v)| v.map(|v| (k.to_string(), v))).collect()
+}
+
+impl AuditedRequest for OnchainSendRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		summary([
+			("address", Some(self.address.clone())),
+			("amount_sats", self.amount_sats.map(|a| a.to_string())),
+			("send_all", self.send_all.map(|s| s.to_string())),
+			("fee_rate_sat_per_vb", self.fee_rate_sat_per_vb.map(|f| f.to_string())),
+		])
+	}
+}
+
+impl AuditedRequest for Bolt11SendRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		summary([
+			("invoice", Some(self.invoice.clone())),
+			("amount_msat", self.amount_msat.map(|a| a.to_string())),
+			("confirm_large", self.confirm_large.then(|| "true".to_string())),
+		])
+	}
+}
+
+impl AuditedRequest for Bolt12SendRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		// The payer note is free-form user data and is deliberately left out.
+		summary([
+			("offer", Some(self.offer.clone())),
+			("amount_msat", self.amount_msat.map(|a| a.to_string())),
+			("quantity", self.quantity.map(|q| q.to_string())),
+		])
+	}
+}
+
+impl AuditedRequest for SpontaneousSendRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		// Custom TLV values may carry arbitrary data, so only their number is recorded.
+		summary([
+			("node_id", Some(self.node_id.clone())),
+			("amount_msat", Some(self.amount_msat.to_string())),
+			("custom_tlv_count", Some(self.custom_tlvs.len().to_string())),
+		])
+	}
+}
+
+impl AuditedRequest for UnifiedSendRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		summary([
+			("uri", Some(self.uri.clone())),
+			("amount_msat", self.amount_msat.map(|a| a.to_string())),
+		])
+	}
+}
+
+impl AuditedRequest for CreateLnurlWithdrawRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		summary([
+			("max_amount_msat", Some(self.max_amount_msat.to_string())),
+			("min_amount_msat", self.min_amount_msat.map(|a| a.to_string())),
+		])
+	}
+}
+
+impl AuditedRequest for OpenChannelRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		summary([
+			("node_pubkey", Some(self.node_pubkey.clone())),
+			("address", Some(self.address.clone())),
+			("channel_amount_sats", Some(self.channel_amount_sats.to_string())),
+			("push_to_counterparty_msat", self.push_to_counterparty_msat.map(|a| a.to_string())),
+			("announce_channel", self.announce_channel.map(|a| a.to_string())),
+		])
+	}
+}
+
+impl AuditedRequest for SpliceInRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		summary([
+			("user_channel_id", Some(self.user_channel_id.clone())),
+			("counterparty_node_id", Some(self.counterparty_node_id.clone())),
+			("splice_amount_sats", Some(self.splice_amount_sats.to_string())),
+		])
+	}
+}
+
+impl AuditedRequest for SpliceOutRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		summary([
+			("user_channel_id", Some(self.user_channel_id.clone())),
+			("counterparty_node_id", Some(self.counterparty_node_id.clone())),
+			("address", self.address.clone()),
+			("splice_amount_sats", Some(self.splice_amount_sats.to_string())),
+		])
+	}
+}
+
+impl AuditedRequest for CloseChannelRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		summary([
+			("user_channel_id", Some(self.user_channel_id.clone())),
+			("counterparty_node_id", Some(self.counterparty_node_id.clone())),
+		])
+	}
+}
+
+impl AuditedRequest for ForceCloseChannelRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		summary([
+			("user_channel_id", Some(self.user_channel_id.clone())),
+			("counterparty_node_id", Some(self.counterparty_node_id.clone())),
+			("force_close_reason", self.force_close_reason.clone()),
+		])
+	}
+}
+
+impl AuditedRequest for Bolt11ClaimForHashRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		// The preimage is the secret that unlocks the payment and must never be recorded.
+		summary([
+			("payment_hash", self.payment_hash.clone()),
+			("claimable_amount_msat", self.claimable_amount_msat.map(|a| a.to_string())),
+		])
+	}
+}
+
+impl AuditedRequest for Bolt11FailForHashRequest {
+	fn audit_summary(&self) -> BTreeMap<String, String> {
+		summary([("payment_hash", Some(self.payment_hash.clone()))])
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::types::CustomTlvRecord;
+
+	use super::*;
+
+	#[test]
+	fn claim_for_hash_summary_omits_preimage() {
+		let preimage = "11".repeat(32);
+		let request = Bolt11ClaimForHashRequest {
+			payment_hash: Some("22".repeat(32)),
+			claimable_amount_msat: Some(1_000),
+			preimage: preimage.clone(),
+		};
+
+		let summary = request.audit_summary();
+
+		assert_eq!(summary.get("payment_hash"), Some(&"22".repeat(32)));
+		assert_eq!(summary.get("claimable_amount_msat").map(String::as_str), Some("1000"));
+		assert!(!summary.contains_key("preimage"));
+		assert!(summary.values().all(|v| !v.contains(&preimage)));
+	}
+
+	#[test]
+	fn bolt12_send_summary_omits_payer_note() {
+		let request = Bolt12SendRequest {
+			offer: "lno1qgsq".to_string(),
+			amount_msat: Some(5_000),
+			quantity: None,
+			payer_note: Some("my secret note".to_string()),
+			route_parameters: None,
+		};
+
+		let summary = request.audit_summary();
+
+		assert_eq!(summary.get("offer").map(String::as_str), Some("lno1qgsq"));
+		assert_eq!(summary.get("amount_msat").map(String::as_str), Some("5000"));
+		assert!(!summary.contains_key("quantity"));
+		assert!(summary.values().all(|v| !v.contains("my secret note")));
+	}
+
+	#[test]
+	fn spontaneous_send_summary_omits_custom_tlv_values() {
+		let request = SpontaneousSendRequest {
+			amount_msat: 42_000,
+			node_id: "02".repeat(33),
+			route_parameters: None,
+			custom_tlvs: vec![CustomTlvRecord {
+				type_num: 65537,
+				value: b"sensitive".to_vec().into(),
+			}],
+		};
+
+		let summary = request.audit_summary();
+
+		assert_eq!(summary.get("amount_msat").map(String::as_str), Some("42000"));
+		assert_eq!(summary.get("custom_tlv_count").map(String::as_str), Some("1"));
+		assert!(summary.values().all(|v| !v.contains("sensitive")));
+	}
+
+	#[test]
+	fn onchain_send_summary_records_amount_and_destination() {
+		let request = OnchainSendRequest {
+			address: "bcrt1qxyz".to_string(),
+			amount_sats: Some(10_000),
+			send_all: None,
+			fee_rate_sat_per_vb: Some(2),
+		};
+
+		let summary = request.audit_summary();
+
+		assert_eq!(summary.get("address").map(String::as_str), Some("bcrt1qxyz"));
+		assert_eq!(summary.get("amount_sats").map(String::as_str), Some("10000"));
+		assert_eq!(summary.get("fee_rate_sat_per_vb").map(String::as_str), Some("2"));
+		assert!(!summary.contains_key("send_all"));
+	}
+}
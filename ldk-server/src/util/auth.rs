@@ -0,0 +1,232 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::fmt;
+use std::str::FromStr;
+
+use ldk_server_grpc::endpoints::{
+	BOLT11_CLAIM_FOR_HASH_PATH, BOLT11_FAIL_FOR_HASH_PATH, BOLT11_RECEIVE_FOR_HASH_PATH,
+	BOLT11_RECEIVE_PATH, BOLT11_RECEIVE_VARIABLE_AMOUNT_VIA_JIT_CHANNEL_PATH,
+	BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH, BOLT11_SEND_PATH, BOLT12_RECEIVE_PATH, BOLT12_SEND_PATH,
+	CREATE_LNURL_WITHDRAW_PATH, DECODE_INVOICE_PATH, DECODE_OFFER_PATH,
+	EXPORT_PATHFINDING_SCORES_PATH, GET_API_VERSION_PATH, GET_BALANCES_PATH,
+	GET_CHANNEL_ANNOUNCEMENT_STATUS_PATH, GET_CHANNEL_ROUTING_STATS_PATH,
+	GET_CLAIMABLE_BALANCES_PATH, GET_FORWARDED_PAYMENT_PATH, GET_NODE_INFO_PATH,
+	GET_PAYMENT_DETAILS_PATH, GET_PEER_DETAILS_PATH, GET_SNAPSHOT_PATH, GRAPH_GET_CHANNEL_PATH,
+	GRAPH_GET_NODE_PATH, GRAPH_LIST_CHANNELS_PATH, GRAPH_LIST_NODES_PATH, IS_OWN_INVOICE_PATH,
+	LIST_CHANNELS_PATH, LIST_FORWARDED_PAYMENTS_PATH, LIST_OFFERS_PATH, LIST_PAYMENTS_PATH,
+	LIST_PEERS_PATH, ONCHAIN_RECEIVE_PATH, ONCHAIN_SEND_PATH, RUN_PREFLIGHT_CHECKS_PATH,
+	SPONTANEOUS_SEND_PATH, SUBSCRIBE_EVENTS_PATH, UNIFIED_SEND_PATH, VERIFY_SIGNATURE_PATH,
+	WHO_AM_I_PATH,
+};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::PermissionDeniedError;
+
+/// The name of the API key that is generated on first startup and stored in the data directory.
+pub(crate) const ADMIN_API_KEY_NAME: &str = "admin";
+
+/// The permission set granted to an API key.
+///
+/// Scopes are ordered: each scope includes all permissions of the scopes before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiKeyScope {
+	/// Allows reading node, channel, payment and network graph state.
+	ReadOnly,
+	/// Additionally allows receiving and sending payments.
+	Send,
+	/// Allows every operation, including channel and peer management.
+	Admin,
+}
+
+impl ApiKeyScope {
+	const ALL: [ApiKeyScope; 3] = [ApiKeyScope::ReadOnly, ApiKeyScope::Send, ApiKeyScope::Admin];
+
+	pub(crate) fn as_str(&self) -> &'static str {
+		match self {
+			ApiKeyScope::ReadOnly => "read_only",
+			ApiKeyScope::Send => "send",
+			ApiKeyScope::Admin => "admin",
+		}
+	}
+
+	/// Returns this scope along with all scopes it implies.
+	pub(crate) fn granted(&self) -> impl Iterator<Item = ApiKeyScope> + '_ {
+		Self::ALL.into_iter().filter(move |scope| scope <= self)
+	}
+}
+
+impl fmt::Display for ApiKeyScope {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl FromStr for ApiKeyScope {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim().to_ascii_lowercase().as_str() {
+			"read_only" | "read-only" => Ok(ApiKeyScope::ReadOnly),
+			"send" => Ok(ApiKeyScope::Send),
+			"admin" => Ok(ApiKeyScope::Admin),
+			other => Err(format!(
+				"Invalid API key scope '{}'; expected 'read_only', 'send' or 'admin'",
+				other
+			)),
+		}
+	}
+}
+
+/// An API key that clients may authenticate with.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ApiKey {
+	/// A human-readable name identifying the holder of the key, e.g. in the audit log.
+	pub name: String,
+	/// The secret used to compute the request HMAC.
+	pub key: String,
+	/// The permissions granted to requests authenticated with this key.
+	pub scope: ApiKeyScope,
+}
+
+impl fmt::Debug for ApiKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		// The key itself is a secret and is deliberately left out.
+		f.debug_struct("ApiKey")
+			.field("name", &self.name)
+			.field("scope", &self.scope)
+			.finish_non_exhaustive()
+	}
+}
+
+/// Returns the scope an API key needs to call the given RPC method.
+///
+/// Methods not listed explicitly require [`ApiKeyScope::Admin`], so new endpoints are restricted
+/// until deliberately opened up.
+pub(crate) fn required_scope(method: &str) -> ApiKeyScope {
+	match method {
+		GET_NODE_INFO_PATH
+		| GET_BALANCES_PATH
+		| GET_CLAIMABLE_BALANCES_PATH
+		| LIST_CHANNELS_PATH
+		| GET_PAYMENT_DETAILS_PATH
+		| LIST_PAYMENTS_PATH
+		| LIST_FORWARDED_PAYMENTS_PATH
+		| GET_FORWARDED_PAYMENT_PATH
+		| GET_CHANNEL_ROUTING_STATS_PATH
+		| GET_CHANNEL_ANNOUNCEMENT_STATUS_PATH
+		| LIST_OFFERS_PATH
+		| LIST_PEERS_PATH
+		| GET_PEER_DETAILS_PATH
+		| VERIFY_SIGNATURE_PATH
+		| EXPORT_PATHFINDING_SCORES_PATH
+		| GRAPH_LIST_CHANNELS_PATH
+		| GRAPH_GET_CHANNEL_PATH
+		| GRAPH_LIST_NODES_PATH
+		| GRAPH_GET_NODE_PATH
+		| DECODE_INVOICE_PATH
+		| IS_OWN_INVOICE_PATH
+		| DECODE_OFFER_PATH
+		| SUBSCRIBE_EVENTS_PATH
+		| WHO_AM_I_PATH
+		| RUN_PREFLIGHT_CHECKS_PATH
+		| GET_SNAPSHOT_PATH
+		| GET_API_VERSION_PATH => ApiKeyScope::ReadOnly,
+		ONCHAIN_RECEIVE_PATH
+		| ONCHAIN_SEND_PATH
+		| BOLT11_RECEIVE_PATH
+		| BOLT11_RECEIVE_FOR_HASH_PATH
+		| BOLT11_CLAIM_FOR_HASH_PATH
+		| BOLT11_FAIL_FOR_HASH_PATH
+		| BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH
+		| BOLT11_RECEIVE_VARIABLE_AMOUNT_VIA_JIT_CHANNEL_PATH
+		| BOLT11_SEND_PATH
+		| BOLT12_RECEIVE_PATH
+		| BOLT12_SEND_PATH
+		| SPONTANEOUS_SEND_PATH
+		| UNIFIED_SEND_PATH
+		| CREATE_LNURL_WITHDRAW_PATH => ApiKeyScope::Send,
+		_ => ApiKeyScope::Admin,
+	}
+}
+
+/// Checks that the given API key is permitted to call the given RPC method.
+pub(crate) fn authorize(api_key: &ApiKey, method: &str) -> Result<(), LdkServerError> {
+	let required = required_scope(method);
+	if api_key.scope < required {
+		return Err(LdkServerError::new(
+			PermissionDeniedError,
+			format!(
+				"Insufficient scope: `{}` requires the `{}` scope, but API key `{}` only has `{}`",
+				method, required, api_key.name, api_key.scope
+			),
+		));
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::endpoints::{CLOSE_CHANNEL_PATH, OPEN_CHANNEL_PATH};
+
+	use super::*;
+
+	fn api_key(scope: ApiKeyScope) -> ApiKey {
+		ApiKey { name: "test".to_string(), key: "secret".to_string(), scope }
+	}
+
+	#[test]
+	fn read_only_key_is_refused_send_and_allowed_list() {
+		let key = api_key(ApiKeyScope::ReadOnly);
+
+		let err = authorize(&key, BOLT11_SEND_PATH).unwrap_err();
+		assert_eq!(err.error_code, PermissionDeniedError);
+		assert!(err.message.contains("Insufficient scope"));
+		assert!(authorize(&key, ONCHAIN_SEND_PATH).is_err());
+
+		assert!(authorize(&key, LIST_PAYMENTS_PATH).is_ok());
+		assert!(authorize(&key, LIST_CHANNELS_PATH).is_ok());
+	}
+
+	#[test]
+	fn send_key_is_refused_channel_management() {
+		let key = api_key(ApiKeyScope::Send);
+
+		assert!(authorize(&key, BOLT11_SEND_PATH).is_ok());
+		assert!(authorize(&key, LIST_PAYMENTS_PATH).is_ok());
+		assert!(authorize(&key, OPEN_CHANNEL_PATH).is_err());
+		assert!(authorize(&key, CLOSE_CHANNEL_PATH).is_err());
+	}
+
+	#[test]
+	fn admin_key_is_allowed_everything() {
+		let key = api_key(ApiKeyScope::Admin);
+
+		assert!(authorize(&key, OPEN_CHANNEL_PATH).is_ok());
+		assert!(authorize(&key, BOLT11_SEND_PATH).is_ok());
+		assert!(authorize(&key, LIST_PAYMENTS_PATH).is_ok());
+		assert!(authorize(&key, "SomeFutureEndpoint").is_ok());
+	}
+
+	#[test]
+	fn granted_scopes_include_implied_scopes() {
+		let granted: Vec<_> = ApiKeyScope::Send.granted().collect();
+		assert_eq!(granted, vec![ApiKeyScope::ReadOnly, ApiKeyScope::Send]);
+		assert_eq!(ApiKeyScope::ReadOnly.granted().count(), 1);
+		assert_eq!(ApiKeyScope::Admin.granted().count(), 3);
+	}
+
+	#[test]
+	fn parses_scope_names() {
+		assert_eq!(ApiKeyScope::from_str("read_only"), Ok(ApiKeyScope::ReadOnly));
+		assert_eq!(ApiKeyScope::from_str("SEND"), Ok(ApiKeyScope::Send));
+		assert_eq!(ApiKeyScope::from_str("admin"), Ok(ApiKeyScope::Admin));
+		assert!(ApiKeyScope::from_str("superuser").is_err());
+	}
+}
@@ -0,0 +1,68 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use ldk_node::config::ChannelConfig;
+use ldk_node::Node;
+use log::{error, info};
+
+/// Raises the CLTV expiry delta of the given channel config to the configured minimum, if lower.
+pub(crate) fn apply_min_cltv_expiry_delta(
+	mut channel_config: ChannelConfig, min_cltv_expiry_delta: u16,
+) -> ChannelConfig {
+	channel_config.cltv_expiry_delta = channel_config.cltv_expiry_delta.max(min_cltv_expiry_delta);
+	channel_config
+}
+
+/// Raises the CLTV expiry delta of every channel below the configured minimum.
+///
+/// This covers channels opened before the minimum was configured, as well as inbound channels,
+/// which start out with the default channel config.
+pub(crate) fn enforce_min_cltv_expiry_delta(node: &Node, min_cltv_expiry_delta: u16) {
+	for channel in node.list_channels() {
+		let current_cltv_expiry_delta = channel.config.cltv_expiry_delta;
+		if current_cltv_expiry_delta >= min_cltv_expiry_delta {
+			continue;
+		}
+
+		let channel_config = apply_min_cltv_expiry_delta(channel.config, min_cltv_expiry_delta);
+		match node.update_channel_config(
+			&channel.user_channel_id,
+			channel.counterparty.node_id,
+			channel_config,
+		) {
+			Ok(()) => info!(
+				"Raised cltv_expiry_delta of channel {} from {} to the configured minimum of {}",
+				channel.channel_id, current_cltv_expiry_delta, min_cltv_expiry_delta
+			),
+			Err(e) => {
+				error!("Failed to raise cltv_expiry_delta of channel {}: {}", channel.channel_id, e)
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn cltv_expiry_delta_below_minimum_is_raised() {
+		let channel_config = ChannelConfig { cltv_expiry_delta: 40, ..ChannelConfig::default() };
+
+		let raised = apply_min_cltv_expiry_delta(channel_config, 144);
+		assert_eq!(raised.cltv_expiry_delta, 144);
+		assert_eq!(
+			raised.forwarding_fee_base_msat, channel_config.forwarding_fee_base_msat,
+			"only the CLTV expiry delta is changed"
+		);
+
+		let unchanged = apply_min_cltv_expiry_delta(channel_config, 34);
+		assert_eq!(unchanged.cltv_expiry_delta, 40);
+	}
+}
@@ -7,6 +7,7 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -24,12 +25,18 @@ use ldk_node::probing::{ProbingConfig, ProbingConfigBuilder};
 use log::LevelFilter;
 use serde::{Deserialize, Serialize};
 
+use crate::util::auth::{ApiKey, ApiKeyScope, ADMIN_API_KEY_NAME};
+use crate::util::payment_log::PaymentLogDetail;
+
 const DEFAULT_GRPC_SERVICE_ADDRESS: &str = "127.0.0.1:3536";
 const DEFAULT_PATHFINDING_SCORES_SOURCE_URL: &str =
 	"https://rapidsync.lightningdevkit.org/scoring/scorer.bin";
 const DEFAULT_LOG_MAX_SIZE_MB: u64 = 50;
 const DEFAULT_LOG_ROTATION_INTERVAL_HOURS: u64 = 24;
 const DEFAULT_LOG_MAX_FILES: usize = 5;
+const DEFAULT_MAX_REQUEST_BODY_BYTES: usize = 10 * 1024 * 1024;
+/// The shortest interval at which ldk-node allows the wallets to be synced in the background.
+const MIN_CHAIN_POLL_INTERVAL_SECS: u64 = 10;
 
 #[cfg(not(test))]
 const DEFAULT_CONFIG_FILE: &str = "config.toml";
@@ -56,6 +63,8 @@ pub struct Config {
 	pub tls_config: Option<TlsConfig>,
 	pub grpc_service_addr: SocketAddr,
 	pub storage_dir_path: Option<String>,
+	/// Where ldk-server keeps the payment, forward and audit history, if not with the node's data.
+	pub history_dir_path: Option<String>,
 	pub chain_source: ChainSource,
 	pub rgs_server_url: Option<String>,
 	pub lsps2_client_config: Option<LSPSClientConfig>,
@@ -67,6 +76,7 @@ pub struct Config {
 	pub log_rotation_interval_secs: u64,
 	pub log_max_files: usize,
 	pub log_to_file: bool,
+	pub payment_log_detail: PaymentLogDetail,
 	pub pathfinding_scores_source_url: Option<String>,
 	pub probing_config: Option<ProbingConfig>,
 	pub async_payments_role: Option<AsyncPaymentsRole>,
@@ -76,6 +86,166 @@ pub struct Config {
 	pub metrics_password: Option<String>,
 	pub tor_config: Option<TorConfig>,
 	pub hrn_config: HumanReadableNamesConfig,
+	pub api_keys: Vec<ApiKey>,
+	pub max_outbound_payment_msat: Option<u64>,
+	pub payment_history_retention: Option<PaymentHistoryRetention>,
+	pub max_offer_quantity: Option<u64>,
+	pub deactivate_expired_offers: bool,
+	pub notify_expired_invoices: bool,
+	pub max_description_length: Option<usize>,
+	pub lnurl_base_url: Option<String>,
+	pub large_payment_warning_msat: Option<u64>,
+	pub min_cltv_expiry_delta: Option<u16>,
+	pub announce_channels_by_default: bool,
+	pub min_channel_size_sat: Option<u64>,
+	pub reject_reserve_shortfall: bool,
+	pub max_request_body_bytes: usize,
+	pub read_requests_per_minute: Option<u32>,
+	pub send_requests_per_minute: Option<u32>,
+}
+
+/// The placeholder secrets are replaced with in [`Config::redacted_entries`].
+pub(crate) const REDACTED: &str = "***";
+
+impl Config {
+	/// Returns the configuration as key/value pairs with every secret replaced by [`REDACTED`], so
+	/// that it can be shared when reporting issues.
+	pub(crate) fn redacted_entries(&self) -> BTreeMap<String, String> {
+		// Destructured so that adding a field forces a decision on whether it needs redacting.
+		let Config {
+			listening_addrs,
+			announcement_addrs,
+			alias,
+			network,
+			tls_config,
+			grpc_service_addr,
+			storage_dir_path,
+			history_dir_path,
+			chain_source,
+			rgs_server_url,
+			lsps2_client_config,
+			lsps2_service_config,
+			log_level,
+			log_file_path,
+			log_max_size_bytes,
+			log_rotation_interval_secs,
+			log_max_files,
+			log_to_file,
+			payment_log_detail,
+			pathfinding_scores_source_url,
+			probing_config,
+			async_payments_role,
+			metrics_enabled,
+			poll_metrics_interval,
+			metrics_username,
+			metrics_password,
+			tor_config,
+			hrn_config,
+			api_keys,
+			max_outbound_payment_msat,
+			payment_history_retention,
+			max_offer_quantity,
+			deactivate_expired_offers,
+			notify_expired_invoices,
+			max_description_length,
+			lnurl_base_url,
+			large_payment_warning_msat,
+			min_cltv_expiry_delta,
+			announce_channels_by_default,
+			min_channel_size_sat,
+			reject_reserve_shortfall,
+			max_request_body_bytes,
+			read_requests_per_minute,
+			send_requests_per_minute,
+		} = self;
+
+		let redact = |secret: &Option<String>| secret.as_ref().map(|_| REDACTED.to_string());
+		let addresses = |addrs: &Option<Vec<SocketAddress>>| {
+			addrs.as_ref().map(|addrs| addrs.iter().map(|a| a.to_string()).collect::<Vec<_>>())
+		};
+		let chain_source = match chain_source {
+			ChainSource::Rpc {
+				rpc_host, rpc_port, rpc_user, wallet_rescan_from_height, ..
+			} => {
+				let redacted = ChainSource::Rpc {
+					rpc_host: rpc_host.clone(),
+					rpc_port: *rpc_port,
+					rpc_user: rpc_user.clone(),
+					rpc_password: REDACTED.to_string(),
+					wallet_rescan_from_height: *wallet_rescan_from_height,
+				};
+				format!("{:?}", redacted)
+			},
+			ChainSource::Electrum { .. } | ChainSource::Esplora { .. } => {
+				format!("{:?}", chain_source)
+			},
+		};
+		let lsps2_client_config = lsps2_client_config
+			.as_ref()
+			.map(|config| LSPSClientConfig { token: redact(&config.token), ..config.clone() });
+		let lsps2_service_config = lsps2_service_config.as_ref().map(|config| LSPS2ServiceConfig {
+			require_token: redact(&config.require_token),
+			..config.clone()
+		});
+
+		let entries = [
+			("listening_addrs", format!("{:?}", addresses(listening_addrs))),
+			("announcement_addrs", format!("{:?}", addresses(announcement_addrs))),
+			("alias", format!("{:?}", alias.as_ref().map(|alias| alias.to_string()))),
+			("network", network.to_string()),
+			("tls_config", format!("{:?}", tls_config)),
+			("grpc_service_addr", grpc_service_addr.to_string()),
+			("storage_dir_path", format!("{:?}", storage_dir_path)),
+			("history_dir_path", format!("{:?}", history_dir_path)),
+			("chain_source", chain_source),
+			("rgs_server_url", format!("{:?}", rgs_server_url)),
+			("lsps2_client_config", format!("{:?}", lsps2_client_config)),
+			("lsps2_service_config", format!("{:?}", lsps2_service_config)),
+			("log_level", log_level.to_string()),
+			("log_file_path", format!("{:?}", log_file_path)),
+			("log_max_size_bytes", log_max_size_bytes.to_string()),
+			("log_rotation_interval_secs", log_rotation_interval_secs.to_string()),
+			("log_max_files", log_max_files.to_string()),
+			("log_to_file", log_to_file.to_string()),
+			("payment_log_detail", payment_log_detail.to_string()),
+			("pathfinding_scores_source_url", format!("{:?}", pathfinding_scores_source_url)),
+			("probing_config", format!("{:?}", probing_config)),
+			("async_payments_role", format!("{:?}", async_payments_role)),
+			("metrics_enabled", metrics_enabled.to_string()),
+			("poll_metrics_interval", format!("{:?}", poll_metrics_interval)),
+			("metrics_username", format!("{:?}", metrics_username)),
+			("metrics_password", format!("{:?}", redact(metrics_password))),
+			("tor_config", format!("{:?}", tor_config)),
+			("hrn_config", format!("{:?}", hrn_config)),
+			// The `Debug` output of `ApiKey` leaves out the key itself.
+			("api_keys", format!("{:?}", api_keys)),
+			("max_outbound_payment_msat", format!("{:?}", max_outbound_payment_msat)),
+			("payment_history_retention", format!("{:?}", payment_history_retention)),
+			("max_offer_quantity", format!("{:?}", max_offer_quantity)),
+			("deactivate_expired_offers", deactivate_expired_offers.to_string()),
+			("notify_expired_invoices", notify_expired_invoices.to_string()),
+			("max_description_length", format!("{:?}", max_description_length)),
+			("lnurl_base_url", format!("{:?}", lnurl_base_url)),
+			("large_payment_warning_msat", format!("{:?}", large_payment_warning_msat)),
+			("min_cltv_expiry_delta", format!("{:?}", min_cltv_expiry_delta)),
+			("announce_channels_by_default", announce_channels_by_default.to_string()),
+			("min_channel_size_sat", format!("{:?}", min_channel_size_sat)),
+			("reject_reserve_shortfall", reject_reserve_shortfall.to_string()),
+			("max_request_body_bytes", max_request_body_bytes.to_string()),
+			("read_requests_per_minute", format!("{:?}", read_requests_per_minute)),
+			("send_requests_per_minute", format!("{:?}", send_requests_per_minute)),
+		];
+		entries.into_iter().map(|(key, value)| (key.to_string(), value)).collect()
+	}
+}
+
+/// Limits how much of the payment history is kept. Pending payments are never pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaymentHistoryRetention {
+	/// Prunes payments that were last updated longer ago than this many seconds.
+	pub max_age_secs: Option<u64>,
+	/// Prunes all but this many of the most recent payments.
+	pub max_records: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -83,6 +253,7 @@ pub struct LSPSClientConfig {
 	pub node_id: PublicKey,
 	pub address: SocketAddress,
 	pub token: Option<String>,
+	pub max_skimmed_fee_msat: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -104,10 +275,12 @@ pub enum ChainSource {
 	Electrum {
 		server_url: String,
 		force_wallet_full_scan: bool,
+		chain_poll_interval_secs: Option<u64>,
 	},
 	Esplora {
 		server_url: String,
 		force_wallet_full_scan: bool,
+		chain_poll_interval_secs: Option<u64>,
 	},
 }
 
@@ -126,8 +299,10 @@ struct ConfigBuilder {
 	tls_config: Option<TlsConfig>,
 	grpc_service_address: Option<String>,
 	storage_dir_path: Option<String>,
+	history_dir_path: Option<String>,
 	electrum_url: Option<String>,
 	esplora_url: Option<String>,
+	chain_poll_interval_secs: Option<u64>,
 	bitcoind_rpc_address: Option<String>,
 	bitcoind_rpc_user: Option<String>,
 	bitcoind_rpc_password: Option<String>,
@@ -141,6 +316,7 @@ struct ConfigBuilder {
 	log_rotation_interval_hours: Option<u64>,
 	log_max_files: Option<usize>,
 	log_to_file: Option<bool>,
+	payment_log_detail: Option<String>,
 	pathfinding_scores_source_url: Option<String>,
 	probing: Option<ProbingTomlConfig>,
 	async_payments_role: Option<String>,
@@ -150,6 +326,24 @@ struct ConfigBuilder {
 	metrics_password: Option<String>,
 	tor_proxy_address: Option<String>,
 	hrn: Option<HrnTomlConfig>,
+	api_keys: Option<Vec<ApiKeyTomlConfig>>,
+	max_outbound_payment_msat: Option<u64>,
+	enforce_max_outbound_payment: Option<bool>,
+	payment_history_max_age_secs: Option<u64>,
+	payment_history_max_records: Option<u64>,
+	max_offer_quantity: Option<u64>,
+	deactivate_expired_offers: Option<bool>,
+	notify_expired_invoices: Option<bool>,
+	max_description_length: Option<usize>,
+	lnurl_base_url: Option<String>,
+	large_payment_warning_msat: Option<u64>,
+	min_cltv_expiry_delta: Option<u16>,
+	announce_channels_by_default: Option<bool>,
+	min_channel_size_sat: Option<u64>,
+	reject_reserve_shortfall: Option<bool>,
+	max_request_body_bytes: Option<usize>,
+	read_requests_per_minute: Option<u32>,
+	send_requests_per_minute: Option<u32>,
 }
 
 impl ConfigBuilder {
@@ -168,11 +362,15 @@ impl ConfigBuilder {
 			self.async_payments_role =
 				node.async_payments_role.or(self.async_payments_role.clone());
 			self.rgs_server_url = node.rgs_server_url.or(self.rgs_server_url.clone());
+			self.max_request_body_bytes =
+				node.max_request_body_bytes.or(self.max_request_body_bytes);
 		}
 
 		if let Some(storage) = toml.storage {
-			self.storage_dir_path =
-				storage.disk.and_then(|d| d.dir_path).or(self.storage_dir_path.clone());
+			if let Some(disk) = storage.disk {
+				self.storage_dir_path = disk.dir_path.or(self.storage_dir_path.clone());
+				self.history_dir_path = disk.history_dir_path.or(self.history_dir_path.clone());
+			}
 		}
 
 		if let Some(bitcoind) = toml.bitcoind {
@@ -184,10 +382,14 @@ impl ConfigBuilder {
 
 		if let Some(electrum) = toml.electrum {
 			self.electrum_url = Some(electrum.server_url);
+			self.chain_poll_interval_secs =
+				electrum.chain_poll_interval_secs.or(self.chain_poll_interval_secs);
 		}
 
 		if let Some(esplora) = toml.esplora {
 			self.esplora_url = Some(esplora.server_url);
+			self.chain_poll_interval_secs =
+				esplora.chain_poll_interval_secs.or(self.chain_poll_interval_secs);
 		}
 
 		if let Some(log) = toml.log {
@@ -198,6 +400,7 @@ impl ConfigBuilder {
 				log.rotation_interval_hours.or(self.log_rotation_interval_hours);
 			self.log_max_files = log.max_files.or(self.log_max_files);
 			self.log_to_file = log.log_to_file.or(self.log_to_file);
+			self.payment_log_detail = log.payment_log_detail.or(self.payment_log_detail.clone());
 		}
 
 		if let Some(liquidity) = toml.liquidity {
@@ -231,6 +434,50 @@ impl ConfigBuilder {
 		if let Some(hrn) = toml.hrn {
 			self.hrn = Some(hrn);
 		}
+
+		if let Some(api_keys) = toml.api_keys {
+			self.api_keys = Some(api_keys);
+		}
+
+		if let Some(payments) = toml.payments {
+			self.max_outbound_payment_msat =
+				payments.max_outbound_payment_msat.or(self.max_outbound_payment_msat);
+			self.enforce_max_outbound_payment =
+				payments.enforce_max_outbound_payment.or(self.enforce_max_outbound_payment);
+			self.max_offer_quantity = payments.max_offer_quantity.or(self.max_offer_quantity);
+			self.deactivate_expired_offers =
+				payments.deactivate_expired_offers.or(self.deactivate_expired_offers);
+			self.notify_expired_invoices =
+				payments.notify_expired_invoices.or(self.notify_expired_invoices);
+			self.max_description_length =
+				payments.max_description_length.or(self.max_description_length);
+			self.lnurl_base_url = payments.lnurl_base_url.or(self.lnurl_base_url.clone());
+			self.large_payment_warning_msat =
+				payments.large_payment_warning_msat.or(self.large_payment_warning_msat);
+			if let Some(history_retention) = payments.history_retention {
+				self.payment_history_max_age_secs =
+					history_retention.max_age_secs.or(self.payment_history_max_age_secs);
+				self.payment_history_max_records =
+					history_retention.max_records.or(self.payment_history_max_records);
+			}
+		}
+
+		if let Some(channels) = toml.channels {
+			self.min_cltv_expiry_delta =
+				channels.min_cltv_expiry_delta.or(self.min_cltv_expiry_delta);
+			self.announce_channels_by_default =
+				channels.announce_channels_by_default.or(self.announce_channels_by_default);
+			self.min_channel_size_sat = channels.min_channel_size_sat.or(self.min_channel_size_sat);
+			self.reject_reserve_shortfall =
+				channels.reject_reserve_shortfall.or(self.reject_reserve_shortfall);
+		}
+
+		if let Some(rate_limits) = toml.rate_limits {
+			self.read_requests_per_minute =
+				rate_limits.read_requests_per_minute.or(self.read_requests_per_minute);
+			self.send_requests_per_minute =
+				rate_limits.send_requests_per_minute.or(self.send_requests_per_minute);
+		}
 	}
 
 	fn merge_args(&mut self, args: &ArgsConfig) {
@@ -351,6 +598,14 @@ impl ConfigBuilder {
 		if let Some(log_to_file) = args.log_to_file {
 			self.log_to_file = Some(log_to_file);
 		}
+
+		if let Some(max_outbound_payment_msat) = args.payments_max_outbound_payment_msat {
+			self.max_outbound_payment_msat = Some(max_outbound_payment_msat);
+		}
+
+		if args.payments_disable_max_outbound_payment {
+			self.enforce_max_outbound_payment = Some(false);
+		}
 	}
 
 	fn build(self) -> io::Result<Config> {
@@ -424,6 +679,18 @@ impl ConfigBuilder {
 			));
 		}
 
+		if let Some(interval_secs) = self.chain_poll_interval_secs {
+			if interval_secs < MIN_CHAIN_POLL_INTERVAL_SECS {
+				return Err(io::Error::new(
+					io::ErrorKind::InvalidInput,
+					format!(
+						"`chain_poll_interval_secs` must be at least {} seconds",
+						MIN_CHAIN_POLL_INTERVAL_SECS
+					),
+				));
+			}
+		}
+
 		let chain_source = if rpc_configured {
 			if self.force_wallet_full_scan {
 				return Err(io::Error::new(
@@ -461,6 +728,7 @@ impl ConfigBuilder {
 			ChainSource::Electrum {
 				server_url: url,
 				force_wallet_full_scan: self.force_wallet_full_scan,
+				chain_poll_interval_secs: self.chain_poll_interval_secs,
 			}
 		} else if let Some(url) = self.esplora_url {
 			if self.rescan_from_height.is_some() {
@@ -472,6 +740,7 @@ impl ConfigBuilder {
 			ChainSource::Esplora {
 				server_url: url,
 				force_wallet_full_scan: self.force_wallet_full_scan,
+				chain_poll_interval_secs: self.chain_poll_interval_secs,
 			}
 		} else {
 			return Err(io::Error::new(io::ErrorKind::InvalidInput, "No valid Chain Source configured. Provide Bitcoind RPC, Electrum, or Esplora details."));
@@ -498,6 +767,13 @@ impl ConfigBuilder {
 				* 60 * 60;
 		let log_max_files = self.log_max_files.unwrap_or(DEFAULT_LOG_MAX_FILES);
 		let log_to_file = self.log_to_file.unwrap_or(true);
+		let payment_log_detail = self
+			.payment_log_detail
+			.as_deref()
+			.map(PaymentLogDetail::from_str)
+			.transpose()
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+			.unwrap_or_default();
 
 		let lsps2_client_config = self
 			.lsps2
@@ -574,6 +850,80 @@ impl ConfigBuilder {
 			None => HumanReadableNamesConfig::default(),
 		};
 
+		let api_keys = build_api_keys(self.api_keys.unwrap_or_default())?;
+
+		let max_outbound_payment_msat = match self.max_outbound_payment_msat {
+			Some(0) => {
+				return Err(io::Error::new(
+					io::ErrorKind::InvalidInput,
+					"`payments.max_outbound_payment_msat` must be greater than 0",
+				));
+			},
+			limit if self.enforce_max_outbound_payment.unwrap_or(true) => limit,
+			_ => None,
+		};
+
+		let payment_history_retention =
+			match (self.payment_history_max_age_secs, self.payment_history_max_records) {
+				(None, None) => None,
+				(Some(0), _) | (_, Some(0)) => {
+					return Err(io::Error::new(
+						io::ErrorKind::InvalidInput,
+						"`payments.history_retention` limits must be greater than 0",
+					));
+				},
+				(max_age_secs, max_records) => {
+					Some(PaymentHistoryRetention { max_age_secs, max_records })
+				},
+			};
+
+		if self.max_offer_quantity == Some(0) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"`payments.max_offer_quantity` must be greater than 0",
+			));
+		}
+
+		if self.max_description_length == Some(0) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"`payments.max_description_length` must be greater than 0",
+			));
+		}
+
+		let lnurl_base_url = match &self.lnurl_base_url {
+			Some(url) => Some(validate_lnurl_base_url(url)?),
+			None => None,
+		};
+
+		if self.large_payment_warning_msat == Some(0) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"`payments.large_payment_warning_msat` must be greater than 0",
+			));
+		}
+
+		if self.min_cltv_expiry_delta == Some(0) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"`channels.min_cltv_expiry_delta` must be greater than 0",
+			));
+		}
+
+		if self.max_request_body_bytes == Some(0) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"`node.max_request_body_bytes` must be greater than 0",
+			));
+		}
+
+		if self.read_requests_per_minute == Some(0) || self.send_requests_per_minute == Some(0) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"`rate_limits.read_requests_per_minute` and `rate_limits.send_requests_per_minute` must be greater than 0",
+			));
+		}
+
 		Ok(Config {
 			network,
 			listening_addrs,
@@ -582,6 +932,7 @@ impl ConfigBuilder {
 			tls_config: self.tls_config,
 			grpc_service_addr,
 			storage_dir_path: self.storage_dir_path,
+			history_dir_path: self.history_dir_path,
 			chain_source,
 			rgs_server_url: self.rgs_server_url,
 			lsps2_client_config,
@@ -592,6 +943,7 @@ impl ConfigBuilder {
 			log_rotation_interval_secs,
 			log_max_files,
 			log_to_file,
+			payment_log_detail,
 			pathfinding_scores_source_url,
 			probing_config,
 			async_payments_role,
@@ -601,6 +953,24 @@ impl ConfigBuilder {
 			metrics_password,
 			tor_config: tor_proxy_address.map(|proxy_address| TorConfig { proxy_address }),
 			hrn_config,
+			api_keys,
+			max_outbound_payment_msat,
+			payment_history_retention,
+			max_offer_quantity: self.max_offer_quantity,
+			deactivate_expired_offers: self.deactivate_expired_offers.unwrap_or(false),
+			notify_expired_invoices: self.notify_expired_invoices.unwrap_or(false),
+			max_description_length: self.max_description_length,
+			lnurl_base_url,
+			large_payment_warning_msat: self.large_payment_warning_msat,
+			min_cltv_expiry_delta: self.min_cltv_expiry_delta,
+			announce_channels_by_default: self.announce_channels_by_default.unwrap_or(false),
+			min_channel_size_sat: self.min_channel_size_sat,
+			reject_reserve_shortfall: self.reject_reserve_shortfall.unwrap_or(false),
+			max_request_body_bytes: self
+				.max_request_body_bytes
+				.unwrap_or(DEFAULT_MAX_REQUEST_BODY_BYTES),
+			read_requests_per_minute: self.read_requests_per_minute,
+			send_requests_per_minute: self.send_requests_per_minute,
 		})
 	}
 }
@@ -621,6 +991,10 @@ pub struct TomlConfig {
 	probing: Option<ProbingTomlConfig>,
 	tor: Option<TomlTorConfig>,
 	hrn: Option<HrnTomlConfig>,
+	api_keys: Option<Vec<ApiKeyTomlConfig>>,
+	payments: Option<PaymentsTomlConfig>,
+	channels: Option<ChannelsTomlConfig>,
+	rate_limits: Option<RateLimitsTomlConfig>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -634,6 +1008,7 @@ struct NodeConfig {
 	pathfinding_scores_source_url: Option<String>,
 	async_payments_role: Option<String>,
 	rgs_server_url: Option<String>,
+	max_request_body_bytes: Option<usize>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -646,6 +1021,7 @@ struct StorageConfig {
 #[serde(deny_unknown_fields)]
 struct DiskConfig {
 	dir_path: Option<String>,
+	history_dir_path: Option<String>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -660,12 +1036,14 @@ struct BitcoindConfig {
 #[serde(deny_unknown_fields)]
 struct ElectrumConfig {
 	server_url: String,
+	chain_poll_interval_secs: Option<u64>,
 }
 
 #[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct EsploraConfig {
 	server_url: String,
+	chain_poll_interval_secs: Option<u64>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -677,6 +1055,7 @@ struct LogConfig {
 	rotation_interval_hours: Option<u64>,
 	max_files: Option<usize>,
 	log_to_file: Option<bool>,
+	payment_log_detail: Option<String>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -722,6 +1101,51 @@ struct HrnTomlConfig {
 	enable_resolution_service: Option<bool>,
 }
 
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ChannelsTomlConfig {
+	min_cltv_expiry_delta: Option<u16>,
+	announce_channels_by_default: Option<bool>,
+	min_channel_size_sat: Option<u64>,
+	reject_reserve_shortfall: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct RateLimitsTomlConfig {
+	read_requests_per_minute: Option<u32>,
+	send_requests_per_minute: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct PaymentsTomlConfig {
+	max_outbound_payment_msat: Option<u64>,
+	enforce_max_outbound_payment: Option<bool>,
+	max_offer_quantity: Option<u64>,
+	deactivate_expired_offers: Option<bool>,
+	notify_expired_invoices: Option<bool>,
+	max_description_length: Option<usize>,
+	lnurl_base_url: Option<String>,
+	large_payment_warning_msat: Option<u64>,
+	history_retention: Option<PaymentHistoryRetentionTomlConfig>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct PaymentHistoryRetentionTomlConfig {
+	max_age_secs: Option<u64>,
+	max_records: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ApiKeyTomlConfig {
+	name: String,
+	key: String,
+	scope: String,
+}
+
 impl TryFrom<HrnTomlConfig> for HumanReadableNamesConfig {
 	type Error = io::Error;
 
@@ -806,6 +1230,36 @@ fn parse_dns_server_address(addr: &str) -> io::Result<SocketAddress> {
 	})
 }
 
+/// Validates the additional API keys configured via `[[api_keys]]`.
+fn build_api_keys(configs: Vec<ApiKeyTomlConfig>) -> io::Result<Vec<ApiKey>> {
+	let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+
+	let mut api_keys: Vec<ApiKey> = Vec::with_capacity(configs.len());
+	for ApiKeyTomlConfig { name, key, scope } in configs {
+		if name.is_empty() {
+			return Err(invalid("`api_keys.name` must not be empty".to_string()));
+		}
+		if name == ADMIN_API_KEY_NAME {
+			return Err(invalid(format!(
+				"API key name '{}' is reserved for the API key stored in the data directory",
+				name
+			)));
+		}
+		if api_keys.iter().any(|k| k.name == name) {
+			return Err(invalid(format!("Duplicate API key name '{}' configured", name)));
+		}
+		if key.is_empty() {
+			return Err(invalid(format!("API key '{}' must not have an empty `key`", name)));
+		}
+		if api_keys.iter().any(|k| k.key == key) {
+			return Err(invalid(format!("API key '{}' reuses the key of another API key", name)));
+		}
+		let scope = ApiKeyScope::from_str(&scope).map_err(invalid)?;
+		api_keys.push(ApiKey { name, key, scope });
+	}
+	Ok(api_keys)
+}
+
 fn parse_async_payments_role(role: &str) -> io::Result<AsyncPaymentsRole> {
 	match role.trim().to_ascii_lowercase().as_str() {
 		"client" => Ok(AsyncPaymentsRole::Client),
@@ -909,6 +1363,7 @@ struct LSPSClientTomlConfig {
 	node_pubkey: String,
 	address: String,
 	token: Option<String>,
+	max_skimmed_fee_msat: Option<u64>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -976,7 +1431,12 @@ impl TryFrom<&LSPSClientTomlConfig> for LSPSClientConfig {
 			)
 		})?;
 
-		Ok(Self { node_id, address, token: value.token.clone() })
+		Ok(Self {
+			node_id,
+			address,
+			token: value.token.clone(),
+			max_skimmed_fee_msat: value.max_skimmed_fee_msat,
+		})
 	}
 }
 
@@ -1194,6 +1654,20 @@ pub struct ArgsConfig {
 		help = "Tor daemon SOCKS proxy address. Only connections to OnionV3 peers will be made via this proxy; other connections (IPv4 peers, Electrum server) will not be routed over Tor."
 	)]
 	tor_proxy_address: Option<String>,
+
+	#[arg(
+		long,
+		env = "LDK_SERVER_PAYMENTS_MAX_OUTBOUND_PAYMENT_MSAT",
+		help = "The maximum amount in millisatoshis a single outbound payment may send. API keys with the `admin` scope are exempt."
+	)]
+	payments_max_outbound_payment_msat: Option<u64>,
+
+	#[arg(
+		long,
+		env = "LDK_SERVER_PAYMENTS_DISABLE_MAX_OUTBOUND_PAYMENT",
+		help = "Disables enforcement of the configured maximum outbound payment amount."
+	)]
+	payments_disable_max_outbound_payment: bool,
 }
 
 impl ArgsConfig {
@@ -1259,6 +1733,30 @@ fn parse_alias(alias_str: &str) -> Result<NodeAlias, io::Error> {
 	Ok(NodeAlias(bytes))
 }
 
+/// Checks that the LNURL base URL is one that wallets accept, i.e. uses HTTPS unless it points to
+/// an onion service, and strips any trailing slash.
+fn validate_lnurl_base_url(url: &str) -> io::Result<String> {
+	let url = url.trim_end_matches('/');
+	let host = match url.split_once("://") {
+		Some((scheme, rest)) if scheme == "https" || scheme == "http" => {
+			let authority = rest.split('/').next().unwrap_or_default();
+			let host = authority.rsplit_once(':').map_or(authority, |(host, _)| host);
+			(scheme == "https" || host.ends_with(".onion")).then_some(host)
+		},
+		_ => None,
+	};
+	match host {
+		Some(host) if !host.is_empty() => Ok(url.to_string()),
+		_ => Err(io::Error::new(
+			io::ErrorKind::InvalidInput,
+			format!(
+				"Invalid `payments.lnurl_base_url` {}, expected an https:// URL or an http:// URL of an onion service",
+				url
+			),
+		)),
+	}
+}
+
 fn parse_host_port(addr: &str) -> io::Result<(String, u16)> {
 	let (host, port_str) = addr.rsplit_once(':').ok_or_else(|| {
 		io::Error::new(io::ErrorKind::InvalidInput, "Invalid address format, expected host:port")
@@ -1315,6 +1813,7 @@ mod tests {
 				node_pubkey = "0217890e3aad8d35bc054f43acc00084b25229ecff0ab68debd82883ad65ee8266"
 				address = "127.0.0.1:39735"
 				token = "lsps2-token"
+				max_skimmed_fee_msat = 5000
 
 				[liquidity.lsps2_service]
 				advertise_service = false
@@ -1364,6 +1863,8 @@ mod tests {
 			log_max_size_mb: Some(50),
 			log_rotation_interval_hours: Some(24),
 			log_max_files: Some(5),
+			payments_max_outbound_payment_msat: None,
+			payments_disable_max_outbound_payment: false,
 		}
 	}
 
@@ -1399,6 +1900,8 @@ mod tests {
 			log_max_size_mb: None,
 			log_rotation_interval_hours: None,
 			log_max_files: None,
+			payments_max_outbound_payment_msat: None,
+			payments_disable_max_outbound_payment: false,
 		}
 	}
 
@@ -1432,6 +1935,28 @@ mod tests {
 		}
 	}
 
+	/// Writes a minimal esplora config with `extra_toml` appended to `config_file_name` in the
+	/// temp directory and loads it. The `[node]` table comes last, so `extra_toml` can either add
+	/// `[node]` keys or start new tables.
+	fn load_with_extra_toml(config_file_name: &str, extra_toml: &str) -> io::Result<Config> {
+		load_with_extra_toml_and_args(config_file_name, extra_toml, &mut empty_args_config())
+	}
+
+	fn load_with_extra_toml_and_args(
+		config_file_name: &str, extra_toml: &str, args_config: &mut ArgsConfig,
+	) -> io::Result<Config> {
+		let config_path = std::env::temp_dir().join(config_file_name);
+		let toml_config = format!(
+			"[esplora]\nserver_url = \"https://mempool.space/api\"\n{}\n\
+			[node]\nnetwork = \"regtest\"\n{}\n",
+			lsps2_service_config_for_feature(),
+			extra_toml
+		);
+		fs::write(&config_path, toml_config).unwrap();
+		args_config.config_file = Some(config_path.to_string_lossy().to_string());
+		load_config(args_config)
+	}
+
 	#[test]
 	fn test_config_from_file() {
 		let storage_path = std::env::temp_dir();
@@ -1454,6 +1979,7 @@ mod tests {
 			network: Network::Regtest,
 			grpc_service_addr: SocketAddr::from_str("127.0.0.1:3002").unwrap(),
 			storage_dir_path: Some("/tmp".to_string()),
+			history_dir_path: None,
 			tls_config: Some(TlsConfig {
 				cert_path: Some("/path/to/tls.crt".to_string()),
 				key_path: Some("/path/to/tls.key".to_string()),
@@ -1474,6 +2000,7 @@ mod tests {
 				.unwrap(),
 				address: SocketAddress::from_str("127.0.0.1:39735").unwrap(),
 				token: Some("lsps2-token".to_string()),
+				max_skimmed_fee_msat: Some(5000),
 			}),
 			lsps2_service_config: Some(LSPS2ServiceConfig {
 				require_token: None,
@@ -1494,6 +2021,7 @@ mod tests {
 			log_rotation_interval_secs: 24 * 60 * 60,
 			log_max_files: 5,
 			log_to_file: true,
+			payment_log_detail: PaymentLogDetail::MetadataOnly,
 			pathfinding_scores_source_url: None,
 			probing_config: None,
 			async_payments_role: Some(AsyncPaymentsRole::Client),
@@ -1505,6 +2033,22 @@ mod tests {
 				proxy_address: SocketAddress::from_str("127.0.0.1:9050").unwrap(),
 			}),
 			hrn_config: HumanReadableNamesConfig::default(),
+			api_keys: vec![],
+			max_outbound_payment_msat: None,
+			payment_history_retention: None,
+			max_offer_quantity: None,
+			deactivate_expired_offers: false,
+			notify_expired_invoices: false,
+			max_description_length: None,
+			lnurl_base_url: None,
+			large_payment_warning_msat: None,
+			min_cltv_expiry_delta: None,
+			announce_channels_by_default: false,
+			min_channel_size_sat: None,
+			reject_reserve_shortfall: false,
+			max_request_body_bytes: DEFAULT_MAX_REQUEST_BODY_BYTES,
+			read_requests_per_minute: None,
+			send_requests_per_minute: None,
 		};
 
 		assert_eq!(config.listening_addrs, expected.listening_addrs);
@@ -1571,13 +2115,15 @@ mod tests {
 		fs::write(storage_path.join(config_file_name), toml_config).unwrap();
 		let config = load_config(&args_config).unwrap();
 
-		let ChainSource::Electrum { server_url, force_wallet_full_scan } = config.chain_source
+		let ChainSource::Electrum { server_url, force_wallet_full_scan, chain_poll_interval_secs } =
+			config.chain_source
 		else {
 			panic!("unexpected chain source");
 		};
 
 		assert_eq!(server_url, "ssl://electrum.blockstream.info:50002");
 		assert!(!force_wallet_full_scan);
+		assert_eq!(chain_poll_interval_secs, None);
 
 		// Test case where only bitcoind is set
 
@@ -1923,6 +2469,7 @@ mod tests {
 			.unwrap(),
 			alias: Some(parse_alias(args_config.node_alias.as_deref().unwrap()).unwrap()),
 			storage_dir_path: Some(args_config.storage_dir_path.unwrap()),
+			history_dir_path: None,
 			tls_config: None,
 			chain_source: ChainSource::Rpc {
 				rpc_host: host,
@@ -1945,10 +2492,27 @@ mod tests {
 			metrics_password: None,
 			tor_config: None,
 			hrn_config: HumanReadableNamesConfig::default(),
+			api_keys: vec![],
+			max_outbound_payment_msat: None,
+			payment_history_retention: None,
+			max_offer_quantity: None,
+			deactivate_expired_offers: false,
+			notify_expired_invoices: false,
+			max_description_length: None,
+			lnurl_base_url: None,
+			large_payment_warning_msat: None,
+			min_cltv_expiry_delta: None,
+			announce_channels_by_default: false,
+			min_channel_size_sat: None,
+			reject_reserve_shortfall: false,
+			max_request_body_bytes: DEFAULT_MAX_REQUEST_BODY_BYTES,
+			read_requests_per_minute: None,
+			send_requests_per_minute: None,
 			log_max_size_bytes: 50 * 1024 * 1024,
 			log_rotation_interval_secs: 24 * 60 * 60,
 			log_max_files: 5,
 			log_to_file: true,
+			payment_log_detail: PaymentLogDetail::MetadataOnly,
 		};
 
 		assert_eq!(config.listening_addrs, expected.listening_addrs);
@@ -1967,6 +2531,7 @@ mod tests {
 		assert_eq!(config.log_rotation_interval_secs, expected.log_rotation_interval_secs);
 		assert_eq!(config.log_max_files, expected.log_max_files);
 		assert_eq!(config.log_to_file, expected.log_to_file);
+		assert_eq!(config.payment_log_detail, expected.payment_log_detail);
 	}
 
 	#[test]
@@ -2020,6 +2585,7 @@ mod tests {
 			.unwrap(),
 			alias: Some(parse_alias(args_config.node_alias.as_deref().unwrap()).unwrap()),
 			storage_dir_path: Some(args_config.storage_dir_path.unwrap()),
+			history_dir_path: None,
 			tls_config: Some(TlsConfig {
 				cert_path: Some("/path/to/tls.crt".to_string()),
 				key_path: Some("/path/to/tls.key".to_string()),
@@ -2040,6 +2606,7 @@ mod tests {
 				.unwrap(),
 				address: SocketAddress::from_str("127.0.0.1:39735").unwrap(),
 				token: Some("lsps2-token".to_string()),
+				max_skimmed_fee_msat: Some(5000),
 			}),
 			lsps2_service_config: Some(LSPS2ServiceConfig {
 				require_token: None,
@@ -2067,10 +2634,27 @@ mod tests {
 				proxy_address: SocketAddress::from_str("127.0.0.1:9050").unwrap(),
 			}),
 			hrn_config: HumanReadableNamesConfig::default(),
+			api_keys: vec![],
+			max_outbound_payment_msat: None,
+			payment_history_retention: None,
+			max_offer_quantity: None,
+			deactivate_expired_offers: false,
+			notify_expired_invoices: false,
+			max_description_length: None,
+			lnurl_base_url: None,
+			large_payment_warning_msat: None,
+			min_cltv_expiry_delta: None,
+			announce_channels_by_default: false,
+			min_channel_size_sat: None,
+			reject_reserve_shortfall: false,
+			max_request_body_bytes: DEFAULT_MAX_REQUEST_BODY_BYTES,
+			read_requests_per_minute: None,
+			send_requests_per_minute: None,
 			log_max_size_bytes: 50 * 1024 * 1024,
 			log_rotation_interval_secs: 24 * 60 * 60,
 			log_max_files: 5,
 			log_to_file: false,
+			payment_log_detail: PaymentLogDetail::MetadataOnly,
 		};
 
 		assert_eq!(config.listening_addrs, expected.listening_addrs);
@@ -2323,6 +2907,404 @@ mod tests {
 		assert!(err.to_string().contains("enable_resolution_service"));
 	}
 
+	#[test]
+	fn test_api_keys_config() {
+		let config_file_name = "test_api_keys_config.toml";
+
+		// No `[[api_keys]]` section -> only the API key stored in the data directory is accepted.
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert!(config.api_keys.is_empty());
+
+		let dashboard_api_key = r#"
+				[[api_keys]]
+				name = "dashboard"
+				key = "dashboard-key"
+				scope = "read_only"
+				"#;
+		let pos_api_key = r#"
+				[[api_keys]]
+				name = "pos"
+				key = "pos-key"
+				scope = "send"
+				"#;
+		let toml_config = format!("{}{}", dashboard_api_key, pos_api_key);
+		let config = load_with_extra_toml(config_file_name, &toml_config).unwrap();
+		assert_eq!(
+			config.api_keys,
+			vec![
+				ApiKey {
+					name: "dashboard".to_string(),
+					key: "dashboard-key".to_string(),
+					scope: ApiKeyScope::ReadOnly,
+				},
+				ApiKey {
+					name: "pos".to_string(),
+					key: "pos-key".to_string(),
+					scope: ApiKeyScope::Send,
+				},
+			]
+		);
+
+		for (name, key, scope, expected_err) in [
+			("dashboard", "dashboard-key", "superuser", "Invalid API key scope"),
+			("admin", "admin-key", "read_only", "reserved"),
+			("dashboard", "", "read_only", "empty `key`"),
+			("pos", "other-key", "read_only", "Duplicate API key name"),
+			("other", "pos-key", "read_only", "reuses the key"),
+		] {
+			let toml_config = format!(
+				"{}\n[[api_keys]]\nname = \"{name}\"\nkey = \"{key}\"\nscope = \"{scope}\"\n",
+				pos_api_key
+			);
+			let err = load_with_extra_toml(config_file_name, &toml_config).unwrap_err();
+			assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+			assert!(err.to_string().contains(expected_err), "unexpected error: {}", err);
+		}
+	}
+
+	#[test]
+	fn test_max_outbound_payment_config() {
+		let config_file_name = "test_max_outbound_payment_config.toml";
+		let limit_toml = "[payments]\nmax_outbound_payment_msat = 100000000";
+
+		// No limit is enforced unless one is configured.
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.max_outbound_payment_msat, None);
+
+		let config = load_with_extra_toml(config_file_name, limit_toml).unwrap();
+		assert_eq!(config.max_outbound_payment_msat, Some(100_000_000));
+
+		// The CLI argument overrides the config file.
+		let mut args_config = empty_args_config();
+		args_config.payments_max_outbound_payment_msat = Some(5_000);
+		let config =
+			load_with_extra_toml_and_args(config_file_name, limit_toml, &mut args_config).unwrap();
+		assert_eq!(config.max_outbound_payment_msat, Some(5_000));
+
+		// The limit can be disabled without removing it.
+		args_config.payments_disable_max_outbound_payment = true;
+		let config =
+			load_with_extra_toml_and_args(config_file_name, limit_toml, &mut args_config).unwrap();
+		assert_eq!(config.max_outbound_payment_msat, None);
+
+		let config = load_with_extra_toml(
+			config_file_name,
+			&format!("{}\nenforce_max_outbound_payment = false", limit_toml),
+		)
+		.unwrap();
+		assert_eq!(config.max_outbound_payment_msat, None);
+
+		let err =
+			load_with_extra_toml(config_file_name, "[payments]\nmax_outbound_payment_msat = 0")
+				.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn test_payment_history_retention_config() {
+		let config_file_name = "test_payment_history_retention_config.toml";
+
+		// The whole history is kept unless a limit is configured.
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.payment_history_retention, None);
+
+		let config = load_with_extra_toml(
+			config_file_name,
+			"[payments.history_retention]\nmax_records = 1000",
+		)
+		.unwrap();
+		assert_eq!(
+			config.payment_history_retention,
+			Some(PaymentHistoryRetention { max_age_secs: None, max_records: Some(1000) })
+		);
+
+		let err = load_with_extra_toml(
+			config_file_name,
+			"[payments.history_retention]\nmax_age_secs = 0",
+		)
+		.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn test_max_offer_quantity_config() {
+		let config_file_name = "test_max_offer_quantity_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.max_offer_quantity, None);
+
+		let config =
+			load_with_extra_toml(config_file_name, "[payments]\nmax_offer_quantity = 10").unwrap();
+		assert_eq!(config.max_offer_quantity, Some(10));
+
+		let err = load_with_extra_toml(config_file_name, "[payments]\nmax_offer_quantity = 0")
+			.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn test_deactivate_expired_offers_config() {
+		let config_file_name = "test_deactivate_expired_offers_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert!(!config.deactivate_expired_offers);
+
+		let config =
+			load_with_extra_toml(config_file_name, "[payments]\ndeactivate_expired_offers = true")
+				.unwrap();
+		assert!(config.deactivate_expired_offers);
+	}
+
+	#[test]
+	fn test_notify_expired_invoices_config() {
+		let config_file_name = "test_notify_expired_invoices_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert!(!config.notify_expired_invoices);
+
+		let config =
+			load_with_extra_toml(config_file_name, "[payments]\nnotify_expired_invoices = true")
+				.unwrap();
+		assert!(config.notify_expired_invoices);
+		assert_eq!(config.redacted_entries()["notify_expired_invoices"], "true");
+	}
+
+	#[test]
+	fn test_max_description_length_config() {
+		let config_file_name = "test_max_description_length_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.max_description_length, None);
+
+		let config =
+			load_with_extra_toml(config_file_name, "[payments]\nmax_description_length = 140")
+				.unwrap();
+		assert_eq!(config.max_description_length, Some(140));
+
+		let err = load_with_extra_toml(config_file_name, "[payments]\nmax_description_length = 0")
+			.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn test_lnurl_base_url_config() {
+		let config_file_name = "test_lnurl_base_url_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.lnurl_base_url, None);
+
+		let config = load_with_extra_toml(
+			config_file_name,
+			"[payments]\nlnurl_base_url = \"https://node.example.com/\"",
+		)
+		.unwrap();
+		assert_eq!(config.lnurl_base_url.as_deref(), Some("https://node.example.com"));
+
+		for url in ["http://node.example.com", "node.example.com", "https://"] {
+			let err = load_with_extra_toml(
+				config_file_name,
+				&format!("[payments]\nlnurl_base_url = \"{}\"", url),
+			)
+			.unwrap_err();
+			assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{}", url);
+		}
+		assert!(validate_lnurl_base_url("http://abcdef.onion:8080").is_ok());
+	}
+
+	#[test]
+	fn test_payment_log_detail_config() {
+		let config_file_name = "test_payment_log_detail_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.payment_log_detail, PaymentLogDetail::MetadataOnly);
+
+		let config =
+			load_with_extra_toml(config_file_name, "[log]\npayment_log_detail = \"none\"").unwrap();
+		assert_eq!(config.payment_log_detail, PaymentLogDetail::None);
+		assert_eq!(config.redacted_entries()["payment_log_detail"], "none");
+
+		let err =
+			load_with_extra_toml(config_file_name, "[log]\npayment_log_detail = \"preimages\"")
+				.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn test_large_payment_warning_msat_config() {
+		let config_file_name = "test_large_payment_warning_msat_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.large_payment_warning_msat, None);
+
+		let config = load_with_extra_toml(
+			config_file_name,
+			"[payments]\nlarge_payment_warning_msat = 100000000",
+		)
+		.unwrap();
+		assert_eq!(config.large_payment_warning_msat, Some(100_000_000));
+
+		let err =
+			load_with_extra_toml(config_file_name, "[payments]\nlarge_payment_warning_msat = 0")
+				.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn test_min_cltv_expiry_delta_config() {
+		let config_file_name = "test_min_cltv_expiry_delta_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.min_cltv_expiry_delta, None);
+
+		let config =
+			load_with_extra_toml(config_file_name, "[channels]\nmin_cltv_expiry_delta = 144")
+				.unwrap();
+		assert_eq!(config.min_cltv_expiry_delta, Some(144));
+
+		let err = load_with_extra_toml(config_file_name, "[channels]\nmin_cltv_expiry_delta = 0")
+			.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn test_announce_channels_by_default_config() {
+		let config_file_name = "test_announce_channels_by_default_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert!(!config.announce_channels_by_default);
+
+		let config = load_with_extra_toml(
+			config_file_name,
+			"[channels]\nannounce_channels_by_default = true",
+		)
+		.unwrap();
+		assert!(config.announce_channels_by_default);
+		assert_eq!(config.redacted_entries()["announce_channels_by_default"], "true");
+	}
+
+	#[test]
+	fn test_min_channel_size_sat_config() {
+		let config_file_name = "test_min_channel_size_sat_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.min_channel_size_sat, None);
+
+		let config =
+			load_with_extra_toml(config_file_name, "[channels]\nmin_channel_size_sat = 100000")
+				.unwrap();
+		assert_eq!(config.min_channel_size_sat, Some(100_000));
+		assert_eq!(config.redacted_entries()["min_channel_size_sat"], "Some(100000)");
+	}
+
+	#[test]
+	fn test_reject_reserve_shortfall_config() {
+		let config_file_name = "test_reject_reserve_shortfall_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert!(!config.reject_reserve_shortfall);
+
+		let config =
+			load_with_extra_toml(config_file_name, "[channels]\nreject_reserve_shortfall = true")
+				.unwrap();
+		assert!(config.reject_reserve_shortfall);
+	}
+
+	#[test]
+	fn test_max_request_body_bytes_config() {
+		let config_file_name = "test_max_request_body_bytes_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.max_request_body_bytes, DEFAULT_MAX_REQUEST_BODY_BYTES);
+
+		let config =
+			load_with_extra_toml(config_file_name, "max_request_body_bytes = 4096").unwrap();
+		assert_eq!(config.max_request_body_bytes, 4096);
+
+		let err = load_with_extra_toml(config_file_name, "max_request_body_bytes = 0").unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn test_history_dir_path_config() {
+		let config_file_name = "test_history_dir_path_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.history_dir_path, None);
+
+		let config = load_with_extra_toml(
+			config_file_name,
+			"[storage.disk]\ndir_path = \"/fast\"\nhistory_dir_path = \"/bulk\"",
+		)
+		.unwrap();
+		assert_eq!(config.storage_dir_path.as_deref(), Some("/fast"));
+		assert_eq!(config.history_dir_path.as_deref(), Some("/bulk"));
+	}
+
+	#[test]
+	fn test_rate_limits_config() {
+		let config_file_name = "test_rate_limits_config.toml";
+
+		let config = load_with_extra_toml(config_file_name, "").unwrap();
+		assert_eq!(config.read_requests_per_minute, None);
+		assert_eq!(config.send_requests_per_minute, None);
+
+		let config =
+			load_with_extra_toml(config_file_name, "[rate_limits]\nsend_requests_per_minute = 30")
+				.unwrap();
+		assert_eq!(config.read_requests_per_minute, None);
+		assert_eq!(config.send_requests_per_minute, Some(30));
+
+		let err =
+			load_with_extra_toml(config_file_name, "[rate_limits]\nread_requests_per_minute = 0")
+				.unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	}
+
+	#[test]
+	fn test_redacted_config_entries() {
+		let storage_path = std::env::temp_dir();
+		let config_file_name = "test_redacted_config_entries.toml";
+		fs::write(storage_path.join(config_file_name), DEFAULT_CONFIG).unwrap();
+
+		let mut args_config = empty_args_config();
+		args_config.config_file =
+			Some(storage_path.join(config_file_name).to_string_lossy().to_string());
+
+		let mut config = load_config(&args_config).unwrap();
+		config.metrics_password = Some("metrics-password".to_string());
+		config.api_keys = vec![ApiKey {
+			name: "reader".to_string(),
+			key: "api-key-secret".to_string(),
+			scope: ApiKeyScope::ReadOnly,
+		}];
+		if let Some(lsps2_service_config) = config.lsps2_service_config.as_mut() {
+			lsps2_service_config.require_token = Some("lsps2-service-token".to_string());
+		}
+
+		let entries = config.redacted_entries();
+		let secrets = [
+			"bitcoind-testpassword",
+			"lsps2-token",
+			"lsps2-service-token",
+			"metrics-password",
+			"api-key-secret",
+		];
+		for (key, value) in &entries {
+			for secret in secrets {
+				assert!(!value.contains(secret), "`{}` leaks a secret: {}", key, value);
+			}
+		}
+		assert!(entries["chain_source"].contains(REDACTED));
+		assert!(entries["lsps2_client_config"].contains(REDACTED));
+		assert!(entries["metrics_password"].contains(REDACTED));
+		assert!(entries["api_keys"].contains("reader"));
+
+		// Everything that is not a secret is passed through as is.
+		assert_eq!(entries["network"], "regtest");
+		assert!(entries["chain_source"].contains("bitcoind-testuser"));
+		assert_eq!(entries["log_max_files"], "5");
+	}
+
 	#[test]
 	fn test_parse_dns_server_address() {
 		assert_eq!(
@@ -2730,4 +3712,74 @@ mod tests {
 		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
 		assert!(err.to_string().contains("--force-wallet-full-scan"));
 	}
+
+	#[test]
+	fn test_chain_poll_interval_configures_electrum_and_esplora() {
+		for (config_file_name, chain_config) in [
+			(
+				"test_chain_poll_interval_electrum.toml",
+				r#"
+				[node]
+				network = "regtest"
+
+				[electrum]
+				server_url = "ssl://electrum.blockstream.info:50002"
+				chain_poll_interval_secs = 15
+				"#,
+			),
+			(
+				"test_chain_poll_interval_esplora.toml",
+				r#"
+				[node]
+				network = "regtest"
+
+				[esplora]
+				server_url = "https://mempool.space/api"
+				chain_poll_interval_secs = 15
+				"#,
+			),
+		] {
+			let storage_path = std::env::temp_dir();
+			let chain_config = format!("{}{}", chain_config, lsps2_service_config_for_feature());
+			fs::write(storage_path.join(config_file_name), chain_config).unwrap();
+			let mut args_config = empty_args_config();
+			args_config.config_file =
+				Some(storage_path.join(config_file_name).to_string_lossy().to_string());
+
+			let config = load_config(&args_config).unwrap();
+			let chain_poll_interval_secs = match config.chain_source {
+				ChainSource::Electrum { chain_poll_interval_secs, .. }
+				| ChainSource::Esplora { chain_poll_interval_secs, .. } => chain_poll_interval_secs,
+				ChainSource::Rpc { .. } => panic!("unexpected chain source"),
+			};
+
+			assert_eq!(chain_poll_interval_secs, Some(15));
+		}
+	}
+
+	#[test]
+	fn test_chain_poll_interval_rejects_too_short_interval() {
+		let storage_path = std::env::temp_dir();
+		let config_file_name = "test_chain_poll_interval_too_short.toml";
+		let toml_config = format!(
+			r#"
+			[node]
+			network = "regtest"
+
+			[esplora]
+			server_url = "https://mempool.space/api"
+			chain_poll_interval_secs = 5
+			{}"#,
+			lsps2_service_config_for_feature()
+		);
+		fs::write(storage_path.join(config_file_name), toml_config).unwrap();
+		let mut args_config = empty_args_config();
+		args_config.config_file =
+			Some(storage_path.join(config_file_name).to_string_lossy().to_string());
+
+		let err = load_config(&args_config).unwrap_err();
+
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		assert!(err.to_string().contains("chain_poll_interval_secs"));
+	}
 }
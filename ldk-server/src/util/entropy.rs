@@ -12,24 +12,25 @@ use std::str::FromStr;
 use std::{fs, io};
 
 use ldk_node::bip39::Mnemonic;
-use ldk_node::entropy::{generate_entropy_mnemonic, NodeEntropy};
+use ldk_node::entropy::generate_entropy_mnemonic;
 use log::info;
 
 use crate::util::write_new;
 
 const DEFAULT_MNEMONIC_FILE: &str = "keys_mnemonic";
 
-pub(crate) fn load_or_generate_node_entropy(storage_dir: &Path) -> io::Result<NodeEntropy> {
+/// Reads the BIP39 mnemonic the node's keys are derived from, generating it on first start.
+pub(crate) fn load_or_generate_mnemonic(storage_dir: &Path) -> io::Result<Mnemonic> {
 	let mnemonic_path = storage_dir.join(DEFAULT_MNEMONIC_FILE);
 
-	let mnemonic = if mnemonic_path.exists() {
+	if mnemonic_path.exists() {
 		let raw = fs::read_to_string(&mnemonic_path)?;
 		Mnemonic::from_str(raw.trim()).map_err(|e| {
 			io::Error::new(
 				io::ErrorKind::InvalidData,
 				format!("Invalid BIP39 mnemonic in {}: {}", mnemonic_path.display(), e),
 			)
-		})?
+		})
 	} else {
 		if let Some(parent) = mnemonic_path.parent() {
 			fs::create_dir_all(parent)?;
@@ -40,10 +41,8 @@ pub(crate) fn load_or_generate_node_entropy(storage_dir: &Path) -> io::Result<No
 			"Generated new BIP39 mnemonic at {}. Back up this file securely — it is required to recover on-chain funds.",
 			mnemonic_path.display()
 		);
-		mnemonic
-	};
-
-	Ok(NodeEntropy::from_bip39_mnemonic(mnemonic, None))
+		Ok(mnemonic)
+	}
 }
 
 #[cfg(test)]
@@ -71,7 +70,7 @@ mod tests {
 	fn generates_mnemonic_on_fresh_start() {
 		let dir = tempdir("fresh");
 
-		load_or_generate_node_entropy(&dir).unwrap();
+		load_or_generate_mnemonic(&dir).unwrap();
 
 		let mnemonic_path = dir.join(DEFAULT_MNEMONIC_FILE);
 		assert!(mnemonic_path.exists(), "keys_mnemonic was not created");
@@ -84,7 +83,7 @@ mod tests {
 		assert_eq!(word_count, 24, "expected 24-word mnemonic, got {}", word_count);
 
 		let mtime_before = fs::metadata(&mnemonic_path).unwrap().mtime();
-		load_or_generate_node_entropy(&dir).unwrap();
+		load_or_generate_mnemonic(&dir).unwrap();
 		let mtime_after = fs::metadata(&mnemonic_path).unwrap().mtime();
 		assert_eq!(mtime_before, mtime_after, "mnemonic file was rewritten on second call");
 	}
@@ -96,7 +95,7 @@ mod tests {
 		fs::write(&mnemonic_path, format!("{}\n", KNOWN_MNEMONIC)).unwrap();
 		let bytes_before = fs::read(&mnemonic_path).unwrap();
 
-		load_or_generate_node_entropy(&dir).unwrap();
+		load_or_generate_mnemonic(&dir).unwrap();
 
 		let bytes_after = fs::read(&mnemonic_path).unwrap();
 		assert_eq!(bytes_before, bytes_after, "mnemonic file content changed");
@@ -108,7 +107,7 @@ mod tests {
 		let stale_seed_path = dir.join(STALE_SEED_FILE);
 		fs::write(&stale_seed_path, vec![0x42u8; 64]).unwrap();
 
-		load_or_generate_node_entropy(&dir).unwrap();
+		load_or_generate_mnemonic(&dir).unwrap();
 
 		assert!(dir.join(DEFAULT_MNEMONIC_FILE).exists(), "keys_mnemonic was not created");
 		assert!(stale_seed_path.exists(), "stale keys_seed was removed");
@@ -123,7 +122,7 @@ mod tests {
 		)
 		.unwrap();
 
-		let err = load_or_generate_node_entropy(&dir).unwrap_err();
+		let err = load_or_generate_mnemonic(&dir).unwrap_err();
 		assert_eq!(err.kind(), io::ErrorKind::InvalidData);
 	}
 }
@@ -0,0 +1,195 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::io;
+use std::str::FromStr;
+use std::time::Duration;
+
+use hex::DisplayHex;
+use ldk_node::lightning_invoice::Bolt11Invoice;
+use ldk_node::lightning_types::payment::PaymentHash;
+use ldk_server_grpc::events::{event_envelope, EventEnvelope, InvoiceExpired};
+use log::{debug, error, info};
+use tokio::sync::broadcast;
+
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	INVOICES_PERSISTENCE_PRIMARY_NAMESPACE, INVOICES_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::payment_log::{PaymentLogDetail, PaymentLogLine};
+
+/// How often invoices are checked for having expired unpaid in the background, if enabled.
+pub(crate) const INVOICE_EXPIRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Persists an invoice created by the node, keyed by its payment hash, so that it can be reported
+/// once it expires unpaid.
+///
+/// Failures are only logged, as the invoice was already created and handed out.
+pub(crate) fn track_invoice_expiry(store: &dyn PaginatedKVStore, invoice: &Bolt11Invoice) {
+	let payment_hash = invoice.payment_hash().0.to_lower_hex_string();
+	if let Err(e) = store.write(
+		INVOICES_PERSISTENCE_PRIMARY_NAMESPACE,
+		INVOICES_PERSISTENCE_SECONDARY_NAMESPACE,
+		&payment_hash,
+		invoice.duration_since_epoch().as_secs() as i64,
+		invoice.to_string().as_bytes(),
+	) {
+		error!("Failed to persist invoice {}: {}", payment_hash, e);
+	}
+}
+
+/// Stops tracking the invoices that were paid or whose expiry has passed, and notifies event
+/// subscribers of each invoice that expired unpaid. Returns the payment hashes of those invoices.
+///
+/// As an invoice is no longer tracked once reported, each expiry is reported exactly once.
+/// Records that cannot be decoded are logged and skipped, so they do not hold up the others.
+pub(crate) fn expire_unpaid_invoices(
+	store: &dyn PaginatedKVStore, now: u64, is_paid: impl Fn(&PaymentHash) -> bool,
+	event_sender: &broadcast::Sender<EventEnvelope>, payment_log_detail: PaymentLogDetail,
+) -> io::Result<Vec<String>> {
+	let (primary_namespace, secondary_namespace) =
+		(INVOICES_PERSISTENCE_PRIMARY_NAMESPACE, INVOICES_PERSISTENCE_SECONDARY_NAMESPACE);
+	let mut expired = Vec::new();
+	let mut page_token = None;
+	loop {
+		let list_response = store.list(primary_namespace, secondary_namespace, page_token)?;
+
+		// Listing continues after the last key of the page, so removing keys does not skip any.
+		for key in list_response.keys {
+			let bytes = store.read(primary_namespace, secondary_namespace, &key)?;
+			let invoice = match String::from_utf8(bytes)
+				.ok()
+				.and_then(|invoice| Bolt11Invoice::from_str(&invoice).ok())
+			{
+				Some(invoice) => invoice,
+				None => {
+					error!("Skipping invalid invoice {} while checking for expired invoices", key);
+					continue;
+				},
+			};
+
+			if is_paid(&invoice.payment_hash()) {
+				store.remove(primary_namespace, secondary_namespace, &key)?;
+				continue;
+			}
+			match invoice.expires_at() {
+				Some(expires_at) if expires_at.as_secs() <= now => {},
+				_ => continue,
+			}
+
+			store.remove(primary_namespace, secondary_namespace, &key)?;
+			let mut log_line =
+				PaymentLogLine::new(payment_log_detail, "Invoice expired without being paid")
+					.metadata("hash", &key);
+			if let Some(amount_msat) = invoice.amount_milli_satoshis() {
+				log_line = log_line.amount("amount_msat", amount_msat);
+			}
+			info!("{}", log_line);
+			let event = event_envelope::Event::InvoiceExpired(InvoiceExpired {
+				payment_hash: key.clone(),
+				amount_msat: invoice.amount_milli_satoshis(),
+			});
+			if let Err(e) = event_sender.send(EventEnvelope { event: Some(event) }) {
+				debug!("No event subscribers connected, skipping event: {e}");
+			}
+			expired.push(key);
+		}
+
+		page_token = list_response.next_page_token;
+		if page_token.is_none() {
+			break;
+		}
+	}
+	Ok(expired)
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_node::bitcoin::hashes::{sha256, Hash};
+	use ldk_node::bitcoin::secp256k1::{Secp256k1, SecretKey};
+	use ldk_node::lightning_invoice::{Currency, InvoiceBuilder};
+	use ldk_node::lightning_types::payment::PaymentSecret;
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::random_storage_path;
+	use crate::io::persist::sqlite_store::SqliteStore;
+
+	fn invoice(preimage: &[u8], created_at: u64, expiry_secs: u64) -> Bolt11Invoice {
+		let secret_key = SecretKey::from_slice(&[42; 32]).unwrap();
+		InvoiceBuilder::new(Currency::Regtest)
+			.description("coffee".to_string())
+			.payment_hash(sha256::Hash::hash(preimage))
+			.payment_secret(PaymentSecret([7; 32]))
+			.duration_since_epoch(Duration::from_secs(created_at))
+			.expiry_time(Duration::from_secs(expiry_secs))
+			.min_final_cltv_expiry_delta(144)
+			.amount_milli_satoshis(1_000)
+			.build_signed(|hash| Secp256k1::new().sign_ecdsa_recoverable(hash, &secret_key))
+			.unwrap()
+	}
+
+	#[test]
+	fn unpaid_invoice_past_expiry_emits_one_event() {
+		let dir = random_storage_path();
+		let store = SqliteStore::new(dir.clone(), None, None).unwrap();
+
+		let unpaid = invoice(b"unpaid", 1_000, 600);
+		let paid = invoice(b"paid", 1_000, 600);
+		let later = invoice(b"later", 1_000, 3_600);
+		for invoice in [&unpaid, &paid, &later] {
+			track_invoice_expiry(&store, invoice);
+		}
+		let paid_hash = paid.payment_hash();
+		let is_paid = |payment_hash: &PaymentHash| *payment_hash == paid_hash;
+
+		let (event_sender, mut event_receiver) = broadcast::channel(4);
+		let expired =
+			expire_unpaid_invoices(&store, 2_000, is_paid, &event_sender, PaymentLogDetail::Full)
+				.unwrap();
+
+		let unpaid_hash = unpaid.payment_hash().0.to_lower_hex_string();
+		assert_eq!(expired, vec![unpaid_hash.clone()]);
+		let event = event_receiver.try_recv().unwrap();
+		let expected = InvoiceExpired { payment_hash: unpaid_hash, amount_msat: Some(1_000) };
+		assert_eq!(event.event, Some(event_envelope::Event::InvoiceExpired(expected)));
+		assert!(event_receiver.try_recv().is_err());
+
+		// Only the invoice that has yet to expire is still tracked, so nothing is reported again.
+		let (primary_namespace, secondary_namespace) =
+			(INVOICES_PERSISTENCE_PRIMARY_NAMESPACE, INVOICES_PERSISTENCE_SECONDARY_NAMESPACE);
+		let listed = store.list(primary_namespace, secondary_namespace, None).unwrap();
+		assert_eq!(listed.keys, vec![later.payment_hash().0.to_lower_hex_string()]);
+		let expired =
+			expire_unpaid_invoices(&store, 2_000, is_paid, &event_sender, PaymentLogDetail::Full);
+		assert!(expired.unwrap().is_empty());
+		assert!(event_receiver.try_recv().is_err());
+
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn invalid_invoice_does_not_stop_the_sweep() {
+		let dir = random_storage_path();
+		let store = SqliteStore::new(dir.clone(), None, None).unwrap();
+
+		let (primary_namespace, secondary_namespace) =
+			(INVOICES_PERSISTENCE_PRIMARY_NAMESPACE, INVOICES_PERSISTENCE_SECONDARY_NAMESPACE);
+		store.write(primary_namespace, secondary_namespace, "invalid", 0, b"lnbc1garbage").unwrap();
+		let unpaid = invoice(b"unpaid", 1_000, 600);
+		track_invoice_expiry(&store, &unpaid);
+
+		let (event_sender, _event_receiver) = broadcast::channel(4);
+		let expired =
+			expire_unpaid_invoices(&store, 2_000, |_| false, &event_sender, PaymentLogDetail::Full)
+				.unwrap();
+		assert_eq!(expired, vec![unpaid.payment_hash().0.to_lower_hex_string()]);
+
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+}
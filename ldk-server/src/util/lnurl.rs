@@ -0,0 +1,408 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+//! LNURL-withdraws created via `CreateLnurlWithdraw`, and the endpoints wallets claim them at.
+//!
+//! See LUD-01 and LUD-03 at <https://github.com/lnurl/luds> for the protocol.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use bytes::Bytes;
+use ldk_node::bitcoin::bech32::{self, Bech32, Hrp};
+use ldk_node::lightning::ln::channelmanager::PaymentId;
+use ldk_node::lightning_invoice::Bolt11Invoice;
+use ldk_node::NodeError;
+use ldk_server_grpc::events::{event_envelope, EventEnvelope, LnurlWithdrawClaimed};
+use ldk_server_grpc::types::LnurlWithdrawRecord;
+use log::{debug, error, info};
+use prost::Message;
+use tokio::sync::broadcast;
+
+use crate::api::check_bolt11_payment_metadata;
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	LNURL_WITHDRAWS_PERSISTENCE_PRIMARY_NAMESPACE, LNURL_WITHDRAWS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::service::Context;
+use crate::util::audit::{current_timestamp, record_audit_entry, AuditContext};
+use crate::util::payment_limit::check_outbound_payment_amount;
+use crate::util::payment_log::PaymentLogLine;
+use crate::util::rate_limit::RateLimits;
+
+/// The path below which wallets request the parameters of a withdraw and claim it.
+pub(crate) const LNURL_WITHDRAW_PATH_PREFIX: &str = "/lnurlw/";
+
+const LNURL_HRP: Hrp = Hrp::parse_unchecked("lnurl");
+
+/// The operation claims of withdraws are recorded under in the audit log.
+const LNURL_WITHDRAW_CLAIM_OPERATION: &str = "LnurlWithdrawClaim";
+
+/// The actor claims of withdraws are recorded under in the audit log, as wallets do not use an
+/// API key.
+const LNURL_WITHDRAW_CLAIM_ACTOR: &str = "lnurl";
+
+/// Held while a withdraw is marked as claimed or released again, so that concurrent claims of the
+/// same withdraw cannot both pay out.
+static CLAIM_LOCK: Mutex<()> = Mutex::new(());
+
+/// Returns the URL wallets request the parameters of the given withdraw from.
+pub(crate) fn withdraw_url(base_url: &str, withdraw_id: &str) -> String {
+	format!("{}{}{}", base_url, LNURL_WITHDRAW_PATH_PREFIX, withdraw_id)
+}
+
+/// Encodes the given URL as an LNURL, i.e. bech32 without the usual length limit, in upper case
+/// as that makes for smaller QR codes.
+pub(crate) fn encode_lnurl(url: &str) -> Result<String, bech32::EncodeError> {
+	bech32::encode::<Bech32>(LNURL_HRP, url.as_bytes()).map(|lnurl| lnurl.to_uppercase())
+}
+
+/// Persists the given withdraw record, keyed by its id.
+pub(crate) fn write_withdraw_record(
+	store: &dyn PaginatedKVStore, record: &LnurlWithdrawRecord,
+) -> io::Result<()> {
+	store.write(
+		LNURL_WITHDRAWS_PERSISTENCE_PRIMARY_NAMESPACE,
+		LNURL_WITHDRAWS_PERSISTENCE_SECONDARY_NAMESPACE,
+		&record.id,
+		record.created_at as i64,
+		&record.encode_to_vec(),
+	)
+}
+
+/// Reads the record of the withdraw with the given id, if the withdraw was created by this node.
+pub(crate) fn read_withdraw_record(
+	store: &dyn PaginatedKVStore, withdraw_id: &str,
+) -> io::Result<Option<LnurlWithdrawRecord>> {
+	let bytes = match store.read(
+		LNURL_WITHDRAWS_PERSISTENCE_PRIMARY_NAMESPACE,
+		LNURL_WITHDRAWS_PERSISTENCE_SECONDARY_NAMESPACE,
+		withdraw_id,
+	) {
+		Ok(bytes) => bytes,
+		Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+		Err(e) => return Err(e),
+	};
+	let record = LnurlWithdrawRecord::decode(Bytes::from(bytes))
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+	Ok(Some(record))
+}
+
+/// Answers a request of a wallet below [`LNURL_WITHDRAW_PATH_PREFIX`], returning the JSON body of
+/// the response.
+///
+/// LNURL reports failures in the body rather than through the HTTP status, so every request is
+/// answered with a body, either with the requested data or with an error status and reason.
+///
+/// Callbacks are rate limited per withdraw, and every claim attempt of a known withdraw is
+/// recorded in the audit log under the given correlation id.
+pub(crate) fn handle_lnurl_withdraw_request(
+	context: &Context, rate_limits: &RateLimits, path: &str, query: &str, correlation_id: String,
+	event_sender: &broadcast::Sender<EventEnvelope>,
+) -> String {
+	let base_url = match &context.config.lnurl_base_url {
+		Some(base_url) => base_url,
+		None => return error_json("LNURL-withdraws are not enabled"),
+	};
+	let store = context.paginated_kv_store.as_ref();
+	let rest = path.strip_prefix(LNURL_WITHDRAW_PATH_PREFIX).unwrap_or_default();
+	match rest.split_once('/') {
+		None => match read_withdraw_record(store, rest) {
+			Ok(Some(record)) if record.claimed_at.is_none() => {
+				let callback = format!("{}/callback", withdraw_url(base_url, &record.id));
+				withdraw_request_json(&record, &callback)
+			},
+			Ok(Some(_)) => error_json("Withdraw was already claimed"),
+			Ok(None) => error_json("Unknown withdraw"),
+			Err(e) => {
+				error!("Failed to read LNURL-withdraw {}: {}", rest, e);
+				error_json("Failed to read withdraw")
+			},
+		},
+		Some((withdraw_id, "callback")) => {
+			let (k1, invoice) = match (query_param(query, "k1"), query_param(query, "pr")) {
+				(Some(k1), Some(invoice)) => (k1, invoice),
+				_ => return error_json("Missing `k1` or `pr` parameter"),
+			};
+			if k1 != withdraw_id {
+				return error_json("Unknown withdraw");
+			}
+			match read_withdraw_record(store, withdraw_id) {
+				Ok(Some(_)) => {},
+				Ok(None) => return error_json("Unknown withdraw"),
+				Err(e) => {
+					error!("Failed to read LNURL-withdraw {}: {}", withdraw_id, e);
+					return error_json("Failed to read withdraw");
+				},
+			}
+			if let Err(retry_after) = rate_limits.check_lnurl_callback(withdraw_id, Instant::now())
+			{
+				let retry_after_secs =
+					retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+				return error_json(&format!(
+					"Too many attempts, retry after {} seconds",
+					retry_after_secs
+				));
+			}
+
+			let timestamp = current_timestamp();
+			let max_outbound_payment_msat = context.max_outbound_payment_msat;
+			let pay = |invoice: &Bolt11Invoice| context.node.bolt11_payment().send(invoice, None);
+			let claim = claim_withdraw(
+				store,
+				withdraw_id,
+				invoice,
+				max_outbound_payment_msat,
+				timestamp,
+				pay,
+			);
+
+			// The withdraw id is what allows claiming, so it is not recorded.
+			let audit_context = AuditContext {
+				operation: LNURL_WITHDRAW_CLAIM_OPERATION.to_string(),
+				actor: LNURL_WITHDRAW_CLAIM_ACTOR.to_string(),
+				correlation_id,
+			};
+			let mut request_summary = BTreeMap::new();
+			request_summary.insert("invoice".to_string(), invoice.to_string());
+			if let Ok(claimed) = &claim {
+				request_summary.insert("payment_id".to_string(), claimed.payment_id.clone());
+				request_summary.insert("amount_msat".to_string(), claimed.amount_msat.to_string());
+			}
+			record_audit_entry(
+				store,
+				audit_context,
+				timestamp,
+				request_summary,
+				claim.as_ref().map(|_| ()).map_err(String::as_str),
+			);
+
+			match claim {
+				Ok(claimed) => {
+					// Only the payment is logged, as the withdraw id is what allows claiming.
+					info!(
+						"{}",
+						PaymentLogLine::new(
+							context.config.payment_log_detail,
+							"LNURL-withdraw claimed"
+						)
+						.metadata("payment_id", &claimed.payment_id)
+						.amount("amount_msat", claimed.amount_msat)
+					);
+					let event = event_envelope::Event::LnurlWithdrawClaimed(claimed);
+					if let Err(e) = event_sender.send(EventEnvelope { event: Some(event) }) {
+						debug!("No event subscribers connected, skipping event: {e}");
+					}
+					r#"{"status":"OK"}"#.to_string()
+				},
+				Err(reason) => error_json(&reason),
+			}
+		},
+		Some(_) => error_json("Unknown withdraw"),
+	}
+}
+
+/// Claims the given withdraw by paying the given invoice with `pay`, after checking that the
+/// invoice requests an amount the withdraw allows.
+///
+/// The claim is persisted before paying, so a withdraw is paid out at most once, even across
+/// restarts. If the payment cannot be started at all, the withdraw may be claimed again. Only
+/// marking the withdraw as claimed and releasing it again happen under [`CLAIM_LOCK`], so that
+/// claims of other withdraws do not wait for the payment to be started.
+fn claim_withdraw(
+	store: &dyn PaginatedKVStore, withdraw_id: &str, invoice: &str,
+	max_outbound_payment_msat: Option<u64>, now: u64,
+	pay: impl FnOnce(&Bolt11Invoice) -> Result<PaymentId, NodeError>,
+) -> Result<LnurlWithdrawClaimed, String> {
+	let invoice = Bolt11Invoice::from_str(invoice).map_err(|_| "Invalid invoice".to_string())?;
+	check_bolt11_payment_metadata(&invoice).map_err(|e| e.message)?;
+
+	let amount_msat = invoice
+		.amount_milli_satoshis()
+		.ok_or_else(|| "The invoice must request an amount".to_string())?;
+
+	let claim_guard = CLAIM_LOCK.lock().unwrap();
+	let mut record = match read_withdraw_record(store, withdraw_id) {
+		Ok(Some(record)) => record,
+		Ok(None) => return Err("Unknown withdraw".to_string()),
+		Err(e) => {
+			error!("Failed to read LNURL-withdraw {}: {}", withdraw_id, e);
+			return Err("Failed to read withdraw".to_string());
+		},
+	};
+	if record.claimed_at.is_some() {
+		return Err("Withdraw was already claimed".to_string());
+	}
+
+	if amount_msat < record.min_amount_msat || amount_msat > record.max_amount_msat {
+		return Err(format!(
+			"The invoice amount of {} msat is outside the withdrawable range of {} to {} msat",
+			amount_msat, record.min_amount_msat, record.max_amount_msat
+		));
+	}
+	check_outbound_payment_amount(max_outbound_payment_msat, amount_msat).map_err(|e| e.message)?;
+
+	record.claimed_at = Some(now);
+	write_withdraw_record(store, &record).map_err(|e| {
+		error!("Failed to persist claim of LNURL-withdraw {}: {}", withdraw_id, e);
+		"Failed to claim withdraw".to_string()
+	})?;
+	drop(claim_guard);
+
+	let payment_id = match pay(&invoice) {
+		Ok(payment_id) => payment_id,
+		Err(e) => {
+			let _claim_guard = CLAIM_LOCK.lock().unwrap();
+			record.claimed_at = None;
+			if let Err(e) = write_withdraw_record(store, &record) {
+				error!("Failed to release claim of LNURL-withdraw {}: {}", withdraw_id, e);
+			}
+			return Err(format!("Failed to pay invoice: {}", e));
+		},
+	};
+
+	record.payment_id = Some(payment_id.to_string());
+	if let Err(e) = write_withdraw_record(store, &record) {
+		error!("Failed to persist payment of LNURL-withdraw {}: {}", withdraw_id, e);
+	}
+	Ok(LnurlWithdrawClaimed {
+		withdraw_id: record.id,
+		payment_id: payment_id.to_string(),
+		amount_msat,
+	})
+}
+
+fn withdraw_request_json(record: &LnurlWithdrawRecord, callback: &str) -> String {
+	format!(
+		r#"{{"tag":"withdrawRequest","callback":{},"k1":{},"defaultDescription":{},"minWithdrawable":{},"maxWithdrawable":{}}}"#,
+		json_string(callback),
+		json_string(&record.id),
+		json_string(&record.description),
+		record.min_amount_msat,
+		record.max_amount_msat
+	)
+}
+
+fn error_json(reason: &str) -> String {
+	format!(r#"{{"status":"ERROR","reason":{}}}"#, json_string(reason))
+}
+
+fn json_string(value: &str) -> String {
+	let mut escaped = String::with_capacity(value.len() + 2);
+	escaped.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => escaped.push_str("\\\""),
+			'\\' => escaped.push_str("\\\\"),
+			c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+			c => escaped.push(c),
+		}
+	}
+	escaped.push('"');
+	escaped
+}
+
+/// Returns the value of the given query parameter. Values are not percent-decoded, as neither
+/// withdraw ids nor invoices contain characters that would be encoded.
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+	query
+		.split('&')
+		.filter_map(|param| param.split_once('='))
+		.find(|(key, _)| *key == name)
+		.map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use ldk_node::bitcoin::hashes::{sha256, Hash};
+	use ldk_node::bitcoin::secp256k1::{Secp256k1, SecretKey};
+	use ldk_node::lightning_invoice::{Currency, InvoiceBuilder};
+	use ldk_node::lightning_types::payment::PaymentSecret;
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::random_storage_path;
+	use crate::io::persist::sqlite_store::SqliteStore;
+
+	fn invoice(amount_msat: u64) -> String {
+		let secret_key = SecretKey::from_slice(&[42; 32]).unwrap();
+		InvoiceBuilder::new(Currency::Regtest)
+			.description("withdrawal".to_string())
+			.payment_hash(sha256::Hash::hash(&amount_msat.to_be_bytes()))
+			.payment_secret(PaymentSecret([7; 32]))
+			.current_timestamp()
+			.expiry_time(Duration::from_secs(600))
+			.min_final_cltv_expiry_delta(144)
+			.amount_milli_satoshis(amount_msat)
+			.build_signed(|hash| Secp256k1::new().sign_ecdsa_recoverable(hash, &secret_key))
+			.unwrap()
+			.to_string()
+	}
+
+	#[test]
+	fn encodes_lnurls() {
+		let url = "https://service.com/api?q=3fc3645b439ce8e7f2553a69e5267081d96dcd340693afabe04be7b0ccd178df";
+		assert_eq!(
+			encode_lnurl(url).unwrap(),
+			"LNURL1DP68GURN8GHJ7UM9WFMXJCM99E3K7MF0V9CXJ0M385EKVCENXC6R2C35XVUKXEFCV5MKVV34X5EKZD3EV56NYD3HXQURZEPEXEJXXEPNXSCRVWFNV9NXZCN9XQ6XYEFHVGCXXCMYXYMNSERXFQ5FNS"
+		);
+	}
+
+	#[test]
+	fn withdraw_is_claimed_once_within_its_range() {
+		let dir = random_storage_path();
+		let store = SqliteStore::new(dir.clone(), None, None).unwrap();
+
+		let record = LnurlWithdrawRecord {
+			id: "ab".repeat(32),
+			created_at: 1_000,
+			min_amount_msat: 1_000,
+			max_amount_msat: 5_000,
+			description: "faucet".to_string(),
+			claimed_at: None,
+			payment_id: None,
+		};
+		write_withdraw_record(&store, &record).unwrap();
+		let id = record.id.as_str();
+		let pay = |_: &Bolt11Invoice| Ok(PaymentId([1; 32]));
+
+		let err = claim_withdraw(&store, id, &invoice(6_000), None, 2_000, pay).unwrap_err();
+		assert!(err.contains("outside the withdrawable range"), "{}", err);
+		let err = claim_withdraw(&store, id, &invoice(5_000), Some(4_000), 2_000, pay).unwrap_err();
+		assert!(err.contains("exceeds"), "{}", err);
+
+		// A payment that cannot be started leaves the withdraw claimable.
+		let failing_pay = |_: &Bolt11Invoice| Err(NodeError::PaymentSendingFailed);
+		assert!(claim_withdraw(&store, id, &invoice(5_000), None, 2_000, failing_pay).is_err());
+		assert_eq!(read_withdraw_record(&store, id).unwrap().unwrap().claimed_at, None);
+
+		let claimed = claim_withdraw(&store, id, &invoice(5_000), None, 2_000, pay).unwrap();
+		assert_eq!(claimed.withdraw_id, record.id);
+		assert_eq!(claimed.amount_msat, 5_000);
+		let persisted = read_withdraw_record(&store, id).unwrap().unwrap();
+		assert_eq!(persisted.claimed_at, Some(2_000));
+		assert_eq!(persisted.payment_id, Some(claimed.payment_id));
+
+		let err = claim_withdraw(&store, id, &invoice(2_000), None, 2_000, pay).unwrap_err();
+		assert_eq!(err, "Withdraw was already claimed");
+
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn escapes_json_strings() {
+		assert_eq!(json_string("a \"quoted\"\\n\nline"), r#""a \"quoted\"\\n\u000aline""#);
+		assert_eq!(query_param("k1=ab&pr=lnbcrt1", "pr"), Some("lnbcrt1"));
+		assert_eq!(query_param("k1=ab", "pr"), None);
+	}
+}
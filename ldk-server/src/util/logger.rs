@@ -319,3 +319,91 @@ impl Log for LoggerWrapper {
 		self.0.flush()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::random_storage_path;
+
+	fn test_logger(log_max_size_bytes: usize, log_max_files: usize) -> ServerLogger {
+		let dir = random_storage_path();
+		fs::create_dir(&dir).unwrap();
+
+		let log_file_path = dir.join("ldk-server.log");
+		let state = LoggerState {
+			file: LineWriter::new(open_log_file(&log_file_path).unwrap()),
+			bytes_written: 0,
+			created_at: SystemTime::now(),
+			log_max_size_bytes,
+			log_rotation_interval_secs: 0,
+			log_max_files,
+		};
+		ServerLogger {
+			level: LevelFilter::Info,
+			state: Some(Mutex::new(state)),
+			log_file_path: Some(log_file_path),
+		}
+	}
+
+	fn log_message(logger: &ServerLogger, message: &str) {
+		logger.log(
+			&Record::builder()
+				.args(format_args!("{}", message))
+				.level(Level::Info)
+				.target("test")
+				.build(),
+		);
+	}
+
+	fn rotated_logs(log_file_path: &Path) -> Vec<PathBuf> {
+		let prefix = format!("{}.", log_file_path.file_name().unwrap().to_str().unwrap());
+		let mut rotated: Vec<_> = fs::read_dir(log_file_path.parent().unwrap())
+			.unwrap()
+			.map(|entry| entry.unwrap().path())
+			.filter(|path| path.file_name().unwrap().to_str().unwrap().starts_with(&prefix))
+			.collect();
+		rotated.sort();
+		rotated
+	}
+
+	#[test]
+	fn writing_past_max_size_rotates_log_file() {
+		let logger = test_logger(200, 5);
+		let log_file_path = logger.log_file_path.clone().unwrap();
+
+		log_message(&logger, "first");
+		assert!(rotated_logs(&log_file_path).is_empty());
+
+		log_message(&logger, &"x".repeat(200));
+		logger.flush();
+
+		let rotated = rotated_logs(&log_file_path);
+		assert_eq!(rotated.len(), 1);
+		assert!(fs::read_to_string(&rotated[0]).unwrap().contains("first"));
+		let current = fs::read_to_string(&log_file_path).unwrap();
+		assert!(!current.contains("first"));
+		assert!(current.contains(&"x".repeat(200)));
+
+		fs::remove_dir_all(log_file_path.parent().unwrap()).unwrap();
+	}
+
+	#[test]
+	fn rotation_only_retains_max_files() {
+		let logger = test_logger(200, 2);
+		let log_file_path = logger.log_file_path.clone().unwrap();
+		for name in ["2020-01-01T00-00-00Z", "2020-01-02T00-00-00Z", "2020-01-03T00-00-00Z"] {
+			let mut path = log_file_path.clone().into_os_string();
+			path.push(".");
+			path.push(name);
+			fs::write(path, "old").unwrap();
+		}
+
+		log_message(&logger, "first");
+		log_message(&logger, &"x".repeat(200));
+
+		assert_eq!(rotated_logs(&log_file_path).len(), 2);
+		assert!(log_file_path.exists());
+
+		fs::remove_dir_all(log_file_path.parent().unwrap()).unwrap();
+	}
+}
@@ -7,11 +7,22 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
+pub(crate) mod audit;
+pub(crate) mod auth;
+pub(crate) mod channel_policy;
 pub(crate) mod config;
 pub(crate) mod entropy;
+pub(crate) mod invoices;
+pub(crate) mod lnurl;
 pub(crate) mod logger;
 pub(crate) mod metrics;
+pub(crate) mod offers;
+pub(crate) mod payment_history;
+pub(crate) mod payment_limit;
+pub(crate) mod payment_log;
 pub(crate) mod proto_adapter;
+pub(crate) mod rate_limit;
+pub(crate) mod routing_stats;
 pub(crate) mod systemd;
 pub(crate) mod tls;
 
@@ -0,0 +1,285 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::io;
+use std::str::FromStr;
+use std::time::Duration;
+
+use bytes::Bytes;
+use ldk_node::lightning::offers::offer::{Offer, Quantity};
+use ldk_server_grpc::events::{event_envelope, EventEnvelope, OfferExpired};
+use ldk_server_grpc::types::OfferRecord;
+use log::{debug, info};
+use prost::Message;
+use tokio::sync::broadcast;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InvalidRequestError;
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	OFFERS_PERSISTENCE_PRIMARY_NAMESPACE, OFFERS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+
+/// How often expired offers are deactivated in the background, if enabled.
+pub(crate) const OFFER_EXPIRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Persists the given offer record, keyed by its offer id.
+///
+/// Records are ordered by their creation time, which is kept when an existing record is updated.
+pub(crate) fn write_offer_record(
+	store: &dyn PaginatedKVStore, record: &OfferRecord,
+) -> io::Result<()> {
+	store.write(
+		OFFERS_PERSISTENCE_PRIMARY_NAMESPACE,
+		OFFERS_PERSISTENCE_SECONDARY_NAMESPACE,
+		&record.offer_id,
+		record.created_at as i64,
+		&record.encode_to_vec(),
+	)
+}
+
+/// Reads the record of the offer with the given id, if the offer was created by this node.
+pub(crate) fn read_offer_record(
+	store: &dyn PaginatedKVStore, offer_id: &str,
+) -> io::Result<Option<OfferRecord>> {
+	let bytes = match store.read(
+		OFFERS_PERSISTENCE_PRIMARY_NAMESPACE,
+		OFFERS_PERSISTENCE_SECONDARY_NAMESPACE,
+		offer_id,
+	) {
+		Ok(bytes) => bytes,
+		Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+		Err(e) => return Err(e),
+	};
+	let record = OfferRecord::decode(Bytes::from(bytes))
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+	Ok(Some(record))
+}
+
+/// Deactivates the active offers whose absolute expiry has passed, and notifies event subscribers
+/// of each. Returns the ids of the deactivated offers.
+pub(crate) fn deactivate_expired_offers(
+	store: &dyn PaginatedKVStore, now: u64, event_sender: &broadcast::Sender<EventEnvelope>,
+) -> io::Result<Vec<String>> {
+	let mut deactivated = Vec::new();
+	let mut page_token = None;
+	loop {
+		let list_response = store.list(
+			OFFERS_PERSISTENCE_PRIMARY_NAMESPACE,
+			OFFERS_PERSISTENCE_SECONDARY_NAMESPACE,
+			page_token,
+		)?;
+
+		for key in list_response.keys {
+			let mut record = match read_offer_record(store, &key)? {
+				Some(record) if record.active => record,
+				_ => continue,
+			};
+			let expired_at = match offer_expiry(&record.offer) {
+				Some(expired_at) if expired_at <= now => expired_at,
+				_ => continue,
+			};
+
+			// Updating a record keeps its position, so the listing can continue past it.
+			record.active = false;
+			write_offer_record(store, &record)?;
+			info!("Deactivated offer {} as it expired at {}", record.offer_id, expired_at);
+			let event = event_envelope::Event::OfferExpired(OfferExpired {
+				offer_id: record.offer_id.clone(),
+				expired_at,
+			});
+			if let Err(e) = event_sender.send(EventEnvelope { event: Some(event) }) {
+				debug!("No event subscribers connected, skipping event: {e}");
+			}
+			deactivated.push(record.offer_id);
+		}
+
+		page_token = list_response.next_page_token;
+		if page_token.is_none() {
+			break;
+		}
+	}
+	Ok(deactivated)
+}
+
+/// Returns the absolute expiry of the given encoded offer, in seconds since the Unix epoch.
+fn offer_expiry(offer: &str) -> Option<u64> {
+	Offer::from_str(offer).ok()?.absolute_expiry().map(|expiry| expiry.as_secs())
+}
+
+/// Rejects paying an offer whose absolute expiry has passed.
+pub(crate) fn check_offer_not_expired(offer: &Offer, now: u64) -> Result<(), LdkServerError> {
+	match offer.absolute_expiry() {
+		Some(expiry) if expiry.as_secs() <= now => Err(LdkServerError::new(
+			InvalidRequestError,
+			format!("Offer expired at {}", expiry.as_secs()),
+		)),
+		_ => Ok(()),
+	}
+}
+
+/// Rejects creating an offer that supports a larger quantity than the configured maximum, if any.
+pub(crate) fn check_offer_quantity_limit(
+	max_offer_quantity: Option<u64>, quantity: Option<u64>,
+) -> Result<(), LdkServerError> {
+	match (max_offer_quantity, quantity) {
+		(Some(max_offer_quantity), Some(quantity)) if quantity > max_offer_quantity => {
+			Err(LdkServerError::new(
+				InvalidRequestError,
+				format!(
+					"Offer quantity of {} exceeds configured maximum of {}",
+					quantity, max_offer_quantity
+				),
+			))
+		},
+		_ => Ok(()),
+	}
+}
+
+/// Rejects paying an offer for a quantity of items it does not support.
+pub(crate) fn check_offer_quantity(
+	offer: &Offer, quantity: Option<u64>,
+) -> Result<(), LdkServerError> {
+	let quantity = match quantity {
+		Some(quantity) => quantity,
+		None => return Ok(()),
+	};
+	let message = match offer.supported_quantity() {
+		Quantity::One => "Offer does not support a quantity".to_string(),
+		_ if quantity == 0 => "Quantity must be greater than 0".to_string(),
+		Quantity::Bounded(max_quantity) if quantity > max_quantity.get() => format!(
+			"Quantity of {} exceeds the offer's maximum quantity of {}",
+			quantity, max_quantity
+		),
+		Quantity::Bounded(_) | Quantity::Unbounded => return Ok(()),
+	};
+	Err(LdkServerError::new(InvalidRequestError, message))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::num::NonZeroU64;
+
+	use ldk_node::bitcoin::secp256k1::{PublicKey, Secp256k1, SecretKey};
+	use ldk_node::lightning::offers::offer::OfferBuilder;
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::random_storage_path;
+	use crate::io::persist::sqlite_store::SqliteStore;
+
+	fn offer(quantity: Quantity) -> Offer {
+		let secret_key = SecretKey::from_slice(&[42; 32]).unwrap();
+		let signing_pubkey = PublicKey::from_secret_key(&Secp256k1::new(), &secret_key);
+		OfferBuilder::new(signing_pubkey)
+			.amount_msats(1_000)
+			.supported_quantity(quantity)
+			.build()
+			.unwrap()
+	}
+
+	#[test]
+	fn creating_offer_above_configured_quantity_is_rejected() {
+		let err = check_offer_quantity_limit(Some(10), Some(11)).unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+		assert!(err.message.contains("exceeds configured maximum of 10"));
+
+		assert!(check_offer_quantity_limit(Some(10), Some(10)).is_ok());
+		assert!(check_offer_quantity_limit(Some(10), None).is_ok());
+		assert!(check_offer_quantity_limit(None, Some(u64::MAX)).is_ok());
+	}
+
+	#[test]
+	fn paying_offer_above_its_quantity_is_rejected() {
+		let bounded = offer(Quantity::Bounded(NonZeroU64::new(5).unwrap()));
+		let err = check_offer_quantity(&bounded, Some(6)).unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+		assert!(err.message.contains("exceeds the offer's maximum quantity of 5"));
+		assert!(check_offer_quantity(&bounded, Some(5)).is_ok());
+		assert!(check_offer_quantity(&bounded, Some(0)).is_err());
+
+		assert!(check_offer_quantity(&offer(Quantity::Unbounded), Some(1_000)).is_ok());
+		assert!(check_offer_quantity(&offer(Quantity::One), Some(2)).is_err());
+		assert!(check_offer_quantity(&offer(Quantity::One), None).is_ok());
+	}
+
+	#[test]
+	fn updating_offer_record_keeps_creation_order() {
+		let dir = random_storage_path();
+		let store = SqliteStore::new(dir.clone(), None, None).unwrap();
+
+		let record = |offer_id: &str, created_at| OfferRecord {
+			offer: format!("lno1{}", offer_id),
+			offer_id: offer_id.to_string(),
+			created_at,
+			amount_msat: None,
+			active: true,
+		};
+		write_offer_record(&store, &record("aa", 1)).unwrap();
+		write_offer_record(&store, &record("bb", 2)).unwrap();
+		assert_eq!(read_offer_record(&store, "cc").unwrap(), None);
+
+		let mut deactivated = read_offer_record(&store, "aa").unwrap().unwrap();
+		deactivated.active = false;
+		write_offer_record(&store, &deactivated).unwrap();
+
+		assert_eq!(read_offer_record(&store, "aa").unwrap(), Some(deactivated));
+		let (primary_namespace, secondary_namespace) =
+			(OFFERS_PERSISTENCE_PRIMARY_NAMESPACE, OFFERS_PERSISTENCE_SECONDARY_NAMESPACE);
+		let listed = store.list(primary_namespace, secondary_namespace, None).unwrap();
+		assert_eq!(listed.keys, vec!["bb".to_string(), "aa".to_string()]);
+
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn expired_offers_are_deactivated_and_unpayable() {
+		let dir = random_storage_path();
+		let store = SqliteStore::new(dir.clone(), None, None).unwrap();
+
+		let secret_key = SecretKey::from_slice(&[42; 32]).unwrap();
+		let signing_pubkey = PublicKey::from_secret_key(&Secp256k1::new(), &secret_key);
+		let offer = |expiry_secs: Option<u64>| {
+			let mut builder = OfferBuilder::new(signing_pubkey).amount_msats(1_000);
+			if let Some(expiry_secs) = expiry_secs {
+				builder = builder.absolute_expiry(Duration::from_secs(expiry_secs));
+			}
+			builder.build().unwrap()
+		};
+		let record = |offer_id: &str, offer: &Offer| OfferRecord {
+			offer: offer.to_string(),
+			offer_id: offer_id.to_string(),
+			created_at: 1,
+			amount_msat: Some(1_000),
+			active: true,
+		};
+		let expired = offer(Some(1_000));
+		write_offer_record(&store, &record("expired", &expired)).unwrap();
+		write_offer_record(&store, &record("later", &offer(Some(3_000)))).unwrap();
+		write_offer_record(&store, &record("unbounded", &offer(None))).unwrap();
+
+		let (event_sender, mut event_receiver) = broadcast::channel(2);
+		let deactivated = deactivate_expired_offers(&store, 2_000, &event_sender).unwrap();
+
+		assert_eq!(deactivated, vec!["expired".to_string()]);
+		assert!(!read_offer_record(&store, "expired").unwrap().unwrap().active);
+		assert!(read_offer_record(&store, "later").unwrap().unwrap().active);
+		assert!(read_offer_record(&store, "unbounded").unwrap().unwrap().active);
+		let event = event_receiver.try_recv().unwrap();
+		let expected = OfferExpired { offer_id: "expired".to_string(), expired_at: 1_000 };
+		assert_eq!(event.event, Some(event_envelope::Event::OfferExpired(expected)));
+		assert!(deactivate_expired_offers(&store, 2_000, &event_sender).unwrap().is_empty());
+
+		let err = check_offer_not_expired(&expired, 2_000).unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+		assert_eq!(err.message, "Offer expired at 1000");
+		assert!(check_offer_not_expired(&expired, 999).is_ok());
+
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+}
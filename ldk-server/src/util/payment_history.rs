@@ -0,0 +1,191 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::io;
+use std::time::Duration;
+
+use bytes::Bytes;
+use ldk_server_grpc::events::{event_envelope, EventEnvelope, PaymentsPruned};
+use ldk_server_grpc::types::{Payment, PaymentStatus};
+use log::{debug, info};
+use prost::Message;
+use tokio::sync::broadcast;
+
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE, PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::config::PaymentHistoryRetention;
+
+/// How often the configured payment history retention is applied in the background.
+pub(crate) const PAYMENT_PRUNING_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Removes the payments falling outside the given retention from the payment history, and
+/// notifies event subscribers if any were removed.
+pub(crate) fn prune_payments(
+	store: &dyn PaginatedKVStore, retention: &PaymentHistoryRetention, now: u64,
+	event_sender: &broadcast::Sender<EventEnvelope>,
+) -> io::Result<PaymentsPruned> {
+	let payments = read_payment_history(store)?;
+	let prunable = select_prunable_payments(&payments, retention, now);
+	for payment_id in &prunable {
+		store.remove(
+			PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+			PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+			payment_id,
+		)?;
+	}
+
+	let summary = PaymentsPruned {
+		pruned_count: prunable.len() as u64,
+		remaining_count: (payments.len() - prunable.len()) as u64,
+	};
+	if summary.pruned_count > 0 {
+		info!(
+			"Pruned {} payments from the payment history, {} remain",
+			summary.pruned_count, summary.remaining_count
+		);
+		let event = event_envelope::Event::PaymentsPruned(summary.clone());
+		if let Err(e) = event_sender.send(EventEnvelope { event: Some(event) }) {
+			debug!("No event subscribers connected, skipping event: {e}");
+		}
+	}
+	Ok(summary)
+}
+
+/// Reads the whole payment history, newest first.
+fn read_payment_history(store: &dyn PaginatedKVStore) -> io::Result<Vec<Payment>> {
+	let mut payments = Vec::new();
+	let mut page_token = None;
+	loop {
+		let list_response = store.list(
+			PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+			PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+			page_token,
+		)?;
+
+		for key in list_response.keys {
+			let payment_bytes = store.read(
+				PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+				PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+				&key,
+			)?;
+			let payment = Payment::decode(Bytes::from(payment_bytes))
+				.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+			payments.push(payment);
+		}
+
+		page_token = list_response.next_page_token;
+		if page_token.is_none() {
+			break;
+		}
+	}
+	Ok(payments)
+}
+
+/// Returns the ids of the payments to prune from the given history, which is ordered newest first.
+///
+/// Pending payments are never pruned, but still count towards `max_records`.
+fn select_prunable_payments<'a>(
+	payments: &'a [Payment], retention: &PaymentHistoryRetention, now: u64,
+) -> Vec<&'a str> {
+	payments
+		.iter()
+		.enumerate()
+		.filter(|(index, payment)| {
+			let is_pending = payment.status == PaymentStatus::Pending as i32;
+			let too_old = retention.max_age_secs.is_some_and(|max_age_secs| {
+				now.saturating_sub(payment.latest_update_timestamp) > max_age_secs
+			});
+			let too_many =
+				retention.max_records.is_some_and(|max_records| *index as u64 >= max_records);
+			!is_pending && (too_old || too_many)
+		})
+		.map(|(_, payment)| payment.id.as_str())
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::random_storage_path;
+	use crate::io::persist::sqlite_store::SqliteStore;
+
+	const NOW: u64 = 1_000_000;
+
+	fn payment(id: &str, status: PaymentStatus, latest_update_timestamp: u64) -> Payment {
+		Payment {
+			id: id.to_string(),
+			status: status as i32,
+			latest_update_timestamp,
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn pending_and_recent_payments_survive_pruning() {
+		let payments = vec![
+			payment("recent", PaymentStatus::Succeeded, NOW - 10),
+			payment("old-pending", PaymentStatus::Pending, NOW - 10_000),
+			payment("old-failed", PaymentStatus::Failed, NOW - 10_000),
+			payment("old-succeeded", PaymentStatus::Succeeded, NOW - 20_000),
+		];
+
+		let retention = PaymentHistoryRetention { max_age_secs: Some(1_000), max_records: None };
+		assert_eq!(
+			select_prunable_payments(&payments, &retention, NOW),
+			vec!["old-failed", "old-succeeded"]
+		);
+
+		let retention = PaymentHistoryRetention { max_age_secs: None, max_records: Some(1) };
+		assert_eq!(
+			select_prunable_payments(&payments, &retention, NOW),
+			vec!["old-failed", "old-succeeded"]
+		);
+
+		let retention = PaymentHistoryRetention { max_age_secs: None, max_records: Some(4) };
+		assert!(select_prunable_payments(&payments, &retention, NOW).is_empty());
+	}
+
+	#[test]
+	fn pruning_removes_payments_from_store_and_emits_summary() {
+		let dir = random_storage_path();
+		let store = SqliteStore::new(dir.clone(), None, None).unwrap();
+		let payments = [
+			payment("old-failed", PaymentStatus::Failed, NOW - 10_000),
+			payment("old-pending", PaymentStatus::Pending, NOW - 10_000),
+		];
+		for (time, payment) in payments.iter().enumerate() {
+			store
+				.write(
+					PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+					PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+					&payment.id,
+					time as i64,
+					&payment.encode_to_vec(),
+				)
+				.unwrap();
+		}
+
+		let (event_sender, mut event_receiver) = broadcast::channel(1);
+		let retention = PaymentHistoryRetention { max_age_secs: Some(1_000), max_records: None };
+		let summary = prune_payments(&store, &retention, NOW, &event_sender).unwrap();
+
+		assert_eq!(summary, PaymentsPruned { pruned_count: 1, remaining_count: 1 });
+		let (primary_namespace, secondary_namespace) =
+			(PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE, PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE);
+		let err = store.read(primary_namespace, secondary_namespace, "old-failed").unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::NotFound);
+		assert!(store.read(primary_namespace, secondary_namespace, "old-pending").is_ok());
+		let event = event_receiver.try_recv().unwrap();
+		assert_eq!(event.event, Some(event_envelope::Event::PaymentsPruned(summary)));
+
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+}
@@ -0,0 +1,183 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::str::FromStr;
+
+use ldk_node::bitcoin::{Amount, Denomination};
+use ldk_node::lightning::offers::offer::{self, Offer};
+use ldk_node::lightning_invoice::Bolt11Invoice;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InvalidRequestError;
+use crate::util::auth::{ApiKey, ApiKeyScope};
+
+/// Returns the outbound payment limit that applies to requests authenticated with the given key.
+///
+/// Keys with the [`ApiKeyScope::Admin`] scope are trusted to exceed the configured limit.
+pub(crate) fn outbound_payment_limit_msat(
+	max_outbound_payment_msat: Option<u64>, api_key: &ApiKey,
+) -> Option<u64> {
+	match api_key.scope {
+		ApiKeyScope::Admin => None,
+		ApiKeyScope::ReadOnly | ApiKeyScope::Send => max_outbound_payment_msat,
+	}
+}
+
+/// Rejects a payment whose amount exceeds the given limit, if any.
+pub(crate) fn check_outbound_payment_amount(
+	limit_msat: Option<u64>, amount_msat: u64,
+) -> Result<(), LdkServerError> {
+	match limit_msat {
+		Some(limit_msat) if amount_msat > limit_msat => Err(LdkServerError::new(
+			InvalidRequestError,
+			format!(
+				"Payment amount of {} msat exceeds configured limit of {} msat",
+				amount_msat, limit_msat
+			),
+		)),
+		_ => Ok(()),
+	}
+}
+
+/// Like [`check_outbound_payment_amount`], but for payments whose amount may not be known up
+/// front. Such payments are rejected while a limit is in place, as it could not be enforced.
+pub(crate) fn check_optional_outbound_payment_amount(
+	limit_msat: Option<u64>, amount_msat: Option<u64>,
+) -> Result<(), LdkServerError> {
+	match (limit_msat, amount_msat) {
+		(None, _) => Ok(()),
+		(Some(_), Some(amount_msat)) => check_outbound_payment_amount(limit_msat, amount_msat),
+		(Some(_), None) => Err(LdkServerError::new(
+			InvalidRequestError,
+			"Payment amount could not be determined, so the configured limit cannot be \
+			enforced. Please specify `amount_msat`.",
+		)),
+	}
+}
+
+/// Rejects paying a zero-amount invoice an amount above the configured warning threshold, unless
+/// the caller confirmed it, as a guard against mistyped amounts.
+pub(crate) fn check_large_payment_amount(
+	warning_msat: Option<u64>, amount_msat: u64, confirmed: bool,
+) -> Result<(), LdkServerError> {
+	match warning_msat {
+		Some(warning_msat) if amount_msat > warning_msat && !confirmed => Err(LdkServerError::new(
+			InvalidRequestError,
+			format!(
+				"Payment amount of {} msat exceeds the large payment threshold of {} msat. Set \
+				`confirm_large` to send it anyway.",
+				amount_msat, warning_msat
+			),
+		)),
+		_ => Ok(()),
+	}
+}
+
+/// Returns the total amount that paying the given offer would send, if the offer specifies an
+/// amount in bitcoin.
+pub(crate) fn offer_amount_msat(offer: &Offer, quantity: Option<u64>) -> Option<u64> {
+	match offer.amount()? {
+		offer::Amount::Bitcoin { amount_msats } => {
+			Some(amount_msats.saturating_mul(quantity.unwrap_or(1)))
+		},
+		offer::Amount::Currency { .. } => None,
+	}
+}
+
+/// Returns the amount requested by a payment URI, BOLT11 invoice or BOLT12 offer.
+///
+/// If a BIP 21 URI carries several amounts (e.g. an on-chain amount and a Lightning invoice), the
+/// largest one is returned.
+pub(crate) fn payment_uri_amount_msat(uri: &str) -> Option<u64> {
+	let uri = uri.trim();
+	if let Ok(invoice) = Bolt11Invoice::from_str(uri) {
+		return invoice.amount_milli_satoshis();
+	}
+	if let Ok(offer) = Offer::from_str(uri) {
+		return offer_amount_msat(&offer, None);
+	}
+
+	let (scheme, rest) = uri.split_once(':')?;
+	if !scheme.eq_ignore_ascii_case("bitcoin") {
+		return None;
+	}
+	let (_, query) = rest.split_once('?')?;
+	query
+		.split('&')
+		.filter_map(|param| param.split_once('='))
+		.filter_map(|(key, value)| match key.to_ascii_lowercase().as_str() {
+			"amount" => Amount::from_str_in(value, Denomination::Bitcoin)
+				.ok()
+				.map(|amount| amount.to_sat().saturating_mul(1000)),
+			"lightning" => Bolt11Invoice::from_str(value).ok()?.amount_milli_satoshis(),
+			"lno" => offer_amount_msat(&Offer::from_str(value).ok()?, None),
+			_ => None,
+		})
+		.max()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn api_key(scope: ApiKeyScope) -> ApiKey {
+		ApiKey { name: "test".to_string(), key: "secret".to_string(), scope }
+	}
+
+	#[test]
+	fn over_limit_send_is_rejected_for_non_admin_key() {
+		let limit = outbound_payment_limit_msat(Some(100_000), &api_key(ApiKeyScope::Send));
+
+		let err = check_outbound_payment_amount(limit, 100_001).unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+		assert!(err.message.contains("exceeds configured limit of 100000 msat"));
+
+		assert!(check_outbound_payment_amount(limit, 100_000).is_ok());
+	}
+
+	#[test]
+	fn admin_key_may_exceed_limit() {
+		let limit = outbound_payment_limit_msat(Some(100_000), &api_key(ApiKeyScope::Admin));
+
+		assert_eq!(limit, None);
+		assert!(check_outbound_payment_amount(limit, 100_001).is_ok());
+	}
+
+	#[test]
+	fn unknown_amount_is_rejected_only_when_limited() {
+		assert!(check_optional_outbound_payment_amount(None, None).is_ok());
+		assert!(check_optional_outbound_payment_amount(Some(1_000), Some(1_000)).is_ok());
+		assert!(check_optional_outbound_payment_amount(Some(1_000), Some(1_001)).is_err());
+		assert!(check_optional_outbound_payment_amount(Some(1_000), None).is_err());
+	}
+
+	#[test]
+	fn large_payment_requires_confirmation() {
+		let err = check_large_payment_amount(Some(1_000), 1_001, false).unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+		assert!(err.message.contains("exceeds the large payment threshold of 1000 msat"));
+
+		assert!(check_large_payment_amount(Some(1_000), 1_001, true).is_ok());
+		assert!(check_large_payment_amount(Some(1_000), 1_000, false).is_ok());
+		assert!(check_large_payment_amount(None, u64::MAX, false).is_ok());
+	}
+
+	#[test]
+	fn parses_bip21_amount() {
+		let uri = "bitcoin:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq?amount=0.001&label=test";
+		assert_eq!(payment_uri_amount_msat(uri), Some(100_000_000));
+
+		let uri = "BITCOIN:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq?AMOUNT=1";
+		assert_eq!(payment_uri_amount_msat(uri), Some(100_000_000_000));
+
+		let uri = "bitcoin:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+		assert_eq!(payment_uri_amount_msat(uri), None);
+		assert_eq!(payment_uri_amount_msat("₿alice@example.com"), None);
+	}
+}
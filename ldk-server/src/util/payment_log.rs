@@ -0,0 +1,194 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+//! Log lines about payments, with as much detail as `log.payment_log_detail` allows.
+//!
+//! Lines are only ever built from identifiers and amounts. Preimages and payment secrets are never
+//! added, so they do not end up in the log at any level. The detail only governs the lines logged
+//! by ldk-server, not those of LDK Node and LDK, which share the log.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use ldk_node::payment::{PaymentDetails, PaymentKind};
+
+/// How much detail about payments is written to the log.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum PaymentLogDetail {
+	/// Only what happened, e.g. that a payment was received.
+	None,
+	/// What happened and the identifiers involved, such as payment ids and hashes.
+	#[default]
+	MetadataOnly,
+	/// Everything logged with `MetadataOnly`, plus amounts and fees.
+	Full,
+}
+
+impl FromStr for PaymentLogDetail {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim().to_ascii_lowercase().as_str() {
+			"none" => Ok(Self::None),
+			"metadata_only" => Ok(Self::MetadataOnly),
+			"full" => Ok(Self::Full),
+			other => Err(format!(
+				"Invalid payment log detail '{}', expected 'none', 'metadata_only' or 'full'",
+				other
+			)),
+		}
+	}
+}
+
+impl Display for PaymentLogDetail {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::None => write!(f, "none"),
+			Self::MetadataOnly => write!(f, "metadata_only"),
+			Self::Full => write!(f, "full"),
+		}
+	}
+}
+
+/// A log line about a payment, to which fields are only added if the configured detail allows.
+pub(crate) struct PaymentLogLine {
+	detail: PaymentLogDetail,
+	line: String,
+	has_fields: bool,
+}
+
+impl PaymentLogLine {
+	pub(crate) fn new(detail: PaymentLogDetail, event: &str) -> Self {
+		Self { detail, line: event.to_string(), has_fields: false }
+	}
+
+	/// Adds an identifier, such as a payment id or hash, or a count, unless the detail is `None`.
+	pub(crate) fn metadata(self, name: &str, value: impl Display) -> Self {
+		let include = self.detail != PaymentLogDetail::None;
+		self.field(include, name, value)
+	}
+
+	/// Adds an amount or fee, only if the detail is `Full`.
+	pub(crate) fn amount(self, name: &str, value: impl Display) -> Self {
+		let include = self.detail == PaymentLogDetail::Full;
+		self.field(include, name, value)
+	}
+
+	/// Adds the id and hash, or transaction id, of `payment` as metadata, and its amount and fee
+	/// as amounts.
+	pub(crate) fn payment(self, payment: &PaymentDetails) -> Self {
+		let mut line = self.metadata("id", payment.id);
+		match &payment.kind {
+			PaymentKind::Onchain { txid, .. } => line = line.metadata("txid", txid),
+			PaymentKind::Bolt11 { hash, .. } | PaymentKind::Spontaneous { hash, .. } => {
+				line = line.metadata("hash", hash);
+			},
+			PaymentKind::Bolt12Offer { hash, .. } | PaymentKind::Bolt12Refund { hash, .. } => {
+				if let Some(hash) = hash {
+					line = line.metadata("hash", hash);
+				}
+			},
+		}
+		if let Some(amount_msat) = payment.amount_msat {
+			line = line.amount("amount_msat", amount_msat);
+		}
+		if let Some(fee_paid_msat) = payment.fee_paid_msat {
+			line = line.amount("fee_paid_msat", fee_paid_msat);
+		}
+		line
+	}
+
+	fn field(mut self, include: bool, name: &str, value: impl Display) -> Self {
+		if include {
+			let separator = if self.has_fields { ", " } else { ": " };
+			self.line.push_str(&format!("{}{} {}", separator, name, value));
+			self.has_fields = true;
+		}
+		self
+	}
+}
+
+impl Display for PaymentLogLine {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.line)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use hex::DisplayHex;
+	use ldk_node::lightning::ln::channelmanager::PaymentId;
+	use ldk_node::lightning_types::payment::{PaymentHash, PaymentPreimage, PaymentSecret};
+	use ldk_node::payment::{PaymentDirection, PaymentStatus};
+
+	use super::*;
+
+	const DETAILS: [PaymentLogDetail; 3] =
+		[PaymentLogDetail::None, PaymentLogDetail::MetadataOnly, PaymentLogDetail::Full];
+
+	fn payment() -> PaymentDetails {
+		PaymentDetails {
+			id: PaymentId([1; 32]),
+			kind: PaymentKind::Bolt11 {
+				hash: PaymentHash([2; 32]),
+				preimage: Some(PaymentPreimage([3; 32])),
+				secret: Some(PaymentSecret([4; 32])),
+				counterparty_skimmed_fee_msat: None,
+			},
+			amount_msat: Some(123_456),
+			fee_paid_msat: Some(789),
+			direction: PaymentDirection::Outbound,
+			status: PaymentStatus::Succeeded,
+			latest_update_timestamp: 1_000,
+		}
+	}
+
+	fn log_line(detail: PaymentLogDetail) -> String {
+		PaymentLogLine::new(detail, "PAYMENT_SUCCESSFUL").payment(&payment()).to_string()
+	}
+
+	#[test]
+	fn preimages_and_secrets_are_never_logged() {
+		let preimage = [3u8; 32].to_lower_hex_string();
+		let secret = [4u8; 32].to_lower_hex_string();
+		for detail in DETAILS {
+			let line = log_line(detail);
+			assert!(!line.contains(&preimage), "{}", line);
+			assert!(!line.contains(&secret), "{}", line);
+		}
+	}
+
+	#[test]
+	fn detail_governs_logged_fields() {
+		let id = [1u8; 32].to_lower_hex_string();
+		let hash = [2u8; 32].to_lower_hex_string();
+
+		assert_eq!(log_line(PaymentLogDetail::None), "PAYMENT_SUCCESSFUL");
+		assert_eq!(
+			log_line(PaymentLogDetail::MetadataOnly),
+			format!("PAYMENT_SUCCESSFUL: id {}, hash {}", id, hash)
+		);
+		assert_eq!(
+			log_line(PaymentLogDetail::Full),
+			format!(
+				"PAYMENT_SUCCESSFUL: id {}, hash {}, amount_msat 123456, fee_paid_msat 789",
+				id, hash
+			)
+		);
+	}
+
+	#[test]
+	fn parses_detail_levels() {
+		for detail in DETAILS {
+			assert_eq!(PaymentLogDetail::from_str(&detail.to_string()), Ok(detail));
+		}
+		assert!(PaymentLogDetail::from_str("everything").is_err());
+		assert_eq!(PaymentLogDetail::default(), PaymentLogDetail::MetadataOnly);
+	}
+}
@@ -36,8 +36,8 @@ use ldk_server_grpc::types::pending_sweep_balance::BalanceType::{
 	AwaitingThresholdConfirmations, BroadcastAwaitingConfirmation, PendingBroadcast,
 };
 use ldk_server_grpc::types::{
-	bolt11_invoice_description, Channel, Feature, ForwardedPayment, HtlcLocator, OutPoint, Payment,
-	Peer,
+	bolt11_invoice_description, Channel, ClaimableBalance, ClaimableBalanceKind, Feature,
+	FormattedAmount, OutPoint, Payment, PaymentFeeBreakdown, Peer,
 };
 
 use crate::api::error::LdkServerError;
@@ -92,6 +92,8 @@ pub(crate) fn channel_to_proto(channel: ChannelDetails) -> Channel {
 			.forwarding_info
 			.as_ref()
 			.map(|info| info.cltv_expiry_delta as u32),
+		inbound_htlc_minimum_msat: channel.inbound_htlc_minimum_msat,
+		inbound_htlc_maximum_msat: channel.inbound_htlc_maximum_msat,
 	}
 }
 
@@ -133,6 +135,7 @@ pub(crate) fn payment_to_proto(payment: PaymentDetails) -> Payment {
 		status,
 		latest_update_timestamp,
 	} = payment;
+	let fee_breakdown = payment_fee_breakdown(&kind, direction, fee_paid_msat);
 
 	Payment {
 		id: id.to_string(),
@@ -149,9 +152,34 @@ pub(crate) fn payment_to_proto(payment: PaymentDetails) -> Payment {
 			PaymentStatus::Failed => ldk_server_grpc::types::PaymentStatus::Failed.into(),
 		},
 		latest_update_timestamp,
+		fee_breakdown,
 	}
 }
 
+/// Attributes the fees of a payment to routing nodes, an LSP or miners, depending on its kind and
+/// direction.
+fn payment_fee_breakdown(
+	kind: &PaymentKind, direction: PaymentDirection, fee_paid_msat: Option<u64>,
+) -> Option<PaymentFeeBreakdown> {
+	let breakdown = match (kind, direction) {
+		(PaymentKind::Onchain { .. }, PaymentDirection::Outbound) => {
+			PaymentFeeBreakdown { onchain_fee_msat: fee_paid_msat, ..Default::default() }
+		},
+		(PaymentKind::Onchain { .. }, PaymentDirection::Inbound) => return None,
+		(PaymentKind::Bolt11 { counterparty_skimmed_fee_msat, .. }, PaymentDirection::Inbound) => {
+			PaymentFeeBreakdown {
+				lsp_skimmed_fee_msat: *counterparty_skimmed_fee_msat,
+				..Default::default()
+			}
+		},
+		(_, PaymentDirection::Inbound) => return None,
+		(_, PaymentDirection::Outbound) => {
+			PaymentFeeBreakdown { routing_fee_msat: fee_paid_msat, ..Default::default() }
+		},
+	};
+	(breakdown != PaymentFeeBreakdown::default()).then_some(breakdown)
+}
+
 pub(crate) fn payment_kind_to_proto(
 	payment_kind: PaymentKind,
 ) -> ldk_server_grpc::types::PaymentKind {
@@ -349,6 +377,67 @@ pub(crate) fn lightning_balance_to_proto(
 	}
 }
 
+/// Flattens a balance as returned in `GetBalancesResponse::lightning_balances` into a
+/// [`ClaimableBalance`], so that both views of the claimable balances stay in agreement. Returns
+/// `None` if the balance has no type set.
+pub(crate) fn lightning_balance_to_claimable_balance(
+	lightning_balance: ldk_server_grpc::types::LightningBalance,
+) -> Option<ClaimableBalance> {
+	let (channel_id, counterparty_node_id, amount_sats, kind, maturity_height) =
+		match lightning_balance.balance_type? {
+			ClaimableOnChannelClose(balance) => (
+				balance.channel_id,
+				balance.counterparty_node_id,
+				balance.amount_satoshis,
+				ClaimableBalanceKind::ClaimableOnChannelClose,
+				None,
+			),
+			ClaimableAwaitingConfirmations(balance) => (
+				balance.channel_id,
+				balance.counterparty_node_id,
+				balance.amount_satoshis,
+				ClaimableBalanceKind::ClaimableAwaitingConfirmations,
+				Some(balance.confirmation_height),
+			),
+			ContentiousClaimable(balance) => (
+				balance.channel_id,
+				balance.counterparty_node_id,
+				balance.amount_satoshis,
+				ClaimableBalanceKind::ContentiousClaimable,
+				Some(balance.timeout_height),
+			),
+			MaybeTimeoutClaimableHtlc(balance) => (
+				balance.channel_id,
+				balance.counterparty_node_id,
+				balance.amount_satoshis,
+				ClaimableBalanceKind::MaybeTimeoutClaimableHtlc,
+				Some(balance.claimable_height),
+			),
+			MaybePreimageClaimableHtlc(balance) => (
+				balance.channel_id,
+				balance.counterparty_node_id,
+				balance.amount_satoshis,
+				ClaimableBalanceKind::MaybePreimageClaimableHtlc,
+				Some(balance.expiry_height),
+			),
+			CounterpartyRevokedOutputClaimable(balance) => (
+				balance.channel_id,
+				balance.counterparty_node_id,
+				balance.amount_satoshis,
+				ClaimableBalanceKind::CounterpartyRevokedOutputClaimable,
+				None,
+			),
+		};
+
+	Some(ClaimableBalance {
+		channel_id,
+		counterparty_node_id,
+		amount_sats,
+		kind: kind.into(),
+		maturity_height,
+	})
+}
+
 pub(crate) fn pending_sweep_balance_to_proto(
 	pending_sweep_balance: PendingSweepBalance,
 ) -> ldk_server_grpc::types::PendingSweepBalance {
@@ -396,26 +485,33 @@ pub(crate) fn pending_sweep_balance_to_proto(
 	}
 }
 
-pub(crate) fn forwarded_payment_to_proto(
-	prev_htlcs: Vec<HtlcLocator>, next_htlcs: Vec<HtlcLocator>, total_fee_earned_msat: Option<u64>,
-	skimmed_fee_msat: Option<u64>, claim_from_onchain_tx: bool,
-	outbound_amount_forwarded_msat: Option<u64>,
-) -> ForwardedPayment {
-	ForwardedPayment {
-		total_fee_earned_msat,
-		skimmed_fee_msat,
-		claim_from_onchain_tx,
-		outbound_amount_forwarded_msat,
-		prev_htlcs,
-		next_htlcs,
+/// The maximum length, in bytes, BOLT11 allows for an invoice description.
+pub(crate) const MAX_BOLT11_DESCRIPTION_LENGTH: usize = 639;
+
+/// Checks an invoice, offer or refund description against a maximum length in bytes.
+pub(crate) fn check_description_length(
+	description: &str, max_length: usize,
+) -> Result<(), LdkServerError> {
+	if description.len() > max_length {
+		return Err(LdkServerError::new(
+			InvalidRequestError,
+			format!("Description too long: {} bytes (max {})", description.len(), max_length),
+		));
 	}
+	Ok(())
 }
 
+/// Converts the requested invoice description, rejecting direct descriptions longer than
+/// `max_description_length` or the BOLT11 limit, whichever is lower.
 pub(crate) fn proto_to_bolt11_description(
 	description: Option<ldk_server_grpc::types::Bolt11InvoiceDescription>,
+	max_description_length: Option<usize>,
 ) -> Result<Bolt11InvoiceDescription, LdkServerError> {
 	Ok(match description.and_then(|d| d.kind) {
 		Some(bolt11_invoice_description::Kind::Direct(s)) => {
+			let max_length =
+				max_description_length.unwrap_or(usize::MAX).min(MAX_BOLT11_DESCRIPTION_LENGTH);
+			check_description_length(&s, max_length)?;
 			Bolt11InvoiceDescription::Direct(Description::new(s).map_err(|e| {
 				LdkServerError::new(
 					InvalidRequestError,
@@ -571,3 +667,104 @@ pub(crate) fn network_to_proto(network: Network) -> ldk_server_grpc::types::Netw
 		Network::Regtest => ProtoNetwork::Regtest,
 	}
 }
+
+/// Converts an amount in millisatoshis to satoshis and bitcoin, so callers don't have to.
+pub(crate) fn formatted_amount_to_proto(amount_msat: u64) -> FormattedAmount {
+	FormattedAmount { sats: format_decimal(amount_msat, 3), btc: format_decimal(amount_msat, 11) }
+}
+
+/// Formats `value / 10^decimals` exactly, leaving out trailing zeros of the fractional part.
+fn format_decimal(value: u64, decimals: u32) -> String {
+	let divisor = 10u64.pow(decimals);
+	let (whole, fraction) = (value / divisor, value % divisor);
+	if fraction == 0 {
+		return whole.to_string();
+	}
+	let fraction = format!("{:0width$}", fraction, width = decimals as usize);
+	format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_node::lightning_types::payment::PaymentHash;
+	use ldk_server_grpc::types::bolt11_invoice_description::Kind;
+
+	use super::*;
+
+	fn bolt11_kind(counterparty_skimmed_fee_msat: Option<u64>) -> PaymentKind {
+		PaymentKind::Bolt11 {
+			hash: PaymentHash([0; 32]),
+			preimage: None,
+			secret: None,
+			counterparty_skimmed_fee_msat,
+		}
+	}
+
+	#[test]
+	fn jit_receive_fee_is_attributed_to_the_lsp() {
+		let breakdown =
+			payment_fee_breakdown(&bolt11_kind(Some(1_500)), PaymentDirection::Inbound, None);
+		assert_eq!(
+			breakdown,
+			Some(PaymentFeeBreakdown { lsp_skimmed_fee_msat: Some(1_500), ..Default::default() })
+		);
+		let breakdown = payment_fee_breakdown(&bolt11_kind(None), PaymentDirection::Inbound, None);
+		assert_eq!(breakdown, None);
+	}
+
+	#[test]
+	fn routed_send_fee_is_attributed_to_routing() {
+		let breakdown =
+			payment_fee_breakdown(&bolt11_kind(None), PaymentDirection::Outbound, Some(42));
+		assert_eq!(
+			breakdown,
+			Some(PaymentFeeBreakdown { routing_fee_msat: Some(42), ..Default::default() })
+		);
+		// The fee is only known once the payment succeeded.
+		let breakdown = payment_fee_breakdown(&bolt11_kind(None), PaymentDirection::Outbound, None);
+		assert_eq!(breakdown, None);
+	}
+
+	fn direct_description(len: usize) -> Option<ldk_server_grpc::types::Bolt11InvoiceDescription> {
+		Some(ldk_server_grpc::types::Bolt11InvoiceDescription {
+			kind: Some(Kind::Direct("a".repeat(len))),
+		})
+	}
+
+	#[test]
+	fn bolt11_description_is_checked_against_the_lower_limit() {
+		assert!(proto_to_bolt11_description(direct_description(639), None).is_ok());
+		let err = proto_to_bolt11_description(direct_description(640), None).unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+		assert_eq!(err.message, "Description too long: 640 bytes (max 639)");
+
+		assert!(proto_to_bolt11_description(direct_description(140), Some(140)).is_ok());
+		let err = proto_to_bolt11_description(direct_description(141), Some(140)).unwrap_err();
+		assert_eq!(err.message, "Description too long: 141 bytes (max 140)");
+
+		// A configured maximum cannot raise the BOLT11 limit.
+		let err = proto_to_bolt11_description(direct_description(640), Some(1_000)).unwrap_err();
+		assert_eq!(err.message, "Description too long: 640 bytes (max 639)");
+	}
+
+	#[test]
+	fn description_length_is_counted_in_bytes() {
+		assert!(check_description_length("₿", 3).is_ok());
+		assert!(check_description_length("₿", 2).is_err());
+	}
+
+	#[test]
+	fn amounts_are_formatted_without_trailing_zeros() {
+		let amount = formatted_amount_to_proto(1_500_500);
+		assert_eq!(amount.sats, "1500.5");
+		assert_eq!(amount.btc, "0.000015005");
+
+		let amount = formatted_amount_to_proto(100_000_000_000);
+		assert_eq!(amount.sats, "100000000");
+		assert_eq!(amount.btc, "1");
+
+		let amount = formatted_amount_to_proto(1);
+		assert_eq!(amount.sats, "0.001");
+		assert_eq!(amount.btc, "0.00000000001");
+	}
+}
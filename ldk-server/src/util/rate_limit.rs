@@ -0,0 +1,160 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::util::auth::{required_scope, ApiKeyScope};
+
+/// How often a wallet may call the callback of a single LNURL-withdraw per minute.
+pub(crate) const LNURL_CALLBACKS_PER_MINUTE: u32 = 5;
+
+/// Limits the request rate of each API key with a token bucket.
+///
+/// A bucket holds up to a minute's worth of requests, so a key that was idle may send a burst of
+/// that size before being limited to the steady rate.
+pub(crate) struct RateLimiter {
+	requests_per_minute: u32,
+	buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+struct Bucket {
+	tokens: f64,
+	refilled_at: Instant,
+}
+
+impl RateLimiter {
+	pub(crate) fn new(requests_per_minute: u32) -> Self {
+		Self { requests_per_minute, buckets: Mutex::new(HashMap::new()) }
+	}
+
+	/// Takes a token from the bucket of the given API key. If the bucket is empty, returns how
+	/// long it takes until the next token is available.
+	pub(crate) fn acquire(&self, api_key_name: &str, now: Instant) -> Result<(), Duration> {
+		let capacity = f64::from(self.requests_per_minute);
+		let tokens_per_sec = capacity / 60.0;
+		let mut buckets = self.buckets.lock().unwrap();
+		let bucket = buckets
+			.entry(api_key_name.to_string())
+			.or_insert(Bucket { tokens: capacity, refilled_at: now });
+
+		let elapsed_secs = now.saturating_duration_since(bucket.refilled_at).as_secs_f64();
+		bucket.tokens = (bucket.tokens + elapsed_secs * tokens_per_sec).min(capacity);
+		bucket.refilled_at = now;
+
+		if bucket.tokens >= 1.0 {
+			bucket.tokens -= 1.0;
+			Ok(())
+		} else {
+			Err(Duration::from_secs_f64((1.0 - bucket.tokens) / tokens_per_sec))
+		}
+	}
+}
+
+/// The rate limits requests are subject to, by whether the requested method only reads state.
+///
+/// The unauthenticated LNURL-withdraw callbacks are always limited, per withdraw.
+pub(crate) struct RateLimits {
+	reads: Option<RateLimiter>,
+	sends: Option<RateLimiter>,
+	lnurl_callbacks: RateLimiter,
+}
+
+impl Default for RateLimits {
+	fn default() -> Self {
+		Self::new(None, None)
+	}
+}
+
+impl RateLimits {
+	pub(crate) fn new(
+		read_requests_per_minute: Option<u32>, send_requests_per_minute: Option<u32>,
+	) -> Self {
+		Self {
+			reads: read_requests_per_minute.map(RateLimiter::new),
+			sends: send_requests_per_minute.map(RateLimiter::new),
+			lnurl_callbacks: RateLimiter::new(LNURL_CALLBACKS_PER_MINUTE),
+		}
+	}
+
+	/// Checks whether the given API key may call the given RPC method now. Methods that need the
+	/// [`ApiKeyScope::ReadOnly`] scope count as reads, all others as sends.
+	///
+	/// Returns how long to wait before retrying if the limit is exceeded.
+	pub(crate) fn check(
+		&self, api_key_name: &str, method: &str, now: Instant,
+	) -> Result<(), Duration> {
+		let limiter = match required_scope(method) {
+			ApiKeyScope::ReadOnly => &self.reads,
+			ApiKeyScope::Send | ApiKeyScope::Admin => &self.sends,
+		};
+		match limiter {
+			Some(limiter) => limiter.acquire(api_key_name, now),
+			None => Ok(()),
+		}
+	}
+
+	/// Checks whether the callback of the given LNURL-withdraw may be called now.
+	///
+	/// Only withdraws known to exist must be checked, so that requests for made up ids do not grow
+	/// the tracked buckets. Returns how long to wait before retrying if the limit is exceeded.
+	pub(crate) fn check_lnurl_callback(
+		&self, withdraw_id: &str, now: Instant,
+	) -> Result<(), Duration> {
+		self.lnurl_callbacks.acquire(withdraw_id, now)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::endpoints::{BOLT11_SEND_PATH, LIST_PAYMENTS_PATH};
+
+	use super::*;
+
+	#[test]
+	fn bursts_beyond_the_limit_are_rejected_until_refilled() {
+		let limits = RateLimits::new(None, Some(3));
+		let now = Instant::now();
+
+		for _ in 0..3 {
+			assert!(limits.check("alice", BOLT11_SEND_PATH, now).is_ok());
+		}
+		let retry_after = limits.check("alice", BOLT11_SEND_PATH, now).unwrap_err();
+		assert_eq!(retry_after, Duration::from_secs(20));
+
+		// Other keys and reads are limited separately.
+		assert!(limits.check("bob", BOLT11_SEND_PATH, now).is_ok());
+		assert!(limits.check("alice", LIST_PAYMENTS_PATH, now).is_ok());
+
+		assert!(limits.check("alice", BOLT11_SEND_PATH, now + Duration::from_secs(20)).is_ok());
+		assert!(limits.check("alice", BOLT11_SEND_PATH, now + Duration::from_secs(20)).is_err());
+	}
+
+	#[test]
+	fn no_limits_by_default() {
+		let limits = RateLimits::default();
+		let now = Instant::now();
+		for _ in 0..1_000 {
+			assert!(limits.check("alice", BOLT11_SEND_PATH, now).is_ok());
+		}
+	}
+
+	#[test]
+	fn lnurl_callbacks_are_limited_per_withdraw() {
+		let limits = RateLimits::default();
+		let now = Instant::now();
+
+		for _ in 0..LNURL_CALLBACKS_PER_MINUTE {
+			assert!(limits.check_lnurl_callback("ab", now).is_ok());
+		}
+		assert_eq!(limits.check_lnurl_callback("ab", now), Err(Duration::from_secs(12)));
+		assert!(limits.check_lnurl_callback("cd", now).is_ok());
+	}
+}
@@ -0,0 +1,109 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::HashMap;
+
+use ldk_server_grpc::api::GetChannelRoutingStatsResponse;
+use ldk_server_grpc::types::{ChannelRoutingStats, ForwardedPayment};
+
+/// Aggregates forwarded payments into per-channel routing statistics.
+///
+/// Each forward is attributed to the channel of its first outgoing HTLC, as that is the channel
+/// whose forwarding fee was charged. Attributing every forward to exactly one channel keeps the
+/// per-channel numbers summing up to the totals.
+#[derive(Default)]
+pub(crate) struct RoutingStatsAggregator {
+	channel_stats: HashMap<String, ChannelRoutingStats>,
+}
+
+impl RoutingStatsAggregator {
+	pub(crate) fn record(&mut self, forwarded_payment: &ForwardedPayment) {
+		let channel_id = match forwarded_payment.next_htlcs.first() {
+			Some(htlc) => &htlc.channel_id,
+			None => return,
+		};
+		let stats = self.channel_stats.entry(channel_id.clone()).or_insert_with(|| {
+			ChannelRoutingStats { channel_id: channel_id.clone(), ..Default::default() }
+		});
+		stats.fees_earned_msat += forwarded_payment.total_fee_earned_msat.unwrap_or(0);
+		stats.forwards_count += 1;
+		stats.amount_forwarded_msat +=
+			forwarded_payment.outbound_amount_forwarded_msat.unwrap_or(0);
+	}
+
+	pub(crate) fn into_response(self) -> GetChannelRoutingStatsResponse {
+		let mut channel_stats: Vec<_> = self.channel_stats.into_values().collect();
+		channel_stats.sort_by(|a, b| {
+			b.fees_earned_msat
+				.cmp(&a.fees_earned_msat)
+				.then_with(|| a.channel_id.cmp(&b.channel_id))
+		});
+
+		GetChannelRoutingStatsResponse {
+			total_fees_earned_msat: channel_stats.iter().map(|s| s.fees_earned_msat).sum(),
+			total_forwards_count: channel_stats.iter().map(|s| s.forwards_count).sum(),
+			total_amount_forwarded_msat: channel_stats
+				.iter()
+				.map(|s| s.amount_forwarded_msat)
+				.sum(),
+			channel_stats,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::types::HtlcLocator;
+
+	use super::*;
+
+	fn forward(channel_ids: &[&str], fee_msat: Option<u64>, amount_msat: u64) -> ForwardedPayment {
+		ForwardedPayment {
+			total_fee_earned_msat: fee_msat,
+			outbound_amount_forwarded_msat: Some(amount_msat),
+			next_htlcs: channel_ids
+				.iter()
+				.map(|channel_id| HtlcLocator {
+					channel_id: channel_id.to_string(),
+					..Default::default()
+				})
+				.collect(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn per_channel_stats_reconcile_with_totals() {
+		let forwards = [
+			forward(&["aa"], Some(1_000), 100_000),
+			forward(&["bb"], Some(5_000), 400_000),
+			forward(&["aa"], Some(2_000), 200_000),
+			forward(&["cc", "aa"], None, 50_000),
+		];
+
+		let mut aggregator = RoutingStatsAggregator::default();
+		for forwarded_payment in &forwards {
+			aggregator.record(forwarded_payment);
+		}
+		let response = aggregator.into_response();
+
+		let channel_ids: Vec<_> =
+			response.channel_stats.iter().map(|s| s.channel_id.as_str()).collect();
+		assert_eq!(channel_ids, vec!["bb", "aa", "cc"]);
+		assert_eq!(response.channel_stats[1].fees_earned_msat, 3_000);
+		assert_eq!(response.channel_stats[1].forwards_count, 2);
+		assert_eq!(response.channel_stats[1].amount_forwarded_msat, 300_000);
+		assert_eq!(response.channel_stats[2].fees_earned_msat, 0);
+
+		let total_fees: u64 = forwards.iter().filter_map(|f| f.total_fee_earned_msat).sum();
+		assert_eq!(response.total_fees_earned_msat, total_fees);
+		assert_eq!(response.total_forwards_count, forwards.len() as u64);
+		assert_eq!(response.total_amount_forwarded_msat, 750_000);
+	}
+}